@@ -55,7 +55,9 @@ const get_state_command = {
         console.log("Place State:");
         console.log("AcctType     :", state.acct_type);
         console.log("Owner        :", state.owner.toBase58());
-        console.log("Frozen?      :", state.is_frozen);
+        console.log("Canvas       :", state.canvas_id.toNumber());
+        console.log("Frozen?      :", state.isFrozen());
+        console.log("Paused       :", state.paused);
         console.log("Pbrush price :", state.paintbrush_price.toNumber());
         console.log("Pbrush cool  :", state.paintbrush_cooldown.toNumber());
         console.log("Bomb price   :", state.bomb_price.toNumber());
@@ -1,4 +1,4 @@
-import { sendAndConfirmTransaction, Transaction, ConfirmOptions, PublicKey } from "@solana/web3.js";
+import { sendAndConfirmTransaction, Transaction, ConfirmOptions } from "@solana/web3.js";
 import { inspect } from "util";
 import { ArgumentsCamelCase, Argv } from "yargs";
 import {
@@ -152,7 +152,7 @@ const rent_check_command = {
     },
 };
 
-type UpdatePlaceStateArgs = { new_owner?: string } & { is_frozen?: boolean } & {
+type UpdatePlaceStateArgs = { paused?: number } & {
     paintbrush_price?: number;
 } & { paintbrush_cooldown?: number } & { bomb_price?: number } & KeynameOptionArgs;
 
@@ -161,14 +161,9 @@ const update_place_state_command = {
     description: "Update the place state account data",
     builder: (args: Argv): Argv<UpdatePlaceStateArgs> => {
         return applyKeynameOption(args)
-            .option("new_owner", {
-                description: "(Optional) change the owner to this public key",
-                type: "string",
-                required: false,
-            })
-            .option("is_frozen", {
-                description: "(Optional) change the is_frozen state of the place",
-                type: "boolean",
+            .option("paused", {
+                description: "(Optional) replace the paused operations bitmask, 16 freezes the place",
+                type: "number",
                 required: false,
             })
             .option("paintbrush_price", {
@@ -188,8 +183,8 @@ const update_place_state_command = {
             });
     },
     handler: async (args: ArgumentsCamelCase<UpdatePlaceStateArgs>) => {
-        let new_owner = args.new_owner ? new PublicKey(args.new_owner!) : null;
-        let is_frozen = args.is_frozen ? args.is_frozen! : null;
+        // 0 unpauses everything, so it can't be treated as missing
+        let paused = args.paused !== undefined ? args.paused! : null;
         let paintbrush_price = args.paintbrush_price ? new BN(args.paintbrush_price!) : null;
         let paintbrush_cooldown = args.paintbrush_cooldown
             ? new BN(args.paintbrush_cooldown!)
@@ -202,8 +197,7 @@ const update_place_state_command = {
 
         let update_place_ix = await placeClient.placeProgram.updatePlaceState({
             current_owner: key.publicKey,
-            new_owner: new_owner,
-            is_frozen: is_frozen,
+            paused: paused,
             paintbrush_price: paintbrush_price,
             paintbrush_cooldown: paintbrush_cooldown,
             bomb_price: bomb_price,
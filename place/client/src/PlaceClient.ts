@@ -139,11 +139,20 @@ export class PlaceClient {
         },
     };
 
+    // every account of a canvas stores its id right after the account type and version
+    private canvasAccountsFilter: GetProgramAccountsFilter;
+
     constructor(programVersion: PlaceProgramVersion, endpoint: PlaceRpcEndpoint) {
         this.programVersion = programVersion;
         this.rpcEndpoint = endpoint;
         this.connection = new Connection(endpoint.url);
         this.placeProgram = new PlaceProgram(programVersion);
+        this.canvasAccountsFilter = {
+            memcmp: {
+                bytes: base58.encode(this.placeProgram.canvasId.toArrayLike(Buffer, "le", 8)),
+                offset: 1 + 1,
+            },
+        };
 
         let bufSize = this.pallete.length * 4;
         let buf = Buffer.alloc(bufSize);
@@ -384,7 +393,7 @@ export class PlaceClient {
                 }
             },
             "processed",
-            [this.patchAccountsFilter]
+            [this.patchAccountsFilter, this.canvasAccountsFilter]
         );
     }
 
@@ -620,7 +629,7 @@ export class PlaceClient {
 
     public async fetchPlaceStateAccount(): Promise<PlaceStateData> {
         extendBorsh();
-        const config = { filters: [this.placeStateAccountsFilter] };
+        const config = { filters: [this.placeStateAccountsFilter, this.canvasAccountsFilter] };
         let results = await this.connection.getProgramAccounts(this.placeProgram.programId, config);
         if (results.length !== 1) {
             console.warn("nexpected number of state accounts: ", results.length);
@@ -633,7 +642,7 @@ export class PlaceClient {
     public async fetchAllPatches() {
         extendBorsh();
         this.unsubscribeFromPatchUpdates();
-        const config = { filters: [this.patchAccountsFilter] };
+        const config = { filters: [this.patchAccountsFilter, this.canvasAccountsFilter] };
         let allAccounts = await this.connection.getProgramAccounts(
             this.placeProgram.programId,
             config
@@ -921,7 +930,9 @@ export class PlaceClient {
                     {
                         memcmp: {
                             bytes: mintPubkey,
-                            offset: 1 + 1 + 8 + 8,
+                            // token_mint_pda comes after acct_type, version, canvas_id,
+                            // gameplay_type, created_at_slot and random_seed
+                            offset: 1 + 1 + 8 + 1 + 8 + 8,
                         },
                     },
                 ],
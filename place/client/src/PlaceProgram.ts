@@ -7,7 +7,10 @@ import {
 import { Program, TokenAccount } from "@metaplex-foundation/mpl-core";
 import { SetPixelArgsData } from "./instructions/setPixel";
 import { InitPatchArgsData } from "./instructions/initPatch";
-import { UpdatePlaceStateArgsData } from "./instructions/updatePlaceState";
+import {
+    UpdatePlaceStateArgs,
+    UpdatePlaceStateArgsData,
+} from "./instructions/updatePlaceState";
 import { GameplayTokenType } from "./accounts";
 
 import BN from "bn.js";
@@ -15,7 +18,11 @@ import { randomBytes } from "crypto";
 import { ASSOCIATED_TOKEN_PROGRAM_ID, Token } from "@solana/spl-token";
 import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import { Metadata, MetadataProgram } from "@metaplex-foundation/mpl-token-metadata";
-import { PurchaseGameplayTokenArgsData } from "./instructions/purchaseGameplayToken";
+import {
+    AllowlistProofData,
+    PaymentMethod,
+    PurchaseGameplayTokenArgsData,
+} from "./instructions/purchaseGameplayToken";
 import { InitMintArgsData } from "./instructions/initMint";
 import { ClaimTokensArgsData } from "./instructions/claimTokens";
 import { PlaceProgramVersion } from "./Config";
@@ -23,6 +30,8 @@ import { PlaceProgramVersion } from "./Config";
 export const PLACE_HEIGHT_PX = 1000;
 export const PLACE_WIDTH_PX = 1000;
 export const PATCH_SIZE_PX = 20;
+// the canvas the place was first deployed as, see LEGACY_CANVAS_ID in state.rs
+export const DEFAULT_CANVAS_ID = new BN(0);
 
 export type InitTokenMintParams = {
     owner: PublicKey;
@@ -45,19 +54,19 @@ export type InitPatchParams = {
     payer: PublicKey;
 };
 
-export type UpdatePlaceStateParams = {
+// Fields left out keep their current value
+export type UpdatePlaceStateParams = Partial<Omit<UpdatePlaceStateArgs, "canvas_id">> & {
     current_owner: PublicKey;
-    new_owner: PublicKey | null;
-    is_frozen: boolean | null;
-    paintbrush_price: BN | null;
-    paintbrush_cooldown: BN | null;
-    bomb_price: BN | null;
 };
 
 export type PurchaseGameplayTokenParams = {
     payer: PublicKey;
     token_type: GameplayTokenType;
     desired_price: BN;
+    // paying with place tokens needs the place token mint to be initialized
+    payment?: PaymentMethod;
+    // only needed to purchase during the allowlist phase
+    allowlist_proof?: AllowlistProofData;
 };
 
 export type ClaimTokensParams = {
@@ -84,30 +93,41 @@ export class PlaceProgram {
     static readonly PATCH_PDA_PREFIX = "patch";
     static readonly PLACE_STATE_PDA_PREFIX = "place";
     static readonly PLACE_TOKEN_MINT_PDA_PREFIX = "tokes";
+    static readonly PLACE_TREASURY_PDA_PREFIX = "treasury";
+    static readonly PATCH_ATTRIBUTION_PDA_PREFIX = "attr";
+    static readonly ALLOWLIST_RECEIPT_PDA_PREFIX = "allowlist";
     static readonly GAMEPLAY_TOKEN_META_PREFIX = "game";
     static readonly GAMEPLAY_TOKEN_MINT_PREFIX = "mint";
 
     public readonly programVersion: PlaceProgramVersion;
     public readonly programId: PublicKey;
+    // every account of a canvas uses its id as a pda seed
+    public readonly canvasId: BN;
 
-    public constructor(version: PlaceProgramVersion) {
+    public constructor(version: PlaceProgramVersion, canvasId: BN = DEFAULT_CANVAS_ID) {
         this.programVersion = version;
         this.programId = new PublicKey(version.programId);
+        this.canvasId = canvasId;
     }
 
     public async initPatch(params: InitPatchParams) {
         let data = InitPatchArgsData.serialize({
+            canvas_id: this.canvasId,
             xPatch: params.xPatch,
             yPatch: params.yPatch,
         });
 
         let patchPda = await this.findPatchPda(params.xPatch, params.yPatch);
+        let place_state_pda = await this.findPlaceStatePda();
+        let patchAttributionPda = await this.findPatchAttributionPda(params.xPatch, params.yPatch);
 
         return new TransactionInstruction({
             keys: [
                 { pubkey: params.payer, isSigner: true, isWritable: true },
                 { pubkey: patchPda, isSigner: false, isWritable: true },
                 { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+                { pubkey: place_state_pda, isSigner: false, isWritable: false },
+                { pubkey: patchAttributionPda, isSigner: false, isWritable: true },
             ],
             programId: this.programId,
             data: data,
@@ -115,7 +135,7 @@ export class PlaceProgram {
     }
 
     public async claimTokens(params: ClaimTokensParams) {
-        let data = ClaimTokensArgsData.serialize({});
+        let data = ClaimTokensArgsData.serialize({ canvas_id: this.canvasId });
         let gameplay_token_meta_pda = await this.findGameplayMetaPda(
             params.gameplay_token_random_seed
         );
@@ -128,7 +148,7 @@ export class PlaceProgram {
                 { pubkey: params.gameplay_token_ata, isSigner: false, isWritable: false },
                 { pubkey: place_token_mint_pda, isSigner: false, isWritable: true },
                 { pubkey: params.dest_ata, isSigner: false, isWritable: true },
-                { pubkey: place_state_pda, isSigner: false, isWritable: false },
+                { pubkey: place_state_pda, isSigner: false, isWritable: true },
                 { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
             ],
             programId: this.programId,
@@ -146,27 +166,58 @@ export class PlaceProgram {
             params.payer
         );
         let gameplay_token_mpl_pda = await Metadata.getPDA(gameplay_token_mint_pda);
+        let treasury_pda = await this.findTreasuryPda();
+        let allowlist_receipt_pda = await this.findAllowlistReceiptPda(params.payer);
+        let payment = params.payment ?? PaymentMethod.Lamports;
 
         let data = PurchaseGameplayTokenArgsData.serialize({
+            canvas_id: this.canvasId,
             token_type: params.token_type,
             random_seed: randomSeed,
             desired_price: params.desired_price,
+            payment: payment,
+            allowlist_proof: params.allowlist_proof ?? null,
         });
 
+        let keys = [
+            { pubkey: params.payer, isSigner: true, isWritable: true },
+            { pubkey: place_state_pda, isSigner: false, isWritable: true },
+            { pubkey: gameplay_meta_pda, isSigner: false, isWritable: true },
+            { pubkey: gameplay_token_mint_pda, isSigner: false, isWritable: true },
+            { pubkey: gameplay_token_ata, isSigner: false, isWritable: true },
+            { pubkey: gameplay_token_mpl_pda, isSigner: false, isWritable: true },
+            { pubkey: MetadataProgram.PUBKEY, isSigner: false, isWritable: false },
+            { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+            { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+            { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+            { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+            { pubkey: treasury_pda, isSigner: false, isWritable: true },
+            { pubkey: allowlist_receipt_pda, isSigner: false, isWritable: true },
+        ];
+
+        if (payment === PaymentMethod.PlaceTokens) {
+            let place_token_mint_pda = await this.findPlaceTokenMintPda();
+            let payer_place_token_ata = await this.findGameplayTokenMintAta(
+                place_token_mint_pda,
+                params.payer
+            );
+            // the treasury is a pda, so its ata is owned off curve
+            let treasury_place_token_ata = await Token.getAssociatedTokenAddress(
+                ASSOCIATED_TOKEN_PROGRAM_ID,
+                TOKEN_PROGRAM_ID,
+                place_token_mint_pda,
+                treasury_pda,
+                true
+            );
+            keys.push(
+                { pubkey: place_token_mint_pda, isSigner: false, isWritable: true },
+                { pubkey: payer_place_token_ata, isSigner: false, isWritable: true },
+                { pubkey: treasury_place_token_ata, isSigner: false, isWritable: true }
+            );
+        }
+
         return new TransactionInstruction({
-            keys: [
-                { pubkey: params.payer, isSigner: true, isWritable: true },
-                { pubkey: place_state_pda, isSigner: false, isWritable: false },
-                { pubkey: gameplay_meta_pda, isSigner: false, isWritable: true },
-                { pubkey: gameplay_token_mint_pda, isSigner: false, isWritable: true },
-                { pubkey: gameplay_token_ata, isSigner: false, isWritable: true },
-                { pubkey: gameplay_token_mpl_pda, isSigner: false, isWritable: true },
-                { pubkey: MetadataProgram.PUBKEY, isSigner: false, isWritable: false },
-                { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
-                { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
-                { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-                { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
-            ],
+            keys: keys,
             programId: this.programId,
             data: data,
         });
@@ -186,18 +237,30 @@ export class PlaceProgram {
     public async updatePlaceState(params: UpdatePlaceStateParams) {
         let place_state_pda = await this.findPlaceStatePda();
         let data = UpdatePlaceStateArgsData.serialize({
-            new_owner: params.new_owner,
-            is_frozen: params.is_frozen,
-            paintbrush_price: params.paintbrush_price,
-            paintbrush_cooldown: params.paintbrush_cooldown,
-            bomb_price: params.bomb_price,
+            canvas_id: this.canvasId,
+            paintbrush_price: params.paintbrush_price ?? null,
+            paintbrush_cooldown: params.paintbrush_cooldown ?? null,
+            bomb_price: params.bomb_price ?? null,
+            shield_price: params.shield_price ?? null,
+            shield_duration: params.shield_duration ?? null,
+            price_curve: params.price_curve ?? null,
+            retire_refund_bps: params.retire_refund_bps ?? null,
+            upgrade_schedule: params.upgrade_schedule ?? null,
+            place_token_pricing: params.place_token_pricing ?? null,
+            bomb_cooldown: params.bomb_cooldown ?? null,
+            shield_cooldown: params.shield_cooldown ?? null,
+            paintbrush_rewards: params.paintbrush_rewards ?? null,
+            bomb_rewards: params.bomb_rewards ?? null,
+            shield_rewards: params.shield_rewards ?? null,
+            emission_schedule: params.emission_schedule ?? null,
+            purchase_phases: params.purchase_phases ?? null,
+            paused: params.paused ?? null,
         });
 
         return new TransactionInstruction({
             keys: [
                 { pubkey: params.current_owner, isSigner: true, isWritable: true },
                 { pubkey: place_state_pda, isSigner: false, isWritable: true },
-                { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
             ],
             programId: this.programId,
             data: data,
@@ -209,12 +272,12 @@ export class PlaceProgram {
         let place_token_mint_pda = await this.findPlaceTokenMintPda();
         let place_token_mpl_meta_pda = await Metadata.getPDA(place_token_mint_pda);
 
-        let data = InitMintArgsData.serialize({});
+        let data = InitMintArgsData.serialize({ canvas_id: this.canvasId });
 
         return new TransactionInstruction({
             keys: [
                 { pubkey: params.owner, isSigner: true, isWritable: true },
-                { pubkey: place_state_pda, isSigner: false, isWritable: false },
+                { pubkey: place_state_pda, isSigner: false, isWritable: true },
                 { pubkey: place_token_mint_pda, isSigner: false, isWritable: true },
                 { pubkey: place_token_mpl_meta_pda, isSigner: false, isWritable: true },
                 { pubkey: MetadataProgram.PUBKEY, isSigner: false, isWritable: false },
@@ -232,6 +295,7 @@ export class PlaceProgram {
 
         console.log("Setting Patch Coords: ", patchCoords, "to color: ", params.pixel);
         let data = SetPixelArgsData.serialize({
+            canvas_id: this.canvasId,
             x: patchCoords.xPatch,
             y: patchCoords.yPatch,
             x_offset: patchCoords.xOffset,
@@ -240,6 +304,11 @@ export class PlaceProgram {
         });
 
         let patchPda = await this.findPatchPda(patchCoords.xPatch, patchCoords.yPatch);
+        let place_state_pda = await this.findPlaceStatePda();
+        let patchAttributionPda = await this.findPatchAttributionPda(
+            patchCoords.xPatch,
+            patchCoords.yPatch
+        );
 
         return new TransactionInstruction({
             keys: [
@@ -249,6 +318,8 @@ export class PlaceProgram {
                 { pubkey: params.gameplay_token_meta_acct, isSigner: false, isWritable: true },
                 { pubkey: params.gameplay_token_acct, isSigner: false, isWritable: false },
                 { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+                { pubkey: place_state_pda, isSigner: false, isWritable: false },
+                { pubkey: patchAttributionPda, isSigner: false, isWritable: true },
             ],
             programId: this.programId,
             data: data,
@@ -256,19 +327,33 @@ export class PlaceProgram {
     }
 
     public async findPatchPda(xPatch: number, yPatch: number): Promise<PublicKey> {
-        let xBuf = Buffer.alloc(1);
-        xBuf.writeUInt8(xPatch);
-        let yBuf = Buffer.alloc(1);
-        yBuf.writeUInt8(yPatch);
+        let seeds = Buffer.concat([
+            Buffer.from(PlaceProgram.PATCH_PDA_PREFIX),
+            this.canvasIdSeed(),
+            this.patchCoordsSeed(xPatch, yPatch),
+        ]);
+
+        let result = await PublicKey.findProgramAddress([seeds], this.programId);
+        return result[0];
+    }
 
-        let seeds = Buffer.concat([Buffer.from(PlaceProgram.PATCH_PDA_PREFIX), xBuf, yBuf]);
+    public async findPatchAttributionPda(xPatch: number, yPatch: number): Promise<PublicKey> {
+        let seeds = Buffer.concat([
+            Buffer.from(PlaceProgram.PATCH_PDA_PREFIX),
+            this.canvasIdSeed(),
+            this.patchCoordsSeed(xPatch, yPatch),
+            Buffer.from(PlaceProgram.PATCH_ATTRIBUTION_PDA_PREFIX),
+        ]);
 
         let result = await PublicKey.findProgramAddress([seeds], this.programId);
         return result[0];
     }
 
     public async findPlaceStatePda(): Promise<PublicKey> {
-        let seeds = Buffer.concat([Buffer.from(PlaceProgram.PLACE_STATE_PDA_PREFIX)]);
+        let seeds = Buffer.concat([
+            Buffer.from(PlaceProgram.PLACE_STATE_PDA_PREFIX),
+            this.canvasIdSeed(),
+        ]);
 
         let result = await PublicKey.findProgramAddress([seeds], this.programId);
         return result[0];
@@ -277,6 +362,7 @@ export class PlaceProgram {
     public async findPlaceTokenMintPda(): Promise<PublicKey> {
         let seeds = Buffer.concat([
             Buffer.from(PlaceProgram.PLACE_STATE_PDA_PREFIX),
+            this.canvasIdSeed(),
             Buffer.from(PlaceProgram.PLACE_TOKEN_MINT_PDA_PREFIX),
         ]);
 
@@ -284,6 +370,28 @@ export class PlaceProgram {
         return result[0];
     }
 
+    public async findTreasuryPda(): Promise<PublicKey> {
+        let seeds = Buffer.concat([
+            Buffer.from(PlaceProgram.PLACE_STATE_PDA_PREFIX),
+            this.canvasIdSeed(),
+            Buffer.from(PlaceProgram.PLACE_TREASURY_PDA_PREFIX),
+        ]);
+
+        let result = await PublicKey.findProgramAddress([seeds], this.programId);
+        return result[0];
+    }
+
+    public async findAllowlistReceiptPda(wallet: PublicKey): Promise<PublicKey> {
+        let seeds = Buffer.concat([
+            Buffer.from(PlaceProgram.ALLOWLIST_RECEIPT_PDA_PREFIX),
+            this.canvasIdSeed(),
+        ]);
+
+        // a pubkey is a full 32 byte seed on its own
+        let result = await PublicKey.findProgramAddress([seeds, wallet.toBuffer()], this.programId);
+        return result[0];
+    }
+
     public async findGameplayMetaPda(randomSeed: BN): Promise<PublicKey> {
         let seeds = Buffer.concat([
             Buffer.from(PlaceProgram.GAMEPLAY_TOKEN_META_PREFIX),
@@ -320,6 +428,19 @@ export class PlaceProgram {
         ); // TODO(will): what are the implications of this?
     }
 
+    private canvasIdSeed(): Buffer {
+        // NOTE(will): can't use .toBuffer("le") here
+        // https://github.com/indutny/bn.js/issues/227
+        return this.canvasId.toArrayLike(Buffer, "le", 8);
+    }
+
+    private patchCoordsSeed(xPatch: number, yPatch: number): Buffer {
+        let buf = Buffer.alloc(2);
+        buf.writeUInt8(xPatch, 0);
+        buf.writeUInt8(yPatch, 1);
+        return buf;
+    }
+
     private computePatchCoords(x: number, y: number): PixelPatchCoords {
         if (x > PLACE_WIDTH_PX || y > PLACE_HEIGHT_PX || x < 0 || y < 0) {
             throw Error("Invalid pixel coordinates: " + x + "," + y);
@@ -5,8 +5,13 @@ import { Account, AnyPublicKey, Borsh } from "@metaplex-foundation/mpl-core";
 import { Schema } from "borsh";
 import { extendBorsh } from "../utils/borsh";
 
+// Duplicate of the reserved lengths in state.rs
+export const GAMEPLAY_TOKEN_META_RESERVED_LEN = 32;
+
 type GameplayTokenMetaArgs = {
     acct_type: PlaceAccountType;
+    version: number;
+    canvas_id: BN;
     gameplay_type: GameplayTokenType;
     created_at_slot: BN;
     random_seed: BN;
@@ -14,12 +19,19 @@ type GameplayTokenMetaArgs = {
     update_allowed_slot: BN;
     cooldown_duration: BN;
     place_tokens_owed: number;
+    purchase_price: BN;
+    upgrade_level: number;
+    is_banned: boolean;
+    season: number;
+    reserved: Uint8Array;
 };
 
 export class GameplayTokenMetaData extends Borsh.Data<GameplayTokenMetaArgs> {
     static readonly SCHEMA: Schema = new Map([
         ...GameplayTokenMetaData.struct([
             ["acct_type", "u8"],
+            ["version", "u8"],
+            ["canvas_id", "u64"],
             ["gameplay_type", "u8"],
             ["created_at_slot", "u64"],
             ["random_seed", "u64"],
@@ -27,10 +39,17 @@ export class GameplayTokenMetaData extends Borsh.Data<GameplayTokenMetaArgs> {
             ["update_allowed_slot", "u64"],
             ["cooldown_duration", "u64"],
             ["place_tokens_owed", "u32"],
+            ["purchase_price", "u64"],
+            ["upgrade_level", "u8"],
+            ["is_banned", "boolean"],
+            ["season", "u32"],
+            ["reserved", [GAMEPLAY_TOKEN_META_RESERVED_LEN]],
         ]),
     ]);
 
     acct_type: PlaceAccountType;
+    version: number;
+    canvas_id: BN;
     gameplay_type: GameplayTokenType;
     created_at_slot: BN;
     random_seed: BN;
@@ -38,11 +57,18 @@ export class GameplayTokenMetaData extends Borsh.Data<GameplayTokenMetaArgs> {
     update_allowed_slot: BN;
     cooldown_duration: BN;
     place_tokens_owed: number;
+    purchase_price: BN;
+    upgrade_level: number;
+    is_banned: boolean;
+    season: number;
+    reserved: Uint8Array;
 
     constructor(args: GameplayTokenMetaArgs) {
         super(args);
 
         this.acct_type = args.acct_type;
+        this.version = args.version;
+        this.canvas_id = args.canvas_id;
         this.gameplay_type = args.gameplay_type;
         this.created_at_slot = args.created_at_slot;
         this.random_seed = args.random_seed;
@@ -50,6 +76,11 @@ export class GameplayTokenMetaData extends Borsh.Data<GameplayTokenMetaArgs> {
         this.update_allowed_slot = args.update_allowed_slot;
         this.cooldown_duration = args.cooldown_duration;
         this.place_tokens_owed = args.place_tokens_owed;
+        this.purchase_price = args.purchase_price;
+        this.upgrade_level = args.upgrade_level;
+        this.is_banned = args.is_banned;
+        this.season = args.season;
+        this.reserved = args.reserved;
     }
 }
 
@@ -1,33 +1,93 @@
 import { Borsh } from "@metaplex-foundation/mpl-core";
 import { Schema } from "borsh";
 import { extendBorsh } from "../utils/borsh";
-import { PlaceAccountType } from "./types";
+import { PixelFormat, PlaceAccountType } from "./types";
+import BN from "bn.js";
+
+// Duplicate of the reserved lengths in state.rs
+export const PATCH_RESERVED_LEN = 32;
+
+export type PatchShieldArgs = {
+    x_min: number;
+    y_min: number;
+    x_max: number;
+    y_max: number;
+    expires_at_slot: BN;
+};
+
+export class PatchShieldData extends Borsh.Data<PatchShieldArgs> {
+    static readonly SCHEMA: Schema = new Map([
+        ...PatchShieldData.struct([
+            ["x_min", "u8"],
+            ["y_min", "u8"],
+            ["x_max", "u8"],
+            ["y_max", "u8"],
+            ["expires_at_slot", "u64"],
+        ]),
+    ]);
+
+    x_min: number;
+    y_min: number;
+    x_max: number;
+    y_max: number;
+    expires_at_slot: BN;
+
+    constructor(args: PatchShieldArgs) {
+        super(args);
+        this.x_min = args.x_min;
+        this.y_min = args.y_min;
+        this.x_max = args.x_max;
+        this.y_max = args.y_max;
+        this.expires_at_slot = args.expires_at_slot;
+    }
+}
 
 export type PatchArgs = {
+    version: number;
+    canvas_id: BN;
+    format: PixelFormat;
     x: number;
     y: number;
+    shield: PatchShieldData;
+    reserved: Uint8Array;
     pixels: Buffer;
 };
 
 export class PatchData extends Borsh.Data<PatchArgs> {
     static readonly SCHEMA: Schema = new Map([
+        ...PatchShieldData.SCHEMA,
         ...PatchData.struct([
             ["acct_type", "u8"],
+            ["version", "u8"],
+            ["canvas_id", "u64"],
+            ["format", "u8"],
             ["x", "u8"],
             ["y", "u8"],
+            ["shield", PatchShieldData],
+            ["reserved", [PATCH_RESERVED_LEN]],
             ["pixels", "vecU8"],
         ]),
     ]);
 
     acct_type: PlaceAccountType = PlaceAccountType.Patch;
+    version: number;
+    canvas_id: BN;
+    format: PixelFormat;
     x: number;
     y: number;
+    shield: PatchShieldData;
+    reserved: Uint8Array;
     pixels: Buffer;
 
     constructor(args: PatchArgs) {
         super(args);
+        this.version = args.version;
+        this.canvas_id = args.canvas_id;
+        this.format = args.format;
         this.x = args.x;
         this.y = args.y;
+        this.shield = args.shield;
+        this.reserved = args.reserved;
         this.pixels = args.pixels;
     }
 }
@@ -1,42 +1,401 @@
 import { Borsh } from "@metaplex-foundation/mpl-core";
 import { PublicKey } from "@solana/web3.js";
 import { Schema } from "borsh";
-import { PlaceAccountType } from "./types";
+import { PAUSE_FROZEN, PixelFormat, PlaceAccountType, PriceCurveKind } from "./types";
 import BN from "bn.js";
 
+// Duplicate of the reserved lengths in state.rs
+export const PLACE_STATE_RESERVED_LEN = 64;
+
+export type PriceCurveArgs = {
+    kind: PriceCurveKind;
+    step: BN;
+    decay_slots: BN;
+    max_price: BN;
+};
+
+export class PriceCurveData extends Borsh.Data<PriceCurveArgs> {
+    static readonly SCHEMA: Schema = new Map([
+        ...PriceCurveData.struct([
+            ["kind", "u8"],
+            ["step", "u64"],
+            ["decay_slots", "u64"],
+            ["max_price", "u64"],
+        ]),
+    ]);
+
+    kind: PriceCurveKind;
+    step: BN;
+    decay_slots: BN;
+    max_price: BN;
+
+    constructor(args: PriceCurveArgs) {
+        super(args);
+        this.kind = args.kind;
+        this.step = args.step;
+        this.decay_slots = args.decay_slots;
+        this.max_price = args.max_price;
+    }
+}
+
+export type GameplayTokenSalesArgs = {
+    recent_sales: BN;
+    last_sale_slot: BN;
+};
+
+export class GameplayTokenSalesData extends Borsh.Data<GameplayTokenSalesArgs> {
+    static readonly SCHEMA: Schema = new Map([
+        ...GameplayTokenSalesData.struct([
+            ["recent_sales", "u64"],
+            ["last_sale_slot", "u64"],
+        ]),
+    ]);
+
+    recent_sales: BN;
+    last_sale_slot: BN;
+
+    constructor(args: GameplayTokenSalesArgs) {
+        super(args);
+        this.recent_sales = args.recent_sales;
+        this.last_sale_slot = args.last_sale_slot;
+    }
+}
+
+export type UpgradeScheduleArgs = {
+    base_cost: BN;
+    cooldown_step: BN;
+    min_cooldown: BN;
+    max_level: number;
+};
+
+export class UpgradeScheduleData extends Borsh.Data<UpgradeScheduleArgs> {
+    static readonly SCHEMA: Schema = new Map([
+        ...UpgradeScheduleData.struct([
+            ["base_cost", "u64"],
+            ["cooldown_step", "u64"],
+            ["min_cooldown", "u64"],
+            ["max_level", "u8"],
+        ]),
+    ]);
+
+    base_cost: BN;
+    cooldown_step: BN;
+    min_cooldown: BN;
+    max_level: number;
+
+    constructor(args: UpgradeScheduleArgs) {
+        super(args);
+        this.base_cost = args.base_cost;
+        this.cooldown_step = args.cooldown_step;
+        this.min_cooldown = args.min_cooldown;
+        this.max_level = args.max_level;
+    }
+}
+
+export type PlaceTokenPricingArgs = {
+    paintbrush_price: BN;
+    bomb_price: BN;
+    shield_price: BN;
+    burn_payments: boolean;
+};
+
+export class PlaceTokenPricingData extends Borsh.Data<PlaceTokenPricingArgs> {
+    static readonly SCHEMA: Schema = new Map([
+        ...PlaceTokenPricingData.struct([
+            ["paintbrush_price", "u64"],
+            ["bomb_price", "u64"],
+            ["shield_price", "u64"],
+            ["burn_payments", "boolean"],
+        ]),
+    ]);
+
+    paintbrush_price: BN;
+    bomb_price: BN;
+    shield_price: BN;
+    burn_payments: boolean;
+
+    constructor(args: PlaceTokenPricingArgs) {
+        super(args);
+        this.paintbrush_price = args.paintbrush_price;
+        this.bomb_price = args.bomb_price;
+        this.shield_price = args.shield_price;
+        this.burn_payments = args.burn_payments;
+    }
+}
+
+export type GameplayTokenRewardsArgs = {
+    purchase_bonus: number;
+    use_reward: number;
+};
+
+export class GameplayTokenRewardsData extends Borsh.Data<GameplayTokenRewardsArgs> {
+    static readonly SCHEMA: Schema = new Map([
+        ...GameplayTokenRewardsData.struct([
+            ["purchase_bonus", "u32"],
+            ["use_reward", "u32"],
+        ]),
+    ]);
+
+    purchase_bonus: number;
+    use_reward: number;
+
+    constructor(args: GameplayTokenRewardsArgs) {
+        super(args);
+        this.purchase_bonus = args.purchase_bonus;
+        this.use_reward = args.use_reward;
+    }
+}
+
+export type EmissionScheduleArgs = {
+    max_supply: BN;
+    window_slots: BN;
+    tokens_per_window: BN;
+};
+
+export class EmissionScheduleData extends Borsh.Data<EmissionScheduleArgs> {
+    static readonly SCHEMA: Schema = new Map([
+        ...EmissionScheduleData.struct([
+            ["max_supply", "u64"],
+            ["window_slots", "u64"],
+            ["tokens_per_window", "u64"],
+        ]),
+    ]);
+
+    max_supply: BN;
+    window_slots: BN;
+    tokens_per_window: BN;
+
+    constructor(args: EmissionScheduleArgs) {
+        super(args);
+        this.max_supply = args.max_supply;
+        this.window_slots = args.window_slots;
+        this.tokens_per_window = args.tokens_per_window;
+    }
+}
+
+export type PlaceRolesArgs = {
+    pricing_admin: PublicKey;
+    freeze_admin: PublicKey;
+    moderator: PublicKey;
+    treasurer: PublicKey;
+};
+
+export class PlaceRolesData extends Borsh.Data<PlaceRolesArgs> {
+    static readonly SCHEMA: Schema = new Map([
+        ...PlaceRolesData.struct([
+            ["pricing_admin", "pubkey"],
+            ["freeze_admin", "pubkey"],
+            ["moderator", "pubkey"],
+            ["treasurer", "pubkey"],
+        ]),
+    ]);
+
+    pricing_admin: PublicKey;
+    freeze_admin: PublicKey;
+    moderator: PublicKey;
+    treasurer: PublicKey;
+
+    constructor(args: PlaceRolesArgs) {
+        super(args);
+        this.pricing_admin = args.pricing_admin;
+        this.freeze_admin = args.freeze_admin;
+        this.moderator = args.moderator;
+        this.treasurer = args.treasurer;
+    }
+}
+
+export type PurchasePhasesArgs = {
+    allowlist_root: Uint8Array;
+    allowlist_start_slot: BN;
+    allowlist_end_slot: BN;
+};
+
+export class PurchasePhasesData extends Borsh.Data<PurchasePhasesArgs> {
+    static readonly SCHEMA: Schema = new Map([
+        ...PurchasePhasesData.struct([
+            ["allowlist_root", [32]],
+            ["allowlist_start_slot", "u64"],
+            ["allowlist_end_slot", "u64"],
+        ]),
+    ]);
+
+    allowlist_root: Uint8Array;
+    allowlist_start_slot: BN;
+    allowlist_end_slot: BN;
+
+    constructor(args: PurchasePhasesArgs) {
+        super(args);
+        this.allowlist_root = args.allowlist_root;
+        this.allowlist_start_slot = args.allowlist_start_slot;
+        this.allowlist_end_slot = args.allowlist_end_slot;
+    }
+}
+
 export type PlaceStateArgs = {
+    version: number;
+    canvas_id: BN;
     owner: PublicKey;
-    is_frozen: boolean;
+    frozen_at_slot: BN;
     paintbrush_price: BN;
     paintbrush_cooldown: BN;
     bomb_price: BN;
+    width_px: number;
+    height_px: number;
+    patch_size_px: number;
+    pixel_format: PixelFormat;
+    shield_price: BN;
+    shield_duration: BN;
+    price_curve: PriceCurveData;
+    paintbrush_sales: GameplayTokenSalesData;
+    bomb_sales: GameplayTokenSalesData;
+    shield_sales: GameplayTokenSalesData;
+    retire_refund_bps: number;
+    upgrade_schedule: UpgradeScheduleData;
+    place_token_pricing: PlaceTokenPricingData;
+    bomb_cooldown: BN;
+    shield_cooldown: BN;
+    paintbrush_rewards: GameplayTokenRewardsData;
+    bomb_rewards: GameplayTokenRewardsData;
+    shield_rewards: GameplayTokenRewardsData;
+    emission_schedule: EmissionScheduleData;
+    emission_window: BN;
+    emitted_in_window: BN;
+    roles: PlaceRolesData;
+    pending_owner: PublicKey;
+    purchase_phases: PurchasePhasesData;
+    season: number;
+    patches_to_reset: number;
+    paused: number;
+    reserved: Uint8Array;
 };
 
 export class PlaceStateData extends Borsh.Data<PlaceStateArgs> {
     static readonly SCHEMA: Schema = new Map([
+        ...PriceCurveData.SCHEMA,
+        ...GameplayTokenSalesData.SCHEMA,
+        ...UpgradeScheduleData.SCHEMA,
+        ...PlaceTokenPricingData.SCHEMA,
+        ...GameplayTokenRewardsData.SCHEMA,
+        ...EmissionScheduleData.SCHEMA,
+        ...PlaceRolesData.SCHEMA,
+        ...PurchasePhasesData.SCHEMA,
         ...PlaceStateData.struct([
             ["acct_type", "u8"],
+            ["version", "u8"],
+            ["canvas_id", "u64"],
             ["owner", "pubkey"],
-            ["is_frozen", "boolean"],
+            ["frozen_at_slot", "u64"],
             ["paintbrush_price", "u64"],
             ["paintbrush_cooldown", "u64"],
             ["bomb_price", "u64"],
+            ["width_px", "u16"],
+            ["height_px", "u16"],
+            ["patch_size_px", "u8"],
+            ["pixel_format", "u8"],
+            ["shield_price", "u64"],
+            ["shield_duration", "u64"],
+            ["price_curve", PriceCurveData],
+            ["paintbrush_sales", GameplayTokenSalesData],
+            ["bomb_sales", GameplayTokenSalesData],
+            ["shield_sales", GameplayTokenSalesData],
+            ["retire_refund_bps", "u16"],
+            ["upgrade_schedule", UpgradeScheduleData],
+            ["place_token_pricing", PlaceTokenPricingData],
+            ["bomb_cooldown", "u64"],
+            ["shield_cooldown", "u64"],
+            ["paintbrush_rewards", GameplayTokenRewardsData],
+            ["bomb_rewards", GameplayTokenRewardsData],
+            ["shield_rewards", GameplayTokenRewardsData],
+            ["emission_schedule", EmissionScheduleData],
+            ["emission_window", "u64"],
+            ["emitted_in_window", "u64"],
+            ["roles", PlaceRolesData],
+            ["pending_owner", "pubkey"],
+            ["purchase_phases", PurchasePhasesData],
+            ["season", "u32"],
+            ["patches_to_reset", "u32"],
+            ["paused", "u8"],
+            ["reserved", [PLACE_STATE_RESERVED_LEN]],
         ]),
     ]);
 
     acct_type: PlaceAccountType = PlaceAccountType.PlaceState;
+    version: number;
+    canvas_id: BN;
     owner: PublicKey;
-    is_frozen: boolean;
+    frozen_at_slot: BN;
     paintbrush_price: BN;
     paintbrush_cooldown: BN;
     bomb_price: BN;
+    width_px: number;
+    height_px: number;
+    patch_size_px: number;
+    pixel_format: PixelFormat;
+    shield_price: BN;
+    shield_duration: BN;
+    price_curve: PriceCurveData;
+    paintbrush_sales: GameplayTokenSalesData;
+    bomb_sales: GameplayTokenSalesData;
+    shield_sales: GameplayTokenSalesData;
+    retire_refund_bps: number;
+    upgrade_schedule: UpgradeScheduleData;
+    place_token_pricing: PlaceTokenPricingData;
+    bomb_cooldown: BN;
+    shield_cooldown: BN;
+    paintbrush_rewards: GameplayTokenRewardsData;
+    bomb_rewards: GameplayTokenRewardsData;
+    shield_rewards: GameplayTokenRewardsData;
+    emission_schedule: EmissionScheduleData;
+    emission_window: BN;
+    emitted_in_window: BN;
+    roles: PlaceRolesData;
+    pending_owner: PublicKey;
+    purchase_phases: PurchasePhasesData;
+    season: number;
+    patches_to_reset: number;
+    paused: number;
+    reserved: Uint8Array;
 
     constructor(args: PlaceStateArgs) {
         super(args);
+        this.version = args.version;
+        this.canvas_id = args.canvas_id;
         this.owner = args.owner;
-        this.is_frozen = args.is_frozen;
+        this.frozen_at_slot = args.frozen_at_slot;
         this.paintbrush_price = args.paintbrush_price;
         this.paintbrush_cooldown = args.paintbrush_cooldown;
         this.bomb_price = args.bomb_price;
+        this.width_px = args.width_px;
+        this.height_px = args.height_px;
+        this.patch_size_px = args.patch_size_px;
+        this.pixel_format = args.pixel_format;
+        this.shield_price = args.shield_price;
+        this.shield_duration = args.shield_duration;
+        this.price_curve = args.price_curve;
+        this.paintbrush_sales = args.paintbrush_sales;
+        this.bomb_sales = args.bomb_sales;
+        this.shield_sales = args.shield_sales;
+        this.retire_refund_bps = args.retire_refund_bps;
+        this.upgrade_schedule = args.upgrade_schedule;
+        this.place_token_pricing = args.place_token_pricing;
+        this.bomb_cooldown = args.bomb_cooldown;
+        this.shield_cooldown = args.shield_cooldown;
+        this.paintbrush_rewards = args.paintbrush_rewards;
+        this.bomb_rewards = args.bomb_rewards;
+        this.shield_rewards = args.shield_rewards;
+        this.emission_schedule = args.emission_schedule;
+        this.emission_window = args.emission_window;
+        this.emitted_in_window = args.emitted_in_window;
+        this.roles = args.roles;
+        this.pending_owner = args.pending_owner;
+        this.purchase_phases = args.purchase_phases;
+        this.season = args.season;
+        this.patches_to_reset = args.patches_to_reset;
+        this.paused = args.paused;
+        this.reserved = args.reserved;
+    }
+
+    public isFrozen(): boolean {
+        return (this.paused & PAUSE_FROZEN) !== 0;
     }
 }
@@ -1,11 +1,37 @@
+// Duplicate in state.rs
 export enum PlaceAccountType {
     Uninitialized,
     PlaceState,
     Patch,
     GameplayTokenMeta,
+    CanvasSnapshot,
+    PatchAttribution,
+    RegionNftMeta,
+    AllowlistReceipt,
+    SeasonArchive,
 }
 
 export enum GameplayTokenType {
     PaintBrush,
     Bomb,
+    Shield,
 }
+
+export enum PixelFormat {
+    Indexed8,
+    Rgb565,
+}
+
+export enum PriceCurveKind {
+    Flat,
+    Linear,
+    Exponential,
+}
+
+// Bits of PlaceState.paused, duplicate of PauseFlags in state.rs
+export const PAUSE_NONE = 0;
+export const PAUSE_PURCHASES = 1 << 0;
+export const PAUSE_PAINTING = 1 << 1;
+export const PAUSE_CLAIMS = 1 << 2;
+export const PAUSE_PATCH_CREATION = 1 << 3;
+export const PAUSE_FROZEN = 1 << 4;
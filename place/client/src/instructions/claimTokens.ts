@@ -1,17 +1,25 @@
 import { Borsh } from "@metaplex-foundation/mpl-core";
 import { Schema } from "borsh";
 import { PlaceInstruction } from "./types";
+import BN from "bn.js";
 
-export type ClaimTokensArgs = {};
+export type ClaimTokensArgs = {
+    canvas_id: BN;
+};
 
 export class ClaimTokensArgsData extends Borsh.Data<ClaimTokensArgs> {
     static readonly SCHEMA: Schema = new Map([
-        ...ClaimTokensArgsData.struct([["instruction", "u8"]]),
+        ...ClaimTokensArgsData.struct([
+            ["instruction", "u8"],
+            ["canvas_id", "u64"],
+        ]),
     ]);
 
     instruction: number = PlaceInstruction.ClaimTokens;
+    canvas_id: BN;
 
     constructor(args: ClaimTokensArgs) {
         super(args);
+        this.canvas_id = args.canvas_id;
     }
 }
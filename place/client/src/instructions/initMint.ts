@@ -1,15 +1,25 @@
 import { Borsh } from "@metaplex-foundation/mpl-core";
 import { Schema } from "borsh";
 import { PlaceInstruction } from "./types";
+import BN from "bn.js";
 
-export type InitMintArgs = {};
+export type InitMintArgs = {
+    canvas_id: BN;
+};
 
 export class InitMintArgsData extends Borsh.Data<InitMintArgs> {
-    static readonly SCHEMA: Schema = new Map([...InitMintArgsData.struct([["instruction", "u8"]])]);
+    static readonly SCHEMA: Schema = new Map([
+        ...InitMintArgsData.struct([
+            ["instruction", "u8"],
+            ["canvas_id", "u64"],
+        ]),
+    ]);
 
     instruction: number = PlaceInstruction.InitMint;
+    canvas_id: BN;
 
     constructor(args: InitMintArgs) {
         super(args);
+        this.canvas_id = args.canvas_id;
     }
 }
@@ -1,8 +1,10 @@
 import { Borsh } from "@metaplex-foundation/mpl-core";
 import { Schema } from "borsh";
 import { PlaceInstruction } from "./types";
+import BN from "bn.js";
 
 export type InitPatchArgs = {
+    canvas_id: BN;
     xPatch: number;
     yPatch: number;
 };
@@ -11,17 +13,20 @@ export class InitPatchArgsData extends Borsh.Data<InitPatchArgs> {
     static readonly SCHEMA: Schema = new Map([
         ...InitPatchArgsData.struct([
             ["instruction", "u8"],
+            ["canvas_id", "u64"],
             ["xPatch", "u8"],
             ["yPatch", "u8"],
         ]),
     ]);
 
     instruction: number = PlaceInstruction.InitPatch;
+    canvas_id: BN;
     xPatch: number;
     yPatch: number;
 
     constructor(args: InitPatchArgs) {
         super(args);
+        this.canvas_id = args.canvas_id;
         this.xPatch = args.xPatch;
         this.yPatch = args.yPatch;
     }
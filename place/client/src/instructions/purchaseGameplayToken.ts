@@ -4,31 +4,73 @@ import { GameplayTokenType } from "../accounts";
 import { PlaceInstruction } from "./types";
 import BN from "bn.js";
 
+// Duplicate in instruction.rs
+export enum PaymentMethod {
+    Lamports,
+    PlaceTokens,
+}
+
+export type AllowlistProofArgs = {
+    max_purchases: number;
+    proof: Uint8Array[];
+};
+
+export class AllowlistProofData extends Borsh.Data<AllowlistProofArgs> {
+    static readonly SCHEMA: Schema = new Map([
+        ...AllowlistProofData.struct([
+            ["max_purchases", "u16"],
+            ["proof", [[32]]],
+        ]),
+    ]);
+
+    max_purchases: number;
+    proof: Uint8Array[];
+
+    constructor(args: AllowlistProofArgs) {
+        super(args);
+        this.max_purchases = args.max_purchases;
+        this.proof = args.proof;
+    }
+}
+
 export type PurchaseGameplayTokenArgs = {
+    canvas_id: BN;
     token_type: GameplayTokenType;
     random_seed: BN;
     desired_price: BN;
+    payment: PaymentMethod;
+    allowlist_proof: AllowlistProofData | null;
 };
 
 export class PurchaseGameplayTokenArgsData extends Borsh.Data<PurchaseGameplayTokenArgs> {
     static readonly SCHEMA: Schema = new Map([
+        ...AllowlistProofData.SCHEMA,
         ...PurchaseGameplayTokenArgsData.struct([
             ["instruction", "u8"],
+            ["canvas_id", "u64"],
             ["token_type", "u8"],
             ["random_seed", "u64"],
             ["desired_price", "u64"],
+            ["payment", "u8"],
+            ["allowlist_proof", { kind: "option", type: AllowlistProofData }],
         ]),
     ]);
 
     instruction: number = PlaceInstruction.PurchaseGameplayToken;
+    canvas_id: BN;
     token_type: GameplayTokenType;
     random_seed: BN;
     desired_price: BN;
+    payment: PaymentMethod;
+    allowlist_proof: AllowlistProofData | null;
 
     constructor(args: PurchaseGameplayTokenArgs) {
         super(args);
+        this.canvas_id = args.canvas_id;
         this.token_type = args.token_type;
         this.random_seed = args.random_seed;
         this.desired_price = args.desired_price;
+        this.payment = args.payment;
+        this.allowlist_proof = args.allowlist_proof;
     }
 }
@@ -1,12 +1,15 @@
 import { Borsh } from "@metaplex-foundation/mpl-core";
 import { Schema } from "borsh";
 import { PlaceInstruction } from "./types";
+import BN from "bn.js";
 
 export type SetPixelArgs = {
+    canvas_id: BN;
     x: number;
     y: number;
     x_offset: number;
     y_offset: number;
+    // a palette index or an RGB565 color, depending on the pixel format of the place
     pixel: number;
 };
 
@@ -14,15 +17,17 @@ export class SetPixelArgsData extends Borsh.Data<SetPixelArgs> {
     static readonly SCHEMA: Schema = new Map([
         ...SetPixelArgsData.struct([
             ["instruction", "u8"],
+            ["canvas_id", "u64"],
             ["x", "u8"],
             ["y", "u8"],
             ["x_offset", "u8"],
             ["y_offset", "u8"],
-            ["pixel", "u8"],
+            ["pixel", "u16"],
         ]),
     ]);
 
     instruction: number = PlaceInstruction.SetPixel;
+    canvas_id: BN;
     x: number;
     y: number;
     x_offset: number;
@@ -31,6 +36,7 @@ export class SetPixelArgsData extends Borsh.Data<SetPixelArgs> {
 
     constructor(args: SetPixelArgs) {
         super(args);
+        this.canvas_id = args.canvas_id;
         this.x = args.x;
         this.y = args.y;
         this.x_offset = args.x_offset;
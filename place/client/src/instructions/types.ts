@@ -6,4 +6,26 @@ export enum PlaceInstruction {
     SetPixel,
     InitMint,
     ClaimTokens,
+    DetonateBomb,
+    SetPixels,
+    SealCanvas,
+    MintRegionNft,
+    ActivateShield,
+    WithdrawTreasury,
+    SweepGameplayTokens,
+    RetireGameplayToken,
+    UpgradeGameplayToken,
+    ClaimTokensBatch,
+    GrantRole,
+    RevokeRole,
+    ProposeOwner,
+    AcceptOwnership,
+    CancelOwnershipTransfer,
+    InitializePlace,
+    MigrateAccount,
+    ClearRegion,
+    BanGameplayToken,
+    StartNewSeason,
+    ResetPatches,
+    InitPatchAttribution,
 }
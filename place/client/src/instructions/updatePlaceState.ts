@@ -1,42 +1,109 @@
 import { Borsh } from "@metaplex-foundation/mpl-core";
-import { PublicKey } from "@solana/web3.js";
 import { Schema } from "borsh";
 import { PlaceInstruction } from "./types";
+import {
+    EmissionScheduleData,
+    GameplayTokenRewardsData,
+    PlaceTokenPricingData,
+    PriceCurveData,
+    PurchasePhasesData,
+    UpgradeScheduleData,
+} from "../accounts";
 import BN from "bn.js";
 
+// Fields left as null keep their current value
 export type UpdatePlaceStateArgs = {
-    new_owner: PublicKey | null;
-    is_frozen: boolean | null;
+    canvas_id: BN;
     paintbrush_price: BN | null;
     paintbrush_cooldown: BN | null;
     bomb_price: BN | null;
+    shield_price: BN | null;
+    shield_duration: BN | null;
+    price_curve: PriceCurveData | null;
+    retire_refund_bps: number | null;
+    upgrade_schedule: UpgradeScheduleData | null;
+    place_token_pricing: PlaceTokenPricingData | null;
+    bomb_cooldown: BN | null;
+    shield_cooldown: BN | null;
+    paintbrush_rewards: GameplayTokenRewardsData | null;
+    bomb_rewards: GameplayTokenRewardsData | null;
+    shield_rewards: GameplayTokenRewardsData | null;
+    emission_schedule: EmissionScheduleData | null;
+    purchase_phases: PurchasePhasesData | null;
+    // the full set of paused operations, see the PAUSE_* bits
+    paused: number | null;
 };
 
 export class UpdatePlaceStateArgsData extends Borsh.Data<UpdatePlaceStateArgs> {
     static readonly SCHEMA: Schema = new Map([
+        ...PriceCurveData.SCHEMA,
+        ...UpgradeScheduleData.SCHEMA,
+        ...PlaceTokenPricingData.SCHEMA,
+        ...GameplayTokenRewardsData.SCHEMA,
+        ...EmissionScheduleData.SCHEMA,
+        ...PurchasePhasesData.SCHEMA,
         ...UpdatePlaceStateArgsData.struct([
             ["instruction", "u8"],
-            ["new_owner", { kind: "option", type: "pubkey" }],
-            ["is_frozen", { kind: "option", type: "boolean" }],
+            ["canvas_id", "u64"],
             ["paintbrush_price", { kind: "option", type: "u64" }],
             ["paintbrush_cooldown", { kind: "option", type: "u64" }],
             ["bomb_price", { kind: "option", type: "u64" }],
+            ["shield_price", { kind: "option", type: "u64" }],
+            ["shield_duration", { kind: "option", type: "u64" }],
+            ["price_curve", { kind: "option", type: PriceCurveData }],
+            ["retire_refund_bps", { kind: "option", type: "u16" }],
+            ["upgrade_schedule", { kind: "option", type: UpgradeScheduleData }],
+            ["place_token_pricing", { kind: "option", type: PlaceTokenPricingData }],
+            ["bomb_cooldown", { kind: "option", type: "u64" }],
+            ["shield_cooldown", { kind: "option", type: "u64" }],
+            ["paintbrush_rewards", { kind: "option", type: GameplayTokenRewardsData }],
+            ["bomb_rewards", { kind: "option", type: GameplayTokenRewardsData }],
+            ["shield_rewards", { kind: "option", type: GameplayTokenRewardsData }],
+            ["emission_schedule", { kind: "option", type: EmissionScheduleData }],
+            ["purchase_phases", { kind: "option", type: PurchasePhasesData }],
+            ["paused", { kind: "option", type: "u8" }],
         ]),
     ]);
 
     instruction: number = PlaceInstruction.UpdatePlaceState;
-    new_owner: PublicKey | null;
-    is_frozen: boolean | null;
+    canvas_id: BN;
     paintbrush_price: BN | null;
     paintbrush_cooldown: BN | null;
     bomb_price: BN | null;
+    shield_price: BN | null;
+    shield_duration: BN | null;
+    price_curve: PriceCurveData | null;
+    retire_refund_bps: number | null;
+    upgrade_schedule: UpgradeScheduleData | null;
+    place_token_pricing: PlaceTokenPricingData | null;
+    bomb_cooldown: BN | null;
+    shield_cooldown: BN | null;
+    paintbrush_rewards: GameplayTokenRewardsData | null;
+    bomb_rewards: GameplayTokenRewardsData | null;
+    shield_rewards: GameplayTokenRewardsData | null;
+    emission_schedule: EmissionScheduleData | null;
+    purchase_phases: PurchasePhasesData | null;
+    paused: number | null;
 
     constructor(args: UpdatePlaceStateArgs) {
         super(args);
-        this.new_owner = args.new_owner;
-        this.is_frozen = args.is_frozen;
+        this.canvas_id = args.canvas_id;
         this.paintbrush_price = args.paintbrush_price;
         this.paintbrush_cooldown = args.paintbrush_cooldown;
         this.bomb_price = args.bomb_price;
+        this.shield_price = args.shield_price;
+        this.shield_duration = args.shield_duration;
+        this.price_curve = args.price_curve;
+        this.retire_refund_bps = args.retire_refund_bps;
+        this.upgrade_schedule = args.upgrade_schedule;
+        this.place_token_pricing = args.place_token_pricing;
+        this.bomb_cooldown = args.bomb_cooldown;
+        this.shield_cooldown = args.shield_cooldown;
+        this.paintbrush_rewards = args.paintbrush_rewards;
+        this.bomb_rewards = args.bomb_rewards;
+        this.shield_rewards = args.shield_rewards;
+        this.emission_schedule = args.emission_schedule;
+        this.purchase_phases = args.purchase_phases;
+        this.paused = args.paused;
     }
 }
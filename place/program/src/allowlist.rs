@@ -0,0 +1,95 @@
+use solana_program::{hash::hashv, pubkey::Pubkey};
+
+// Leaves and inner nodes are hashed with different prefixes, so an inner node can never
+// be passed off as a leaf
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+pub type MerkleNode = [u8; 32];
+
+/// Leaf committing to a wallet and the number of gameplay tokens it can buy
+/// during the allowlist phase
+pub fn allowlist_leaf(wallet: &Pubkey, max_purchases: u16) -> MerkleNode {
+    hashv(&[LEAF_PREFIX, wallet.as_ref(), &max_purchases.to_le_bytes()]).to_bytes()
+}
+
+/// Children are sorted before hashing, so proofs don't need to say which side they are on
+fn parent_node(a: &MerkleNode, b: &MerkleNode) -> MerkleNode {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+/// Checks that the leaf is in the tree with the given root
+pub fn verify_allowlist_proof(root: &MerkleNode, leaf: MerkleNode, proof: &[MerkleNode]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf, |node, sibling| parent_node(&node, sibling));
+    computed == *root
+}
+
+/// Merkle tree over the allowlist, built off chain to get the root for
+/// `PlaceState::purchase_phases` and the proof each wallet passes to PurchaseGameplayToken
+pub struct AllowlistTree {
+    // leaves first, the last layer holds just the root
+    layers: Vec<Vec<MerkleNode>>,
+}
+
+impl AllowlistTree {
+    pub fn new(entries: &[(Pubkey, u16)]) -> AllowlistTree {
+        let leaves: Vec<MerkleNode> = entries
+            .iter()
+            .map(|(wallet, max_purchases)| allowlist_leaf(wallet, *max_purchases))
+            .collect();
+
+        let mut layers = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            // an odd node out is carried up to the next layer unchanged
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => parent_node(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(next);
+        }
+
+        AllowlistTree { layers }
+    }
+
+    /// Tree where every wallet can buy the same number of gameplay tokens
+    pub fn from_wallets(wallets: &[Pubkey], max_purchases: u16) -> AllowlistTree {
+        let entries: Vec<(Pubkey, u16)> = wallets
+            .iter()
+            .map(|wallet| (*wallet, max_purchases))
+            .collect();
+        Self::new(&entries)
+    }
+
+    /// All zeroes for an empty tree, which leaves the allowlist disabled
+    pub fn root(&self) -> MerkleNode {
+        self.layers
+            .last()
+            .and_then(|layer| layer.first())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Proof for the entry at the given index in the list the tree was built from
+    pub fn proof(&self, index: usize) -> Vec<MerkleNode> {
+        let mut proof = vec![];
+        let mut index = index;
+        for layer in &self.layers[..self.layers.len() - 1] {
+            let sibling = index ^ 1;
+            if sibling < layer.len() {
+                proof.push(layer[sibling]);
+            }
+            index /= 2;
+        }
+
+        proof
+    }
+}
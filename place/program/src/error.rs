@@ -96,6 +96,204 @@ pub enum PlaceError {
 
     #[error("No tokens available to claim")]
     NoTokensToBeClaimed, // 30
+
+    #[error("Gameplay token type can not be used for this instruction")]
+    InvalidGameplayTokenType, // 31
+
+    #[error("Pixel offset outside of the patch")]
+    InvalidPixelOffset, // 32
+
+    #[error("Patch accounts did not match the patches touched by the bomb blast")]
+    InvalidBlastPatches, // 33
+
+    #[error("No pixels were provided")]
+    EmptyPixelList, // 34
+
+    #[error("Place is frozen")]
+    PlaceFrozen, // 35
+
+    #[error("Place must be frozen for this instruction")]
+    PlaceNotFrozen, // 36
+
+    #[error("Canvas snapshot has already been sealed")]
+    CanvasAlreadySealed, // 37
+
+    #[error("Patch accounts did not match the next patches in the snapshot")]
+    InvalidSnapshotPatches, // 38
+
+    #[error("Incorrect canvas snapshot pda")]
+    IncorrectCanvasSnapshotPDA, // 39
+
+    #[error("Incorrect patch attribution pda")]
+    IncorrectPatchAttributionPDA, // 40
+
+    #[error("Place dimensions must divide evenly into at most 256x256 patches")]
+    InvalidPlaceDimensions, // 41
+
+    #[error("Place dimensions can only be set when the place is initialized")]
+    PlaceDimensionsImmutable, // 42
+
+    #[error("Gameplay token belongs to a different canvas")]
+    GameplayTokenCanvasMismatch, // 43
+
+    #[error("Pixel format can only be set when the place is initialized")]
+    PixelFormatImmutable, // 44
+
+    #[error("Pixel value does not fit the pixel format of the place")]
+    InvalidPixelValue, // 45
+
+    #[error("Region bounds must be an inclusive rectangle of patches inside the place")]
+    InvalidRegionBounds, // 46
+
+    #[error("Region has already been minted for this freeze")]
+    RegionAlreadyMinted, // 47
+
+    #[error("Incorrect region nft pda")]
+    IncorrectRegionNftPDA, // 48
+
+    #[error("Patch accounts did not match the patches in the region")]
+    InvalidRegionPatches, // 49
+
+    #[error("Pixel is protected by a shield")]
+    PixelShielded, // 50
+
+    #[error("Patch already has an active shield")]
+    PatchAlreadyShielded, // 51
+
+    #[error("Incorrect treasury pda")]
+    IncorrectTreasuryPDA, // 52
+
+    #[error("Treasury does not hold enough lamports")]
+    InsufficientTreasuryFunds, // 53
+
+    #[error("Refund share can't be more than 10000 basis points")]
+    InvalidRetireRefund, // 54
+
+    #[error("Gameplay token is already at the max upgrade level")]
+    GameplayTokenMaxUpgradeLevel, // 55
+
+    #[error("Gameplay token cooldown is already at the minimum")]
+    GameplayTokenCooldownAtMinimum, // 56
+
+    #[error("Upgrade costs more than the max cost")]
+    UpgradeCostAboveMax, // 57
+
+    #[error("Gameplay token type can't be bought with place tokens")]
+    PlaceTokenPaymentDisabled, // 58
+
+    #[error("Incorrect treasury place token account")]
+    IncorrectTreasuryPlaceTokenATA, // 59
+
+    #[error("No place tokens can be minted until the next emission window")]
+    PlaceTokenEmissionExhausted, // 60
+
+    #[error("Signer is not the pricing admin")]
+    NotPricingAdmin, // 61
+
+    #[error("Signer is not the freeze admin")]
+    NotFreezeAdmin, // 62
+
+    #[error("Signer is not the moderator")]
+    NotModerator, // 63
+
+    #[error("Signer is not the treasurer")]
+    NotTreasurer, // 64
+
+    #[error("Role can not be granted or revoked")]
+    RoleNotGrantable, // 65
+
+    #[error("Signer is not the proposed owner")]
+    NotPendingOwner, // 66
+
+    #[error("No ownership transfer has been proposed")]
+    NoPendingOwner, // 67
+
+    #[error("Incorrect program data account")]
+    IncorrectProgramDataAccount, // 68
+
+    #[error("Signer is not the program upgrade authority")]
+    NotUpgradeAuthority, // 69
+
+    #[error("Place state already initialized")]
+    PlaceStateAlreadyInitialized, // 70
+
+    #[error("Place state not initialized")]
+    PlaceStateNotInitialized, // 71
+
+    #[error("Account is already in the current layout")]
+    AccountAlreadyMigrated, // 72
+
+    #[error("Account type has no older layouts to migrate from")]
+    AccountNotMigratable, // 73
+
+    #[error("Gameplay token has been banned by a moderator")]
+    GameplayTokenBanned, // 74
+
+    #[error("Rectangle must be inclusive pixel bounds inside the place")]
+    InvalidClearBounds, // 75
+
+    #[error("Patch accounts did not match the patches touched by the rectangle")]
+    InvalidClearPatches, // 76
+
+    #[error("Purchases have not opened yet")]
+    PurchasesNotOpen, // 77
+
+    #[error("Only allowlisted wallets can purchase right now, a proof is required")]
+    AllowlistProofRequired, // 78
+
+    #[error("Allowlist proof did not match the allowlist root")]
+    InvalidAllowlistProof, // 79
+
+    #[error("Wallet has bought all the gameplay tokens the allowlist allows it")]
+    AllowlistPurchaseCapReached, // 80
+
+    #[error("Incorrect allowlist receipt pda")]
+    IncorrectAllowlistReceiptPDA, // 81
+
+    #[error("Canvas must be sealed for the current freeze")]
+    CanvasNotSealed, // 82
+
+    #[error("Patches from the last season still need to be reset")]
+    SeasonResetIncomplete, // 83
+
+    #[error("Incorrect season archive pda")]
+    IncorrectSeasonArchivePDA, // 84
+
+    #[error("Patch accounts did not match the next patches to reset")]
+    InvalidResetPatches, // 85
+
+    #[error("Gameplay token purchases are paused")]
+    PurchasesPaused, // 86
+
+    #[error("Painting is paused")]
+    PaintingPaused, // 87
+
+    #[error("Place token claims are paused")]
+    ClaimsPaused, // 88
+
+    #[error("Patch creation is paused")]
+    PatchCreationPaused, // 89
+
+    #[error("Pause flags contain unknown operations")]
+    InvalidPauseFlags, // 90
+
+    #[error("Patch attribution account has already been initialized")]
+    PatchAttributionAlreadyInitialized, // 91
+
+    #[error("Patch attribution account has not been initialized, see InitPatchAttribution")]
+    PatchAttributionNotInitialized, // 92
+
+    #[error("Account was written by a newer version of the program")]
+    UnsupportedAccountVersion, // 93
+
+    #[error("Version 0 place states and patches move to the same account of the legacy canvas")]
+    InvalidMigrationDestination, // 94
+
+    #[error("Canvas is being sealed, it can't be cleared until it is unfrozen")]
+    CanvasSealed, // 95
+
+    #[error("Patch account has not been initialized, see InitPatch")]
+    PatchNotInitialized, // 96
 }
 
 impl From<PlaceError> for ProgramError {
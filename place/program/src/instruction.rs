@@ -1,12 +1,18 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{Account, AccountInfo},
+    bpf_loader_upgradeable,
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     sysvar,
 };
 
-use crate::state::{find_address_for_patch, GameplayTokenMeta, GameplayTokenType, PlaceState};
+use crate::state::{
+    find_address_for_patch, AllowlistReceipt, CanvasSnapshot, EmissionSchedule, GameplayTokenMeta,
+    GameplayTokenRewards, GameplayTokenType, Patch, PatchAttribution, PauseFlags, PixelFormat,
+    PlaceRole, PlaceState, PlaceTokenPricing, PriceCurve, PurchasePhases, RegionNftMeta,
+    SeasonArchive, UpgradeSchedule,
+};
 
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
 pub enum PlaceInstruction {
@@ -26,18 +32,86 @@ pub enum PlaceInstruction {
 
     // Claim tokens owed for a gampeplay token account
     ClaimTokens(ClaimTokensDataArgs),
+
+    // Use a bomb gameplay token to paint a square area, possibly spanning several patches
+    DetonateBomb(DetonateBombDataArgs),
+
+    // Set many pixels within a single patch, cooldown scales with the number of pixels
+    SetPixels(SetPixelsDataArgs),
+
+    // Chain the pixels of a batch of patches into the snapshot hash of a frozen place
+    SealCanvas(SealCanvasDataArgs),
+
+    // Mint a rectangle of patches from a frozen place as an NFT
+    MintRegionNft(MintRegionNftDataArgs),
+
+    // Use a shield gameplay token to protect a small area of a patch from being painted
+    ActivateShield(ActivateShieldDataArgs),
+
+    // Transfer lamports out of the treasury, owner only
+    WithdrawTreasury(WithdrawTreasuryDataArgs),
+
+    // Move lamports above the rent exempt minimum from gameplay token meta accounts to the treasury
+    SweepGameplayTokens(SweepGameplayTokensDataArgs),
+
+    // Burn a gameplay token NFT, paying out owed place tokens and closing its accounts
+    RetireGameplayToken(RetireGameplayTokenDataArgs),
+
+    // Burn place tokens to lower the cooldown of a gameplay token
+    UpgradeGameplayToken(UpgradeGameplayTokenDataArgs),
+
+    // Claim tokens owed for many gameplay token accounts with a single mint
+    ClaimTokensBatch(ClaimTokensBatchDataArgs),
+
+    // Give an admin role to a key, replacing whoever held it
+    GrantRole(GrantRoleDataArgs),
+
+    // Take an admin role away from whoever holds it
+    RevokeRole(RevokeRoleDataArgs),
+
+    // Propose a new owner, ownership only moves once they accept
+    ProposeOwner(ProposeOwnerDataArgs),
+
+    // Become the owner, signed by the proposed owner
+    AcceptOwnership(AcceptOwnershipDataArgs),
+
+    // Withdraw a proposed ownership transfer
+    CancelOwnershipTransfer(CancelOwnershipTransferDataArgs),
+
+    // Create the place state for a canvas, only the program upgrade authority can call this
+    InitializePlace(InitializePlaceDataArgs),
+
+    // Grow a place state, patch or gameplay token meta account into the current layout
+    MigrateAccount(MigrateAccountDataArgs),
+
+    // Paint a rectangle spanning one or more patches a single colour, moderator only
+    ClearRegion(ClearRegionDataArgs),
+
+    // Ban or unban a gameplay token, moderator only
+    BanGameplayToken(BanGameplayTokenDataArgs),
+
+    // Archive the sealed canvas and start the next season, owner only
+    StartNewSeason(StartNewSeasonDataArgs),
+
+    // Blank a batch of patches after a new season starts
+    ResetPatches(ResetPatchesDataArgs),
+
+    // Allocate the attribution account of a patch created before attribution existed
+    InitPatchAttribution(InitPatchAttributionDataArgs),
 }
 
 //////////////////////////////////////////////////////////////////////////////////
-////////////////////////// UPDATE TAPESTRY STATE /////////////////////////////////
+///////////////////////////// INITIALIZE PLACE ///////////////////////////////////
 
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
-pub struct UpdatePlaceStateDataArgs {
-    // The owner the tapestry, if different from the current owner, this will be set
-    pub new_owner: Option<Pubkey>,
+pub struct InitializePlaceDataArgs {
+    // the canvas being created
+    pub canvas_id: u64,
+
+    // the owner of the new place
+    pub owner: Pubkey,
 
-    // is the tapestry frozen
-    pub is_frozen: Option<bool>,
+    // every setting below falls back to its default when not given
 
     // price of a token of type Paintbrush in lamports
     pub paintbrush_price: Option<u64>,
@@ -47,52 +121,244 @@ pub struct UpdatePlaceStateDataArgs {
 
     // price of a token of type Bomb in lamports
     pub bomb_price: Option<u64>,
+
+    // width of the place in pixels, can't be changed later
+    pub width_px: Option<u16>,
+
+    // height of the place in pixels, can't be changed later
+    pub height_px: Option<u16>,
+
+    // width and height of each patch in pixels, can't be changed later
+    pub patch_size_px: Option<u8>,
+
+    // encoding of the pixels in every patch, can't be changed later
+    pub pixel_format: Option<PixelFormat>,
+
+    // price of a token of type Shield in lamports
+    pub shield_price: Option<u64>,
+
+    // number of slots an area stays protected by a shield
+    pub shield_duration: Option<u64>,
+
+    // how gameplay token prices follow demand, see PriceCurve
+    pub price_curve: Option<PriceCurve>,
+
+    // basis points of the purchase price refunded when a gameplay token is retired
+    pub retire_refund_bps: Option<u16>,
+
+    // levels and costs for upgrading gameplay token cooldowns, see UpgradeSchedule
+    pub upgrade_schedule: Option<UpgradeSchedule>,
+
+    // prices for buying gameplay tokens with place tokens, see PlaceTokenPricing
+    pub place_token_pricing: Option<PlaceTokenPricing>,
+
+    // number of slots for the cooldown of new bombs
+    pub bomb_cooldown: Option<u64>,
+
+    // number of slots for the cooldown of new shields
+    pub shield_cooldown: Option<u64>,
+
+    // place tokens earned by paintbrushes
+    pub paintbrush_rewards: Option<GameplayTokenRewards>,
+
+    // place tokens earned by bombs
+    pub bomb_rewards: Option<GameplayTokenRewards>,
+
+    // place tokens earned by shields
+    pub shield_rewards: Option<GameplayTokenRewards>,
+
+    // supply cap and emission rate of place tokens, see EmissionSchedule
+    pub emission_schedule: Option<EmissionSchedule>,
+
+    // allowlist and start slots for purchases, see PurchasePhases
+    pub purchase_phases: Option<PurchasePhases>,
+
+    // operations paused from the start, e.g. PauseFlags::FROZEN to set the place up
+    // before anyone can paint
+    pub paused: Option<PauseFlags>,
 }
 
-pub struct UpdatePlaceStateAccountArgs<'a, 'b: 'a> {
-    /// `[signer]` fee payer and current owner
-    pub current_owner_acct: &'a AccountInfo<'b>,
+pub struct InitializePlaceAccountArgs<'a, 'b: 'a> {
+    // `[writable, signer]` pays for the place state account
+    pub payer_acct: &'a AccountInfo<'b>,
 
-    /// `[writable]` pda of the place state account
+    // `[signer]` the upgrade authority of the program
+    pub upgrade_authority_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` pda of the place state account
     pub place_state_pda_acct: &'a AccountInfo<'b>,
 
-    /// `[]` system program acct
-    pub system_acct: &'a AccountInfo<'b>,
+    // `[]` the program data account of this program, holds the upgrade authority
+    pub program_data_acct: &'a AccountInfo<'b>,
+
+    // `[]` the system program
+    pub system_prog_acct: &'a AccountInfo<'b>,
 }
 
-pub fn get_ix_update_place_state(
-    current_owner: Pubkey,
-    new_owner: Option<Pubkey>,
-    is_frozen: Option<bool>,
+pub fn get_ix_initialize_place(
+    canvas_id: u64,
+    payer: Pubkey,
+    upgrade_authority: Pubkey,
+    owner: Pubkey,
     paintbrush_price: Option<u64>,
     paintbrush_cooldown: Option<u64>,
     bomb_price: Option<u64>,
+    width_px: Option<u16>,
+    height_px: Option<u16>,
+    patch_size_px: Option<u8>,
+    pixel_format: Option<PixelFormat>,
+    shield_price: Option<u64>,
+    shield_duration: Option<u64>,
+    price_curve: Option<PriceCurve>,
+    retire_refund_bps: Option<u16>,
+    upgrade_schedule: Option<UpgradeSchedule>,
+    place_token_pricing: Option<PlaceTokenPricing>,
+    bomb_cooldown: Option<u64>,
+    shield_cooldown: Option<u64>,
+    paintbrush_rewards: Option<GameplayTokenRewards>,
+    bomb_rewards: Option<GameplayTokenRewards>,
+    shield_rewards: Option<GameplayTokenRewards>,
+    emission_schedule: Option<EmissionSchedule>,
+    purchase_phases: Option<PurchasePhases>,
+    paused: Option<PauseFlags>,
 ) -> Instruction {
-    let (place_state_pda, _) = PlaceState::pda();
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    let (program_data, _) =
+        Pubkey::find_program_address(&[crate::id().as_ref()], &bpf_loader_upgradeable::id());
+
     Instruction {
         program_id: crate::id(),
         accounts: vec![
-            AccountMeta::new(current_owner, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(upgrade_authority, true),
             AccountMeta::new(place_state_pda, false),
+            AccountMeta::new_readonly(program_data, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
         ],
-        data: PlaceInstruction::UpdatePlaceState(UpdatePlaceStateDataArgs {
-            new_owner,
-            is_frozen,
+        data: PlaceInstruction::InitializePlace(InitializePlaceDataArgs {
+            canvas_id,
+            owner,
             paintbrush_price,
             paintbrush_cooldown,
             bomb_price,
+            width_px,
+            height_px,
+            patch_size_px,
+            pixel_format,
+            shield_price,
+            shield_duration,
+            price_curve,
+            retire_refund_bps,
+            upgrade_schedule,
+            place_token_pricing,
+            bomb_cooldown,
+            shield_cooldown,
+            paintbrush_rewards,
+            bomb_rewards,
+            shield_rewards,
+            emission_schedule,
+            purchase_phases,
+            paused,
         })
         .try_to_vec()
         .unwrap(),
     }
 }
 
+//////////////////////////////////////////////////////////////////////////////////
+////////////////////////// UPDATE TAPESTRY STATE /////////////////////////////////
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Default)]
+pub struct UpdatePlaceStateDataArgs {
+    // the canvas being updated, created beforehand with InitializePlace
+    pub canvas_id: u64,
+
+    // price of a token of type Paintbrush in lamports
+    pub paintbrush_price: Option<u64>,
+
+    // number of seconds for the cooldown of new paintbrushes
+    pub paintbrush_cooldown: Option<u64>,
+
+    // price of a token of type Bomb in lamports
+    pub bomb_price: Option<u64>,
+
+    // price of a token of type Shield in lamports
+    pub shield_price: Option<u64>,
+
+    // number of slots an area stays protected by a shield
+    pub shield_duration: Option<u64>,
+
+    // how gameplay token prices follow demand, see PriceCurve
+    pub price_curve: Option<PriceCurve>,
+
+    // basis points of the purchase price refunded when a gameplay token is retired
+    pub retire_refund_bps: Option<u16>,
+
+    // levels and costs for upgrading gameplay token cooldowns, see UpgradeSchedule
+    pub upgrade_schedule: Option<UpgradeSchedule>,
+
+    // prices for buying gameplay tokens with place tokens, see PlaceTokenPricing
+    pub place_token_pricing: Option<PlaceTokenPricing>,
+
+    // number of slots for the cooldown of new bombs
+    pub bomb_cooldown: Option<u64>,
+
+    // number of slots for the cooldown of new shields
+    pub shield_cooldown: Option<u64>,
+
+    // place tokens earned by paintbrushes
+    pub paintbrush_rewards: Option<GameplayTokenRewards>,
+
+    // place tokens earned by bombs
+    pub bomb_rewards: Option<GameplayTokenRewards>,
+
+    // place tokens earned by shields
+    pub shield_rewards: Option<GameplayTokenRewards>,
+
+    // supply cap and emission rate of place tokens, see EmissionSchedule
+    pub emission_schedule: Option<EmissionSchedule>,
+
+    // allowlist and start slots for purchases, see PurchasePhases
+    pub purchase_phases: Option<PurchasePhases>,
+
+    // the full set of paused operations, replaces the current set. The tapestry is frozen
+    // with PauseFlags::FROZEN
+    pub paused: Option<PauseFlags>,
+}
+
+pub struct UpdatePlaceStateAccountArgs<'a, 'b: 'a> {
+    /// `[signer]` fee payer and current owner, or the admin holding the role for every field updated
+    pub current_owner_acct: &'a AccountInfo<'b>,
+
+    /// `[writable]` pda of the place state account
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+}
+
+/// Fields left as None keep their current value, e.g.
+/// `UpdatePlaceStateDataArgs { canvas_id, paintbrush_price: Some(price), ..Default::default() }`
+pub fn get_ix_update_place_state(
+    current_owner: Pubkey,
+    args: UpdatePlaceStateDataArgs,
+) -> Instruction {
+    let (place_state_pda, _) = PlaceState::pda(args.canvas_id);
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(current_owner, true),
+            AccountMeta::new(place_state_pda, false),
+        ],
+        data: PlaceInstruction::UpdatePlaceState(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
 //////////////////////////////////////////////////////////////////////////////////
 ////////////////////////////////// INIT PATCH ////////////////////////////////////
 
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
 pub struct InitPatchDataArgs {
+    pub canvas_id: u64,
     pub x_patch: u8,
     pub y_patch: u8,
 }
@@ -106,15 +372,24 @@ pub struct InitPatchAccountArgs<'a, 'b: 'a> {
 
     /// `[]` the system program
     pub system_acct: &'a AccountInfo<'b>,
+
+    /// `[]` the place state pda, used to check if the place is frozen
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+
+    /// `[writable]` the patch attribution pda we are going to allocate
+    pub patch_attribution_pda_acct: &'a AccountInfo<'b>,
 }
 
 pub fn get_ix_init_patch(
     program_id: Pubkey,
+    canvas_id: u64,
     payer: Pubkey,
     x_patch: u8,
     y_patch: u8,
 ) -> Instruction {
-    let (patch_pda, _) = find_address_for_patch(x_patch, y_patch, &program_id);
+    let (patch_pda, _) = find_address_for_patch(canvas_id, x_patch, y_patch, &program_id);
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    let (patch_attribution_pda, _) = PatchAttribution::pda(canvas_id, x_patch, y_patch);
 
     Instruction {
         program_id,
@@ -123,10 +398,16 @@ pub fn get_ix_init_patch(
             AccountMeta::new(payer, true),
             AccountMeta::new(patch_pda, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(place_state_pda, false),
+            AccountMeta::new(patch_attribution_pda, false),
         ],
-        data: PlaceInstruction::InitPatch(InitPatchDataArgs { x_patch, y_patch })
-            .try_to_vec()
-            .unwrap(),
+        data: PlaceInstruction::InitPatch(InitPatchDataArgs {
+            canvas_id,
+            x_patch,
+            y_patch,
+        })
+        .try_to_vec()
+        .unwrap(),
     }
 }
 
@@ -135,6 +416,9 @@ pub fn get_ix_init_patch(
 
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
 pub struct PurchaseGameplayTokenDataArgs {
+    // The canvas the token is purchased for
+    pub canvas_id: u64,
+
     // The type of token to purchase
     pub token_type: GameplayTokenType,
 
@@ -143,15 +427,39 @@ pub struct PurchaseGameplayTokenDataArgs {
     // on the purchase instruction
     pub random_seed: u64,
 
-    // The price the buyer is expecting to pay
+    // The price the buyer is expecting to pay, in lamports or place tokens depending on payment
     pub desired_price: u64,
+
+    // What the buyer is paying with
+    pub payment: PaymentMethod,
+
+    // Proof the buyer is on the allowlist, only needed during the allowlist phase
+    pub allowlist_proof: Option<AllowlistProof>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct AllowlistProof {
+    // gameplay tokens the buyer can purchase during the allowlist phase, part of the leaf
+    pub max_purchases: u16,
+
+    // sibling hashes from the buyer's leaf up to the root, see allowlist::AllowlistTree
+    pub proof: Vec<[u8; 32]>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy)]
+pub enum PaymentMethod {
+    // lamports sent to the treasury at the current price curve price
+    Lamports,
+
+    // place tokens burned or sent to the treasury at the PlaceTokenPricing price
+    PlaceTokens,
 }
 
 pub struct PurchaseGameplayTokenAccountArgs<'a, 'b: 'a> {
     /// `[signer]` Account that will own this... account... fuck
     pub payer_acct: &'a AccountInfo<'b>,
 
-    /// `[]` PlaceState account
+    /// `[writable]` PlaceState account, tracks sales for the price curve
     pub place_state_pda_acct: &'a AccountInfo<'b>,
 
     // `[writable]` PDA for the GameplayTokenMeta account
@@ -180,15 +488,71 @@ pub struct PurchaseGameplayTokenAccountArgs<'a, 'b: 'a> {
 
     // `[]` the rent sysvar account (needed by token program)
     pub rent_sysvar_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the treasury pda receiving the payment
+    pub treasury_pda_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` counts the payer's purchases, only used during the allowlist phase
+    pub allowlist_receipt_pda_acct: &'a AccountInfo<'b>,
+
+    // the accounts below are left off when paying with lamports
+
+    // `[writable]` the global mint for place tokens
+    pub place_token_mint_acct: Option<&'a AccountInfo<'b>>,
+
+    // `[writable]` payer's place token account
+    pub payer_place_token_ata_acct: Option<&'a AccountInfo<'b>>,
+
+    // `[writable]` treasury's place token account, only used when place token payments aren't burned
+    pub treasury_place_token_ata_acct: Option<&'a AccountInfo<'b>>,
 }
 
 pub fn get_ix_purchase_gameplay_token(
+    canvas_id: u64,
+    payer: Pubkey,
+    random_seed: u64,
+    token_type: GameplayTokenType,
+    desired_price: u64,
+) -> Instruction {
+    get_ix_purchase_gameplay_token_with_payment(
+        canvas_id,
+        payer,
+        random_seed,
+        token_type,
+        desired_price,
+        PaymentMethod::Lamports,
+    )
+}
+
+pub fn get_ix_purchase_gameplay_token_with_payment(
+    canvas_id: u64,
+    payer: Pubkey,
+    random_seed: u64,
+    token_type: GameplayTokenType,
+    desired_price: u64,
+    payment: PaymentMethod,
+) -> Instruction {
+    get_ix_purchase_gameplay_token_with_proof(
+        canvas_id,
+        payer,
+        random_seed,
+        token_type,
+        desired_price,
+        payment,
+        None,
+    )
+}
+
+pub fn get_ix_purchase_gameplay_token_with_proof(
+    canvas_id: u64,
     payer: Pubkey,
     random_seed: u64,
     token_type: GameplayTokenType,
     desired_price: u64,
+    payment: PaymentMethod,
+    allowlist_proof: Option<AllowlistProof>,
 ) -> Instruction {
-    let (place_state_pda, _) = PlaceState::pda();
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
     let (gameplay_meta_pda, _) = GameplayTokenMeta::pda(random_seed);
     let (gameplay_token_mint_pda, _) = GameplayTokenMeta::token_mint_pda(random_seed);
     let gameplay_token_ata = spl_associated_token_account::get_associated_token_address(
@@ -196,26 +560,49 @@ pub fn get_ix_purchase_gameplay_token(
         &gameplay_token_mint_pda,
     );
     let (gameplay_token_mpl_pda, _) = GameplayTokenMeta::token_metadata_pda(random_seed);
+    let (treasury_pda, _) = PlaceState::treasury_pda(canvas_id);
+    let (allowlist_receipt_pda, _) = AllowlistReceipt::pda(canvas_id, &payer);
+
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new(place_state_pda, false),
+        AccountMeta::new(gameplay_meta_pda, false),
+        AccountMeta::new(gameplay_token_mint_pda, false),
+        AccountMeta::new(gameplay_token_ata, false),
+        AccountMeta::new(gameplay_token_mpl_pda, false),
+        AccountMeta::new_readonly(mpl_token_metadata::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new(treasury_pda, false),
+        AccountMeta::new(allowlist_receipt_pda, false),
+    ];
+
+    if payment == PaymentMethod::PlaceTokens {
+        let (place_token_mint_pda, _) = PlaceState::token_mint_pda(canvas_id);
+        let payer_place_token_ata = spl_associated_token_account::get_associated_token_address(
+            &payer,
+            &place_token_mint_pda,
+        );
+        accounts.push(AccountMeta::new(place_token_mint_pda, false));
+        accounts.push(AccountMeta::new(payer_place_token_ata, false));
+        accounts.push(AccountMeta::new(
+            PlaceState::treasury_place_token_ata(canvas_id),
+            false,
+        ));
+    }
 
     Instruction {
         program_id: crate::id(),
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new_readonly(place_state_pda, false),
-            AccountMeta::new(gameplay_meta_pda, false),
-            AccountMeta::new(gameplay_token_mint_pda, false),
-            AccountMeta::new(gameplay_token_ata, false),
-            AccountMeta::new(gameplay_token_mpl_pda, false),
-            AccountMeta::new_readonly(mpl_token_metadata::id(), false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            AccountMeta::new_readonly(sysvar::rent::id(), false),
-        ],
+        accounts,
         data: PlaceInstruction::PurchaseGameplayToken(PurchaseGameplayTokenDataArgs {
+            canvas_id,
             token_type,
             random_seed,
             desired_price,
+            payment,
+            allowlist_proof,
         })
         .try_to_vec()
         .unwrap(),
@@ -226,13 +613,14 @@ pub fn get_ix_purchase_gameplay_token(
 
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
 pub struct SetPixelDataArgs {
+    pub canvas_id: u64,
     pub x: u8,
     pub y: u8,
     pub x_offset: u8,
     pub y_offset: u8,
 
-    // 8 bit value, which gets mapped to a 32 bit color from a pallete on clients
-    pub pixel: u8,
+    // must fit the pixel format of the place, either a palette index or an RGB565 color
+    pub pixel: u16,
 }
 
 pub struct SetPixelAccountArgs<'a, 'b: 'a> {
@@ -251,10 +639,17 @@ pub struct SetPixelAccountArgs<'a, 'b: 'a> {
 
     // `[]` the system program
     pub system_acct: &'a AccountInfo<'b>,
+
+    // `[]` the place state pda, used to check if the place is frozen
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the pda of the attribution account for the patch being set
+    pub patch_attribution_pda_acct: &'a AccountInfo<'b>,
 }
 
 pub fn get_ix_set_pixel(
     program_id: Pubkey,
+    canvas_id: u64,
     payer: Pubkey,
     gameplay_token_meta_pda: Pubkey,
     payer_gameplay_token_acct: Pubkey,
@@ -262,9 +657,11 @@ pub fn get_ix_set_pixel(
     y: u8,
     x_offset: u8,
     y_offset: u8,
-    pixel: u8,
+    pixel: u16,
 ) -> Instruction {
-    let (patch_pda, _) = find_address_for_patch(x, y, &program_id);
+    let (patch_pda, _) = find_address_for_patch(canvas_id, x, y, &program_id);
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    let (patch_attribution_pda, _) = PatchAttribution::pda(canvas_id, x, y);
 
     Instruction {
         program_id,
@@ -275,8 +672,11 @@ pub fn get_ix_set_pixel(
             AccountMeta::new(gameplay_token_meta_pda, false),
             AccountMeta::new_readonly(payer_gameplay_token_acct, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(place_state_pda, false),
+            AccountMeta::new(patch_attribution_pda, false),
         ],
         data: PlaceInstruction::SetPixel(SetPixelDataArgs {
+            canvas_id,
             x: x,
             y: y,
             x_offset: x_offset,
@@ -288,12 +688,95 @@ pub fn get_ix_set_pixel(
     }
 }
 
+//////////////////////////////////////////////////////////////////////////////////
+///////////////////////////////// SET PIXELS /////////////////////////////////////
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct PixelWrite {
+    pub x_offset: u8,
+    pub y_offset: u8,
+
+    // must fit the pixel format of the place, either a palette index or an RGB565 color
+    pub pixel: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct SetPixelsDataArgs {
+    pub canvas_id: u64,
+    pub x: u8,
+    pub y: u8,
+
+    // pixels to write into the patch, applied in order
+    pub pixels: Vec<PixelWrite>,
+}
+
+pub struct SetPixelsAccountArgs<'a, 'b: 'a> {
+    // `[signer]` Fee payer for this tx
+    pub payer_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the pda of the patch being set
+    pub patch_pda_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` gameplay token being used to set the pixels
+    pub gameplay_token_meta_acct: &'a AccountInfo<'b>,
+
+    // `[]` the associated token account owned by the payer that holds the
+    // NFT associated with this gameplay token
+    pub payer_gameplay_token_acct: &'a AccountInfo<'b>,
+
+    // `[]` the system program
+    pub system_acct: &'a AccountInfo<'b>,
+
+    // `[]` the place state pda, used to check if the place is frozen
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the pda of the attribution account for the patch being set
+    pub patch_attribution_pda_acct: &'a AccountInfo<'b>,
+}
+
+pub fn get_ix_set_pixels(
+    program_id: Pubkey,
+    canvas_id: u64,
+    payer: Pubkey,
+    gameplay_token_meta_pda: Pubkey,
+    payer_gameplay_token_acct: Pubkey,
+    x: u8,
+    y: u8,
+    pixels: Vec<PixelWrite>,
+) -> Instruction {
+    let (patch_pda, _) = find_address_for_patch(canvas_id, x, y, &program_id);
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    let (patch_attribution_pda, _) = PatchAttribution::pda(canvas_id, x, y);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(patch_pda, false),
+            AccountMeta::new(gameplay_token_meta_pda, false),
+            AccountMeta::new_readonly(payer_gameplay_token_acct, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(place_state_pda, false),
+            AccountMeta::new(patch_attribution_pda, false),
+        ],
+        data: PlaceInstruction::SetPixels(SetPixelsDataArgs {
+            canvas_id,
+            x,
+            y,
+            pixels,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
 //////////////////////////////////////////////////////////////////////////////////
 ///////////////////////////////// Init Mint //////////////////////////////////////
 
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
 pub struct InitMintDataArgs {
-    // anything?
+    // the canvas to create the tapestry token mint for
+    pub canvas_id: u64,
 }
 
 pub struct InitMintAccountArgs<'a, 'b: 'a> {
@@ -322,10 +805,10 @@ pub struct InitMintAccountArgs<'a, 'b: 'a> {
     pub rent_sysvar_acct: &'a AccountInfo<'b>,
 }
 
-pub fn get_ix_init_mint(owner: Pubkey) -> Instruction {
-    let (place_state_pda, _) = PlaceState::pda();
-    let (place_mint_pda, _) = PlaceState::token_mint_pda();
-    let (place_mint_mpl_metadata_pda, _) = PlaceState::token_mint_mpl_metadata_pda();
+pub fn get_ix_init_mint(canvas_id: u64, owner: Pubkey) -> Instruction {
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    let (place_mint_pda, _) = PlaceState::token_mint_pda(canvas_id);
+    let (place_mint_mpl_metadata_pda, _) = PlaceState::token_mint_mpl_metadata_pda(canvas_id);
     Instruction {
         program_id: crate::id(),
         accounts: vec![
@@ -338,7 +821,7 @@ pub fn get_ix_init_mint(owner: Pubkey) -> Instruction {
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             AccountMeta::new_readonly(sysvar::rent::id(), false),
         ],
-        data: PlaceInstruction::InitMint(InitMintDataArgs {})
+        data: PlaceInstruction::InitMint(InitMintDataArgs { canvas_id })
             .try_to_vec()
             .unwrap(),
     }
@@ -349,7 +832,8 @@ pub fn get_ix_init_mint(owner: Pubkey) -> Instruction {
 
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
 pub struct ClaimTokensDataArgs {
-    // anything?
+    // the canvas the gameplay token belongs to
+    pub canvas_id: u64,
 }
 
 // NOTE(will): I could design this such that anyone can trigger as long as the owner of the ata
@@ -372,7 +856,7 @@ pub struct ClaimTokensAccountArgs<'a, 'b: 'a> {
     // `[writable]` the destination token account for the place tokens to be sent
     pub place_token_dest_ata_acct: &'a AccountInfo<'b>,
 
-    // `[]` the global place state account (needed to sign mint ix)
+    // `[writable]` the global place state account (needed to sign mint ix, tracks emission)
     pub place_state_pda_acct: &'a AccountInfo<'b>,
 
     // `[]` the spl token program executable
@@ -380,6 +864,7 @@ pub struct ClaimTokensAccountArgs<'a, 'b: 'a> {
 }
 
 pub fn get_ix_claim_tokens(
+    canvas_id: u64,
     claimer: Pubkey,
     place_token_dest: Pubkey,
     gameplay_token_mint: Pubkey,
@@ -389,8 +874,8 @@ pub fn get_ix_claim_tokens(
     let gameplay_token_ata =
         spl_associated_token_account::get_associated_token_address(&claimer, &gameplay_token_mint);
 
-    let (place_token_mint_pda, _) = PlaceState::token_mint_pda();
-    let (place_state_pda, _) = PlaceState::pda();
+    let (place_token_mint_pda, _) = PlaceState::token_mint_pda(canvas_id);
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
     Instruction {
         program_id: crate::id(),
         accounts: vec![
@@ -399,11 +884,1167 @@ pub fn get_ix_claim_tokens(
             AccountMeta::new_readonly(gameplay_token_ata, false),
             AccountMeta::new(place_token_mint_pda, false),
             AccountMeta::new(place_token_dest, false),
-            AccountMeta::new_readonly(place_state_pda, false),
+            AccountMeta::new(place_state_pda, false),
             AccountMeta::new_readonly(spl_token::id(), false),
         ],
-        data: PlaceInstruction::ClaimTokens(ClaimTokensDataArgs {})
+        data: PlaceInstruction::ClaimTokens(ClaimTokensDataArgs { canvas_id })
             .try_to_vec()
             .unwrap(),
     }
 }
+
+//////////////////////////////////////////////////////////////////////////////////
+////////////////////////////// DETONATE BOMB /////////////////////////////////////
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct DetonateBombDataArgs {
+    pub canvas_id: u64,
+
+    // patch containing the center of the blast
+    pub x: u8,
+    pub y: u8,
+
+    // offset of the center of the blast within the patch
+    pub x_offset: u8,
+    pub y_offset: u8,
+
+    // value painted over the whole blast area, must fit the pixel format of the place
+    pub pixel: u16,
+}
+
+pub struct DetonateBombAccountArgs<'a, 'b: 'a> {
+    // `[signer]` Fee payer for this tx, must own the bomb NFT
+    pub payer_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` gameplay token meta of the bomb being detonated
+    pub gameplay_token_meta_acct: &'a AccountInfo<'b>,
+
+    // `[]` the associated token account owned by the payer that holds the bomb NFT
+    pub payer_gameplay_token_acct: &'a AccountInfo<'b>,
+
+    // `[]` the place state pda, used to check if the place is frozen
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` (patch pda, patch attribution pda) pairs for every patch touched by
+    // the blast, in the order returned by `PlaceState::bomb_blast_patches`
+    pub patch_pda_accts: &'a [AccountInfo<'b>],
+}
+
+pub fn get_ix_detonate_bomb(
+    place_state: &PlaceState,
+    payer: Pubkey,
+    gameplay_token_meta_pda: Pubkey,
+    payer_gameplay_token_acct: Pubkey,
+    x: u8,
+    y: u8,
+    x_offset: u8,
+    y_offset: u8,
+    pixel: u16,
+) -> Instruction {
+    let canvas_id = place_state.canvas_id;
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    let mut accounts = vec![
+        AccountMeta::new_readonly(payer, true),
+        AccountMeta::new(gameplay_token_meta_pda, false),
+        AccountMeta::new_readonly(payer_gameplay_token_acct, false),
+        AccountMeta::new_readonly(place_state_pda, false),
+    ];
+
+    for (patch_x, patch_y) in place_state.bomb_blast_patches(x, y, x_offset, y_offset) {
+        let (patch_pda, _) = Patch::pda(canvas_id, patch_x, patch_y);
+        let (patch_attribution_pda, _) = PatchAttribution::pda(canvas_id, patch_x, patch_y);
+        accounts.push(AccountMeta::new(patch_pda, false));
+        accounts.push(AccountMeta::new(patch_attribution_pda, false));
+    }
+
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: PlaceInstruction::DetonateBomb(DetonateBombDataArgs {
+            canvas_id,
+            x,
+            y,
+            x_offset,
+            y_offset,
+            pixel,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////
+////////////////////////////// SEAL CANVAS ///////////////////////////////////////
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct SealCanvasDataArgs {
+    // the canvas being sealed
+    pub canvas_id: u64,
+}
+
+pub struct SealCanvasAccountArgs<'a, 'b: 'a> {
+    // `[signer]` the freeze admin (or owner) of the place, pays for the snapshot account
+    pub owner_acct: &'a AccountInfo<'b>,
+
+    // `[]` the global place state pda, must be frozen
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the canvas snapshot pda
+    pub canvas_snapshot_pda_acct: &'a AccountInfo<'b>,
+
+    // `[]` the system program
+    pub system_prog_acct: &'a AccountInfo<'b>,
+
+    // `[]` the next patch pdas to chain into the hash, starting at `CanvasSnapshot::next_patch`
+    pub patch_pda_accts: &'a [AccountInfo<'b>],
+}
+
+/// `first_patch` should be the `next_patch` of the current snapshot, or 0 if the
+/// snapshot hasn't been created for the current freeze yet
+pub fn get_ix_seal_canvas(
+    place_state: &PlaceState,
+    owner: Pubkey,
+    first_patch: u32,
+    patch_count: u32,
+) -> Instruction {
+    let canvas_id = place_state.canvas_id;
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    let (canvas_snapshot_pda, _) = CanvasSnapshot::pda(canvas_id);
+
+    let mut accounts = vec![
+        AccountMeta::new(owner, true),
+        AccountMeta::new_readonly(place_state_pda, false),
+        AccountMeta::new(canvas_snapshot_pda, false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+    ];
+
+    let last_patch = first_patch
+        .saturating_add(patch_count)
+        .min(place_state.patch_count());
+    for index in first_patch..last_patch {
+        let (patch_x, patch_y) = place_state.patch_coords(index);
+        let (patch_pda, _) = Patch::pda(canvas_id, patch_x, patch_y);
+        accounts.push(AccountMeta::new_readonly(patch_pda, false));
+    }
+
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: PlaceInstruction::SealCanvas(SealCanvasDataArgs { canvas_id })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////
+//////////////////////////// MINT REGION NFT /////////////////////////////////////
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct MintRegionNftDataArgs {
+    pub canvas_id: u64,
+
+    // inclusive bounds of the region in patch coordinates
+    pub x_min: u8,
+    pub y_min: u8,
+    pub x_max: u8,
+    pub y_max: u8,
+}
+
+pub struct MintRegionNftAccountArgs<'a, 'b: 'a> {
+    // `[signer]` the owner of the place, pays for the accounts and receives the NFT
+    pub owner_acct: &'a AccountInfo<'b>,
+
+    // `[]` the place state pda, must be frozen
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` PDA for the RegionNftMeta account
+    pub region_meta_pda_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` PDA for the token mint of the region NFT
+    pub region_token_mint_pda_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` ATA of the owner to mint the NFT into
+    pub region_token_ata_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the token metadata account for the NFT
+    pub region_token_mpl_meta_acct: &'a AccountInfo<'b>,
+
+    // `[]` The MPL token metadata program account
+    pub mpl_metadata_prog_acct: &'a AccountInfo<'b>,
+
+    // `[]` token program account
+    pub token_prog_acct: &'a AccountInfo<'b>,
+
+    // `[]` associated token program account
+    pub ata_prog_acct: &'a AccountInfo<'b>,
+
+    // `[]` the system program
+    pub system_prog_acct: &'a AccountInfo<'b>,
+
+    // `[]` the rent sysvar account
+    pub rent_sysvar_acct: &'a AccountInfo<'b>,
+
+    // `[]` the patch pdas in the region, in the order returned by `PlaceState::region_patches`
+    pub patch_pda_accts: &'a [AccountInfo<'b>],
+}
+
+pub fn get_ix_mint_region_nft(
+    place_state: &PlaceState,
+    owner: Pubkey,
+    x_min: u8,
+    y_min: u8,
+    x_max: u8,
+    y_max: u8,
+) -> Instruction {
+    let canvas_id = place_state.canvas_id;
+    let frozen_at_slot = place_state.frozen_at_slot;
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    let (region_meta_pda, _) =
+        RegionNftMeta::pda(canvas_id, frozen_at_slot, x_min, y_min, x_max, y_max);
+    let (region_token_mint_pda, _) =
+        RegionNftMeta::token_mint_pda(canvas_id, frozen_at_slot, x_min, y_min, x_max, y_max);
+    let region_token_ata =
+        spl_associated_token_account::get_associated_token_address(&owner, &region_token_mint_pda);
+    let (region_token_mpl_pda, _) =
+        RegionNftMeta::token_metadata_pda(canvas_id, frozen_at_slot, x_min, y_min, x_max, y_max);
+
+    let mut accounts = vec![
+        AccountMeta::new(owner, true),
+        AccountMeta::new_readonly(place_state_pda, false),
+        AccountMeta::new(region_meta_pda, false),
+        AccountMeta::new(region_token_mint_pda, false),
+        AccountMeta::new(region_token_ata, false),
+        AccountMeta::new(region_token_mpl_pda, false),
+        AccountMeta::new_readonly(mpl_token_metadata::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    for (patch_x, patch_y) in place_state.region_patches(x_min, y_min, x_max, y_max) {
+        let (patch_pda, _) = Patch::pda(canvas_id, patch_x, patch_y);
+        accounts.push(AccountMeta::new_readonly(patch_pda, false));
+    }
+
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: PlaceInstruction::MintRegionNft(MintRegionNftDataArgs {
+            canvas_id,
+            x_min,
+            y_min,
+            x_max,
+            y_max,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////
+///////////////////////////// ACTIVATE SHIELD ////////////////////////////////////
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct ActivateShieldDataArgs {
+    pub canvas_id: u64,
+
+    // patch to protect
+    pub x: u8,
+    pub y: u8,
+
+    // offset of the center of the protected area within the patch
+    pub x_offset: u8,
+    pub y_offset: u8,
+}
+
+pub struct ActivateShieldAccountArgs<'a, 'b: 'a> {
+    // `[signer]` Fee payer for this tx, must own the shield NFT
+    pub payer_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the pda of the patch being protected
+    pub patch_pda_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` gameplay token meta of the shield being activated
+    pub gameplay_token_meta_acct: &'a AccountInfo<'b>,
+
+    // `[]` the associated token account owned by the payer that holds the shield NFT
+    pub payer_gameplay_token_acct: &'a AccountInfo<'b>,
+
+    // `[]` the place state pda, used to check if the place is frozen
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+}
+
+pub fn get_ix_activate_shield(
+    program_id: Pubkey,
+    canvas_id: u64,
+    payer: Pubkey,
+    gameplay_token_meta_pda: Pubkey,
+    payer_gameplay_token_acct: Pubkey,
+    x: u8,
+    y: u8,
+    x_offset: u8,
+    y_offset: u8,
+) -> Instruction {
+    let (patch_pda, _) = find_address_for_patch(canvas_id, x, y, &program_id);
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(payer, true),
+            AccountMeta::new(patch_pda, false),
+            AccountMeta::new(gameplay_token_meta_pda, false),
+            AccountMeta::new_readonly(payer_gameplay_token_acct, false),
+            AccountMeta::new_readonly(place_state_pda, false),
+        ],
+        data: PlaceInstruction::ActivateShield(ActivateShieldDataArgs {
+            canvas_id,
+            x,
+            y,
+            x_offset,
+            y_offset,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////
+//////////////////////////// WITHDRAW TREASURY ///////////////////////////////////
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct WithdrawTreasuryDataArgs {
+    pub canvas_id: u64,
+
+    // number of lamports to withdraw
+    pub amount: u64,
+}
+
+pub struct WithdrawTreasuryAccountArgs<'a, 'b: 'a> {
+    // `[signer]` the treasurer (or owner) of the place
+    pub owner_acct: &'a AccountInfo<'b>,
+
+    // `[]` the place state pda
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the treasury pda to withdraw from
+    pub treasury_pda_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the account receiving the lamports
+    pub destination_acct: &'a AccountInfo<'b>,
+
+    // `[]` the system program
+    pub system_prog_acct: &'a AccountInfo<'b>,
+}
+
+pub fn get_ix_withdraw_treasury(
+    canvas_id: u64,
+    owner: Pubkey,
+    destination: Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    let (treasury_pda, _) = PlaceState::treasury_pda(canvas_id);
+
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(owner, true),
+            AccountMeta::new_readonly(place_state_pda, false),
+            AccountMeta::new(treasury_pda, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: PlaceInstruction::WithdrawTreasury(WithdrawTreasuryDataArgs { canvas_id, amount })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////
+////////////////////////// SWEEP GAMEPLAY TOKENS /////////////////////////////////
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct SweepGameplayTokensDataArgs {
+    pub canvas_id: u64,
+
+    // random seed of each gameplay token meta account, in the same order as the accounts.
+    // The accounts aren't parsed, so older layouts can be swept without migrating them
+    pub random_seeds: Vec<u64>,
+}
+
+// NOTE: anyone can sweep, the lamports can only ever end up in the treasury
+pub struct SweepGameplayTokensAccountArgs<'a, 'b: 'a> {
+    // `[]` the place state pda
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the treasury pda receiving the swept lamports
+    pub treasury_pda_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` gameplay token meta accounts belonging to the canvas, or created before
+    // gameplay tokens belonged to a canvas
+    pub gameplay_token_meta_accts: &'a [AccountInfo<'b>],
+}
+
+pub fn get_ix_sweep_gameplay_tokens(canvas_id: u64, gameplay_token_seeds: &[u64]) -> Instruction {
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    let (treasury_pda, _) = PlaceState::treasury_pda(canvas_id);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(place_state_pda, false),
+        AccountMeta::new(treasury_pda, false),
+    ];
+
+    for random_seed in gameplay_token_seeds {
+        let (gameplay_meta_pda, _) = GameplayTokenMeta::pda(*random_seed);
+        accounts.push(AccountMeta::new(gameplay_meta_pda, false));
+    }
+
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: PlaceInstruction::SweepGameplayTokens(SweepGameplayTokensDataArgs {
+            canvas_id,
+            random_seeds: gameplay_token_seeds.to_vec(),
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////
+////////////////////////// RETIRE GAMEPLAY TOKEN /////////////////////////////////
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct RetireGameplayTokenDataArgs {
+    // the canvas the gameplay token belongs to
+    pub canvas_id: u64,
+}
+
+pub struct RetireGameplayTokenAccountArgs<'a, 'b: 'a> {
+    // `[signer, writable]` holder of the gameplay token NFT, receives the rent and refund
+    pub holder_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the gameplay token meta account being closed
+    pub gameplay_token_pda_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the mint of the gameplay token NFT
+    pub gameplay_token_mint_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the holder's token account for the NFT, burned and closed
+    pub gameplay_token_ata_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the global mint for place tokens
+    pub place_token_mint_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the destination token account for any place tokens still owed
+    pub place_token_dest_ata_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the place state pda (needed to sign the mint ix, tracks emission)
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the treasury pda paying the refund
+    pub treasury_pda_acct: &'a AccountInfo<'b>,
+
+    // `[]` the spl token program executable
+    pub token_prog_acct: &'a AccountInfo<'b>,
+
+    // `[]` the system program
+    pub system_prog_acct: &'a AccountInfo<'b>,
+}
+
+pub fn get_ix_retire_gameplay_token(
+    canvas_id: u64,
+    holder: Pubkey,
+    place_token_dest: Pubkey,
+    gameplay_token_seed: u64,
+) -> Instruction {
+    let (gameplay_meta_pda, _) = GameplayTokenMeta::pda(gameplay_token_seed);
+    let (gameplay_token_mint_pda, _) = GameplayTokenMeta::token_mint_pda(gameplay_token_seed);
+    let gameplay_token_ata = spl_associated_token_account::get_associated_token_address(
+        &holder,
+        &gameplay_token_mint_pda,
+    );
+
+    let (place_token_mint_pda, _) = PlaceState::token_mint_pda(canvas_id);
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    let (treasury_pda, _) = PlaceState::treasury_pda(canvas_id);
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(holder, true),
+            AccountMeta::new(gameplay_meta_pda, false),
+            AccountMeta::new(gameplay_token_mint_pda, false),
+            AccountMeta::new(gameplay_token_ata, false),
+            AccountMeta::new(place_token_mint_pda, false),
+            AccountMeta::new(place_token_dest, false),
+            AccountMeta::new(place_state_pda, false),
+            AccountMeta::new(treasury_pda, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: PlaceInstruction::RetireGameplayToken(RetireGameplayTokenDataArgs { canvas_id })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////
+///////////////////////// UPGRADE GAMEPLAY TOKEN /////////////////////////////////
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct UpgradeGameplayTokenDataArgs {
+    // the canvas the gameplay token belongs to
+    pub canvas_id: u64,
+
+    // most place tokens the holder is willing to burn, guards against schedule changes
+    pub max_cost: u64,
+}
+
+pub struct UpgradeGameplayTokenAccountArgs<'a, 'b: 'a> {
+    // `[signer]` holder of the gameplay token NFT and the place tokens being spent
+    pub holder_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the gameplay token meta account being upgraded
+    pub gameplay_token_meta_acct: &'a AccountInfo<'b>,
+
+    // `[]` the holder's token account for the gameplay token NFT
+    pub gameplay_token_ata_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the global mint for place tokens
+    pub place_token_mint_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the holder's place token account the cost is burned from
+    pub place_token_ata_acct: &'a AccountInfo<'b>,
+
+    // `[]` the place state pda
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+
+    // `[]` the spl token program executable
+    pub token_prog_acct: &'a AccountInfo<'b>,
+}
+
+pub fn get_ix_upgrade_gameplay_token(
+    canvas_id: u64,
+    holder: Pubkey,
+    gameplay_token_seed: u64,
+    max_cost: u64,
+) -> Instruction {
+    let (gameplay_meta_pda, _) = GameplayTokenMeta::pda(gameplay_token_seed);
+    let (gameplay_token_mint_pda, _) = GameplayTokenMeta::token_mint_pda(gameplay_token_seed);
+    let gameplay_token_ata = spl_associated_token_account::get_associated_token_address(
+        &holder,
+        &gameplay_token_mint_pda,
+    );
+
+    let (place_token_mint_pda, _) = PlaceState::token_mint_pda(canvas_id);
+    let place_token_ata =
+        spl_associated_token_account::get_associated_token_address(&holder, &place_token_mint_pda);
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(holder, true),
+            AccountMeta::new(gameplay_meta_pda, false),
+            AccountMeta::new_readonly(gameplay_token_ata, false),
+            AccountMeta::new(place_token_mint_pda, false),
+            AccountMeta::new(place_token_ata, false),
+            AccountMeta::new_readonly(place_state_pda, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: PlaceInstruction::UpgradeGameplayToken(UpgradeGameplayTokenDataArgs {
+            canvas_id,
+            max_cost,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////
+//////////////////////////// CLAIM TOKENS BATCH //////////////////////////////////
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct ClaimTokensBatchDataArgs {
+    // the canvas the gameplay tokens belong to
+    pub canvas_id: u64,
+}
+
+pub struct ClaimTokensBatchAccountArgs<'a, 'b: 'a> {
+    // `[signer]` account that owns every gameplay token ATA, which is claiming the tokens
+    pub claimer_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the global mint for place tokens
+    pub place_token_mint_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the destination token account for the place tokens to be sent
+    pub place_token_dest_ata_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the global place state account (needed to sign mint ix, tracks emission)
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+
+    // `[]` the spl token program executable
+    pub token_prog_acct: &'a AccountInfo<'b>,
+
+    // (`[writable]` gameplay token meta, `[]` token account holding its NFT) pairs,
+    // metas with nothing owed are skipped
+    pub gameplay_token_accts: &'a [AccountInfo<'b>],
+}
+
+/// Claims from every gameplay token in `gameplay_token_seeds`, which must all be held by
+/// the claimer in their associated token accounts
+pub fn get_ix_claim_tokens_batch(
+    canvas_id: u64,
+    claimer: Pubkey,
+    place_token_dest: Pubkey,
+    gameplay_token_seeds: &[u64],
+) -> Instruction {
+    let (place_token_mint_pda, _) = PlaceState::token_mint_pda(canvas_id);
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(claimer, true),
+        AccountMeta::new(place_token_mint_pda, false),
+        AccountMeta::new(place_token_dest, false),
+        AccountMeta::new(place_state_pda, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    for random_seed in gameplay_token_seeds {
+        let (gameplay_meta_pda, _) = GameplayTokenMeta::pda(*random_seed);
+        let (gameplay_token_mint_pda, _) = GameplayTokenMeta::token_mint_pda(*random_seed);
+        let gameplay_token_ata = spl_associated_token_account::get_associated_token_address(
+            &claimer,
+            &gameplay_token_mint_pda,
+        );
+        accounts.push(AccountMeta::new(gameplay_meta_pda, false));
+        accounts.push(AccountMeta::new_readonly(gameplay_token_ata, false));
+    }
+
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: PlaceInstruction::ClaimTokensBatch(ClaimTokensBatchDataArgs { canvas_id })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////
+/////////////////////////////// GRANT ROLE ///////////////////////////////////////
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct GrantRoleDataArgs {
+    pub canvas_id: u64,
+
+    // the role being granted, the super-admin role moves with ownership instead
+    pub role: PlaceRole,
+
+    // the key receiving the role
+    pub grantee: Pubkey,
+}
+
+pub struct GrantRoleAccountArgs<'a, 'b: 'a> {
+    // `[signer]` the owner of the place
+    pub owner_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the place state pda
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+}
+
+pub fn get_ix_grant_role(
+    canvas_id: u64,
+    owner: Pubkey,
+    role: PlaceRole,
+    grantee: Pubkey,
+) -> Instruction {
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(owner, true),
+            AccountMeta::new(place_state_pda, false),
+        ],
+        data: PlaceInstruction::GrantRole(GrantRoleDataArgs {
+            canvas_id,
+            role,
+            grantee,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////
+/////////////////////////////// REVOKE ROLE //////////////////////////////////////
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct RevokeRoleDataArgs {
+    pub canvas_id: u64,
+
+    // the role being revoked
+    pub role: PlaceRole,
+}
+
+pub struct RevokeRoleAccountArgs<'a, 'b: 'a> {
+    // `[signer]` the owner of the place
+    pub owner_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the place state pda
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+}
+
+pub fn get_ix_revoke_role(canvas_id: u64, owner: Pubkey, role: PlaceRole) -> Instruction {
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(owner, true),
+            AccountMeta::new(place_state_pda, false),
+        ],
+        data: PlaceInstruction::RevokeRole(RevokeRoleDataArgs { canvas_id, role })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////
+////////////////////////////// PROPOSE OWNER /////////////////////////////////////
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct ProposeOwnerDataArgs {
+    pub canvas_id: u64,
+
+    // the key that can accept ownership, replaces any earlier proposal
+    pub new_owner: Pubkey,
+}
+
+pub struct ProposeOwnerAccountArgs<'a, 'b: 'a> {
+    // `[signer]` the current owner of the place
+    pub owner_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the place state pda
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+}
+
+pub fn get_ix_propose_owner(canvas_id: u64, owner: Pubkey, new_owner: Pubkey) -> Instruction {
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(owner, true),
+            AccountMeta::new(place_state_pda, false),
+        ],
+        data: PlaceInstruction::ProposeOwner(ProposeOwnerDataArgs {
+            canvas_id,
+            new_owner,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////
+///////////////////////////// ACCEPT OWNERSHIP ///////////////////////////////////
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct AcceptOwnershipDataArgs {
+    pub canvas_id: u64,
+}
+
+pub struct AcceptOwnershipAccountArgs<'a, 'b: 'a> {
+    // `[signer]` the proposed owner of the place
+    pub pending_owner_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the place state pda
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+}
+
+pub fn get_ix_accept_ownership(canvas_id: u64, pending_owner: Pubkey) -> Instruction {
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(pending_owner, true),
+            AccountMeta::new(place_state_pda, false),
+        ],
+        data: PlaceInstruction::AcceptOwnership(AcceptOwnershipDataArgs { canvas_id })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////
+///////////////////////// CANCEL OWNERSHIP TRANSFER //////////////////////////////
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct CancelOwnershipTransferDataArgs {
+    pub canvas_id: u64,
+}
+
+pub struct CancelOwnershipTransferAccountArgs<'a, 'b: 'a> {
+    // `[signer]` the current owner of the place
+    pub owner_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the place state pda
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+}
+
+pub fn get_ix_cancel_ownership_transfer(canvas_id: u64, owner: Pubkey) -> Instruction {
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(owner, true),
+            AccountMeta::new(place_state_pda, false),
+        ],
+        data: PlaceInstruction::CancelOwnershipTransfer(CancelOwnershipTransferDataArgs {
+            canvas_id,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////
+///////////////////////////// MIGRATE ACCOUNT ////////////////////////////////////
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct MigrateAccountDataArgs {}
+
+pub struct MigrateAccountAccountArgs<'a, 'b: 'a> {
+    // `[writable, signer]` pays the rent for the extra space
+    pub payer_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the place state, patch or gameplay token meta account to migrate
+    pub migrated_acct: &'a AccountInfo<'b>,
+
+    // `[]` the system program
+    pub system_prog_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` pda the account moves to, only for version 0 place states and patches.
+    // Those live at addresses without a canvas id and move into LEGACY_CANVAS_ID
+    pub destination_acct: Option<&'a AccountInfo<'b>>,
+}
+
+pub fn get_ix_migrate_account(payer: Pubkey, migrated_account: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(migrated_account, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: PlaceInstruction::MigrateAccount(MigrateAccountDataArgs {})
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Migrates a version 0 place state or patch out of its legacy address, e.g.
+/// `PlaceState::legacy_pda()` into `PlaceState::pda(LEGACY_CANVAS_ID)`. The legacy account
+/// is closed and its rent goes to the payer
+pub fn get_ix_migrate_legacy_account(
+    payer: Pubkey,
+    legacy_account: Pubkey,
+    destination: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(legacy_account, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new(destination, false),
+        ],
+        data: PlaceInstruction::MigrateAccount(MigrateAccountDataArgs {})
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////
+////////////////////////////// CLEAR REGION //////////////////////////////////////
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct ClearRegionDataArgs {
+    // the canvas being moderated
+    pub canvas_id: u64,
+
+    // inclusive bounds of the rectangle, in pixels from the top left of the place
+    pub x_min: u16,
+    pub y_min: u16,
+    pub x_max: u16,
+    pub y_max: u16,
+
+    // value every pixel in the rectangle is set to
+    pub pixel: u16,
+}
+
+pub struct ClearRegionAccountArgs<'a, 'b: 'a> {
+    // `[signer]` the moderator
+    pub moderator_acct: &'a AccountInfo<'b>,
+
+    // `[]` the place state pda
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+
+    // `[]` the canvas snapshot pda, may not exist yet
+    pub canvas_snapshot_pda_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` every patch touched by the rectangle, see PlaceState::patches_in_rect
+    pub patch_pda_accts: &'a [AccountInfo<'b>],
+}
+
+pub fn get_ix_clear_region(
+    place_state: &PlaceState,
+    moderator: Pubkey,
+    x_min: u16,
+    y_min: u16,
+    x_max: u16,
+    y_max: u16,
+    pixel: u16,
+) -> Instruction {
+    let canvas_id = place_state.canvas_id;
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    let (canvas_snapshot_pda, _) = CanvasSnapshot::pda(canvas_id);
+    let mut accounts = vec![
+        AccountMeta::new_readonly(moderator, true),
+        AccountMeta::new_readonly(place_state_pda, false),
+        AccountMeta::new_readonly(canvas_snapshot_pda, false),
+    ];
+
+    for (patch_x, patch_y) in place_state.patches_in_rect(
+        x_min as usize,
+        y_min as usize,
+        x_max as usize,
+        y_max as usize,
+    ) {
+        let (patch_pda, _) = Patch::pda(canvas_id, patch_x, patch_y);
+        accounts.push(AccountMeta::new(patch_pda, false));
+    }
+
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: PlaceInstruction::ClearRegion(ClearRegionDataArgs {
+            canvas_id,
+            x_min,
+            y_min,
+            x_max,
+            y_max,
+            pixel,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////
+/////////////////////////// BAN GAMEPLAY TOKEN ///////////////////////////////////
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct BanGameplayTokenDataArgs {
+    // the canvas being moderated
+    pub canvas_id: u64,
+
+    // false lifts an earlier ban
+    pub is_banned: bool,
+}
+
+pub struct BanGameplayTokenAccountArgs<'a, 'b: 'a> {
+    // `[signer]` the moderator
+    pub moderator_acct: &'a AccountInfo<'b>,
+
+    // `[]` the place state pda
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the gameplay token meta being banned
+    pub gameplay_token_meta_acct: &'a AccountInfo<'b>,
+}
+
+pub fn get_ix_ban_gameplay_token(
+    canvas_id: u64,
+    moderator: Pubkey,
+    gameplay_token_meta_pda: Pubkey,
+    is_banned: bool,
+) -> Instruction {
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(moderator, true),
+            AccountMeta::new_readonly(place_state_pda, false),
+            AccountMeta::new(gameplay_token_meta_pda, false),
+        ],
+        data: PlaceInstruction::BanGameplayToken(BanGameplayTokenDataArgs {
+            canvas_id,
+            is_banned,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////
+//////////////////////////// START NEW SEASON ////////////////////////////////////
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct StartNewSeasonDataArgs {
+    // the canvas starting a new season
+    pub canvas_id: u64,
+}
+
+pub struct StartNewSeasonAccountArgs<'a, 'b: 'a> {
+    // `[writable, signer]` the owner of the place, pays for the archive account
+    pub owner_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the place state pda, must be frozen
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the canvas snapshot pda, must be sealed for the current freeze
+    pub canvas_snapshot_pda_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the season archive pda for the season that is finishing
+    pub season_archive_pda_acct: &'a AccountInfo<'b>,
+
+    // `[]` the system program
+    pub system_prog_acct: &'a AccountInfo<'b>,
+}
+
+/// `finished_season` should be the current `PlaceState::season`
+pub fn get_ix_start_new_season(canvas_id: u64, owner: Pubkey, finished_season: u32) -> Instruction {
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    let (canvas_snapshot_pda, _) = CanvasSnapshot::pda(canvas_id);
+    let (season_archive_pda, _) = SeasonArchive::pda(canvas_id, finished_season);
+
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(owner, true),
+            AccountMeta::new(place_state_pda, false),
+            AccountMeta::new(canvas_snapshot_pda, false),
+            AccountMeta::new(season_archive_pda, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: PlaceInstruction::StartNewSeason(StartNewSeasonDataArgs { canvas_id })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////
+////////////////////////////// RESET PATCHES /////////////////////////////////////
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct ResetPatchesDataArgs {
+    // the canvas being reset
+    pub canvas_id: u64,
+}
+
+pub struct ResetPatchesAccountArgs<'a, 'b: 'a> {
+    // `[signer]` the owner of the place
+    pub owner_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` the place state pda
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+
+    // `[writable]` pairs of (patch pda, patch attribution pda), starting at
+    // `PlaceState::next_patch_to_reset`. Patches that were never created are skipped
+    pub patch_pda_accts: &'a [AccountInfo<'b>],
+}
+
+pub fn get_ix_reset_patches(
+    place_state: &PlaceState,
+    owner: Pubkey,
+    patch_count: u32,
+) -> Instruction {
+    let canvas_id = place_state.canvas_id;
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(owner, true),
+        AccountMeta::new(place_state_pda, false),
+    ];
+
+    let first_patch = place_state.next_patch_to_reset();
+    let last_patch = first_patch
+        .saturating_add(patch_count)
+        .min(place_state.patch_count());
+    for index in first_patch..last_patch {
+        let (patch_x, patch_y) = place_state.patch_coords(index);
+        let (patch_pda, _) = Patch::pda(canvas_id, patch_x, patch_y);
+        let (patch_attribution_pda, _) = PatchAttribution::pda(canvas_id, patch_x, patch_y);
+        accounts.push(AccountMeta::new(patch_pda, false));
+        accounts.push(AccountMeta::new(patch_attribution_pda, false));
+    }
+
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: PlaceInstruction::ResetPatches(ResetPatchesDataArgs { canvas_id })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////
+////////////////////////// INIT PATCH ATTRIBUTION ////////////////////////////////
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct InitPatchAttributionDataArgs {
+    pub canvas_id: u64,
+    pub x_patch: u8,
+    pub y_patch: u8,
+}
+
+pub struct InitPatchAttributionAccountArgs<'a, 'b: 'a> {
+    /// `[signer]` the payer for the data allocation
+    pub payer_acct: &'a AccountInfo<'b>,
+
+    /// `[]` the place state pda
+    pub place_state_pda_acct: &'a AccountInfo<'b>,
+
+    /// `[]` the existing patch the attribution account is for
+    pub patch_pda_acct: &'a AccountInfo<'b>,
+
+    /// `[writable]` the patch attribution pda we are going to allocate
+    pub patch_attribution_pda_acct: &'a AccountInfo<'b>,
+
+    /// `[]` the system program
+    pub system_acct: &'a AccountInfo<'b>,
+}
+
+pub fn get_ix_init_patch_attribution(
+    canvas_id: u64,
+    payer: Pubkey,
+    x_patch: u8,
+    y_patch: u8,
+) -> Instruction {
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    let (patch_pda, _) = Patch::pda(canvas_id, x_patch, y_patch);
+    let (patch_attribution_pda, _) = PatchAttribution::pda(canvas_id, x_patch, y_patch);
+
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(place_state_pda, false),
+            AccountMeta::new_readonly(patch_pda, false),
+            AccountMeta::new(patch_attribution_pda, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: PlaceInstruction::InitPatchAttribution(InitPatchAttributionDataArgs {
+            canvas_id,
+            x_patch,
+            y_patch,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
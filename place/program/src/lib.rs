@@ -1,3 +1,4 @@
+pub mod allowlist;
 pub mod config;
 pub mod error;
 pub mod instruction;
@@ -4,8 +4,10 @@ use solana_program::{
     account_info::{next_account_info, AccountInfo},
     borsh::try_from_slice_unchecked,
     entrypoint::ProgramResult,
+    hash::Hash,
     msg,
     program::{invoke, invoke_signed},
+    program_error::ProgramError,
     program_pack::Pack,
     pubkey::Pubkey,
     system_instruction, sysvar,
@@ -15,23 +17,40 @@ use solana_program::{
 use crate::{
     id,
     instruction::{
-        ClaimTokensAccountArgs, ClaimTokensDataArgs, InitMintAccountArgs, InitMintDataArgs,
-        InitPatchAccountArgs, InitPatchDataArgs, PlaceInstruction,
-        PurchaseGameplayTokenAccountArgs, PurchaseGameplayTokenDataArgs, SetPixelAccountArgs,
-        SetPixelDataArgs, UpdatePlaceStateAccountArgs, UpdatePlaceStateDataArgs,
+        AcceptOwnershipAccountArgs, AcceptOwnershipDataArgs, ActivateShieldAccountArgs,
+        ActivateShieldDataArgs, AllowlistProof, BanGameplayTokenAccountArgs,
+        BanGameplayTokenDataArgs, CancelOwnershipTransferAccountArgs,
+        CancelOwnershipTransferDataArgs, ClaimTokensAccountArgs, ClaimTokensBatchAccountArgs,
+        ClaimTokensBatchDataArgs, ClaimTokensDataArgs, ClearRegionAccountArgs, ClearRegionDataArgs,
+        DetonateBombAccountArgs, DetonateBombDataArgs, GrantRoleAccountArgs, GrantRoleDataArgs,
+        InitMintAccountArgs, InitMintDataArgs, InitPatchAccountArgs,
+        InitPatchAttributionAccountArgs, InitPatchAttributionDataArgs, InitPatchDataArgs,
+        InitializePlaceAccountArgs, InitializePlaceDataArgs, MigrateAccountAccountArgs,
+        MigrateAccountDataArgs, MintRegionNftAccountArgs, MintRegionNftDataArgs, PaymentMethod,
+        PlaceInstruction, ProposeOwnerAccountArgs, ProposeOwnerDataArgs,
+        PurchaseGameplayTokenAccountArgs, PurchaseGameplayTokenDataArgs, ResetPatchesAccountArgs,
+        ResetPatchesDataArgs, RetireGameplayTokenAccountArgs, RetireGameplayTokenDataArgs,
+        RevokeRoleAccountArgs, RevokeRoleDataArgs, SealCanvasAccountArgs, SealCanvasDataArgs,
+        SetPixelAccountArgs, SetPixelDataArgs, SetPixelsAccountArgs, SetPixelsDataArgs,
+        StartNewSeasonAccountArgs, StartNewSeasonDataArgs, SweepGameplayTokensAccountArgs,
+        SweepGameplayTokensDataArgs, UpdatePlaceStateAccountArgs, UpdatePlaceStateDataArgs,
+        UpgradeGameplayTokenAccountArgs, UpgradeGameplayTokenDataArgs, WithdrawTreasuryAccountArgs,
+        WithdrawTreasuryDataArgs,
     },
     utils::{
-        assert_mpl_metadata_prog, assert_owned_by_token_prog, assert_system_prog, assert_token_prog,
+        assert_mpl_metadata_prog, assert_owned_by_token_prog, assert_system_prog,
+        assert_token_prog, assert_upgrade_authority,
     },
 };
 
 use spl_token::{
-    instruction::initialize_mint,
+    instruction::{initialize_mint, set_authority, AuthorityType},
     state::{Account as TokenAccount, Mint},
 };
 
 use spl_associated_token_account::{create_associated_token_account, get_associated_token_address};
 
+use crate::allowlist::{allowlist_leaf, verify_allowlist_proof};
 use crate::error::PlaceError;
 use crate::error::PlaceError::{
     IncorrectPatchPDA, InvalidPatchCoordinates, PatchAccountAlreadyInitialized,
@@ -42,8 +61,10 @@ use mpl_token_metadata::{
 };
 
 use crate::state::{
-    find_address_for_patch, GameplayTokenMeta, GameplayTokenType, Patch, PlaceAccountType,
-    PlaceState, PATCH_DATA_LEN, PATCH_PDA_PREFIX, PATCH_SIZE_PX, PLACE_HEIGHT_PX, PLACE_WIDTH_PX,
+    find_address_for_patch, AllowlistReceipt, CanvasSnapshot, GameplayTokenMeta,
+    GameplayTokenSales, GameplayTokenType, Patch, PatchAttribution, PatchShield, PauseFlags,
+    PixelAttribution, PlaceAccountType, PlaceRole, PlaceState, PurchasePhase, RegionNftMeta,
+    SeasonArchive, CURRENT_ACCOUNT_VERSION, GAMEPLAY_TOKEN_META_RESERVED_LEN, PATCH_PDA_PREFIX,
 };
 
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -66,12 +87,10 @@ impl Processor {
                 let acct_info_iter = &mut accounts.iter();
                 let current_owner_acct = next_account_info(acct_info_iter)?;
                 let place_state_pda_acct = next_account_info(acct_info_iter)?;
-                let system_acct = next_account_info(acct_info_iter)?;
 
                 let acct_args = UpdatePlaceStateAccountArgs {
                     current_owner_acct,
                     place_state_pda_acct,
-                    system_acct,
                 };
 
                 process_update_place_state(program_id, acct_args, args)
@@ -99,11 +118,15 @@ impl Processor {
                 let payer_acct = next_account_info(acct_info_iter)?;
                 let patch_pda_acct = next_account_info(acct_info_iter)?;
                 let system_acct = next_account_info(acct_info_iter)?;
+                let place_state_pda_acct = next_account_info(acct_info_iter)?;
+                let patch_attribution_pda_acct = next_account_info(acct_info_iter)?;
 
                 let acct_args = InitPatchAccountArgs {
                     payer_acct,
                     patch_pda_acct,
                     system_acct,
+                    place_state_pda_acct,
+                    patch_attribution_pda_acct,
                 };
 
                 process_init_patch(program_id, acct_args, &args)
@@ -124,6 +147,11 @@ impl Processor {
                     ata_prog_acct: next_account_info(acct_info_iter)?,
                     system_prog_acct: next_account_info(acct_info_iter)?,
                     rent_sysvar_acct: next_account_info(acct_info_iter)?,
+                    treasury_pda_acct: next_account_info(acct_info_iter)?,
+                    allowlist_receipt_pda_acct: next_account_info(acct_info_iter)?,
+                    place_token_mint_acct: next_account_info(acct_info_iter).ok(),
+                    payer_place_token_ata_acct: next_account_info(acct_info_iter).ok(),
+                    treasury_place_token_ata_acct: next_account_info(acct_info_iter).ok(),
                 };
 
                 process_purchase_gameplay_token(program_id, acct_args, args)
@@ -138,10 +166,28 @@ impl Processor {
                     gameplay_token_meta_acct: next_account_info(acct_info_iter)?,
                     payer_gameplay_token_acct: next_account_info(acct_info_iter)?,
                     system_acct: next_account_info(acct_info_iter)?,
+                    place_state_pda_acct: next_account_info(acct_info_iter)?,
+                    patch_attribution_pda_acct: next_account_info(acct_info_iter)?,
                 };
 
                 process_set_pixel(program_id, acct_args, &args)
             }
+            PlaceInstruction::SetPixels(args) => {
+                msg!("PlaceIX: SetPixels");
+                let acct_info_iter = &mut accounts.iter();
+
+                let acct_args = SetPixelsAccountArgs {
+                    payer_acct: next_account_info(acct_info_iter)?,
+                    patch_pda_acct: next_account_info(acct_info_iter)?,
+                    gameplay_token_meta_acct: next_account_info(acct_info_iter)?,
+                    payer_gameplay_token_acct: next_account_info(acct_info_iter)?,
+                    system_acct: next_account_info(acct_info_iter)?,
+                    place_state_pda_acct: next_account_info(acct_info_iter)?,
+                    patch_attribution_pda_acct: next_account_info(acct_info_iter)?,
+                };
+
+                process_set_pixels(program_id, acct_args, &args)
+            }
             PlaceInstruction::ClaimTokens(args) => {
                 msg!("PlaceIX: ClaimTokens");
                 let acct_info_iter = &mut accounts.iter();
@@ -158,778 +204,3731 @@ impl Processor {
 
                 process_claim_tokens(program_id, acct_args, args)
             }
-        }
-    }
-}
+            PlaceInstruction::ClaimTokensBatch(args) => {
+                msg!("PlaceIX: ClaimTokensBatch");
+                let acct_info_iter = &mut accounts.iter();
 
-fn process_claim_tokens(
-    program_id: &Pubkey,
-    acct_args: ClaimTokensAccountArgs,
-    data_args: ClaimTokensDataArgs,
-) -> ProgramResult {
-    let ClaimTokensAccountArgs {
-        claimer_acct,
-        gameplay_token_pda_acct,
-        gameplay_token_ata_acct,
-        place_token_mint_acct,
-        place_token_dest_ata_acct,
-        place_state_pda_acct,
-        token_prog_acct,
-    } = acct_args;
+                let acct_args = ClaimTokensBatchAccountArgs {
+                    claimer_acct: next_account_info(acct_info_iter)?,
+                    place_token_mint_acct: next_account_info(acct_info_iter)?,
+                    place_token_dest_ata_acct: next_account_info(acct_info_iter)?,
+                    place_state_pda_acct: next_account_info(acct_info_iter)?,
+                    token_prog_acct: next_account_info(acct_info_iter)?,
+                    gameplay_token_accts: acct_info_iter.as_slice(),
+                };
 
-    // Theres a certain amount of paranoia with all these validations
-    // not sure all checks are necessary, but erring on the side of over checking
+                process_claim_tokens_batch(program_id, acct_args, &args)
+            }
+            PlaceInstruction::GrantRole(args) => {
+                msg!("PlaceIX: GrantRole");
+                let acct_info_iter = &mut accounts.iter();
 
-    // not strictly necessary
-    assert_signer(claimer_acct)?;
-    assert_token_prog(token_prog_acct)?;
-    assert_owned_by_token_prog(place_token_mint_acct)?;
+                let acct_args = GrantRoleAccountArgs {
+                    owner_acct: next_account_info(acct_info_iter)?,
+                    place_state_pda_acct: next_account_info(acct_info_iter)?,
+                };
 
-    // the place state owns all the accounts, so need to check against it's pda
-    let (place_state_pda, place_state_pda_bump) = PlaceState::pda();
+                process_grant_role(program_id, acct_args, &args)
+            }
+            PlaceInstruction::RevokeRole(args) => {
+                msg!("PlaceIX: RevokeRole");
+                let acct_info_iter = &mut accounts.iter();
 
-    if *place_state_pda_acct.key != place_state_pda {
-        return Err(PlaceError::IncorrectPlaceStatePDA.into());
-    }
+                let acct_args = RevokeRoleAccountArgs {
+                    owner_acct: next_account_info(acct_info_iter)?,
+                    place_state_pda_acct: next_account_info(acct_info_iter)?,
+                };
 
-    if *gameplay_token_pda_acct.owner != crate::id() {
-        return Err(PlaceError::InvalidGameplayTokenMetaPDAOwner.into());
-    }
+                process_revoke_role(program_id, acct_args, &args)
+            }
+            PlaceInstruction::ProposeOwner(args) => {
+                msg!("PlaceIX: ProposeOwner");
+                let acct_info_iter = &mut accounts.iter();
 
-    let (place_token_mint_pda, _) = PlaceState::token_mint_pda();
-    msg!("desered place state");
+                let acct_args = ProposeOwnerAccountArgs {
+                    owner_acct: next_account_info(acct_info_iter)?,
+                    place_state_pda_acct: next_account_info(acct_info_iter)?,
+                };
 
-    if *place_token_mint_acct.key != place_token_mint_pda {
-        return Err(PlaceError::InvalidPlaceTokenMintPDA.into());
-    }
+                process_propose_owner(program_id, acct_args, &args)
+            }
+            PlaceInstruction::AcceptOwnership(args) => {
+                msg!("PlaceIX: AcceptOwnership");
+                let acct_info_iter = &mut accounts.iter();
 
-    let mut gameplay_token_meta = GameplayTokenMeta::from_account_info(gameplay_token_pda_acct)?;
-    msg!("desered gpt meta");
+                let acct_args = AcceptOwnershipAccountArgs {
+                    pending_owner_acct: next_account_info(acct_info_iter)?,
+                    place_state_pda_acct: next_account_info(acct_info_iter)?,
+                };
 
-    if gameplay_token_meta.place_tokens_owed == 0 {
-        return Err(PlaceError::NoTokensToBeClaimed.into());
-    }
+                process_accept_ownership(program_id, acct_args, &args)
+            }
+            PlaceInstruction::CancelOwnershipTransfer(args) => {
+                msg!("PlaceIX: CancelOwnershipTransfer");
+                let acct_info_iter = &mut accounts.iter();
 
-    let (gameplay_token_pda, _) = gameplay_token_meta.pda_for_instance();
-    if gameplay_token_pda != *gameplay_token_pda_acct.key {
-        return Err(PlaceError::InvalidGameplayTokenMetaPDA.into());
-    }
+                let acct_args = CancelOwnershipTransferAccountArgs {
+                    owner_acct: next_account_info(acct_info_iter)?,
+                    place_state_pda_acct: next_account_info(acct_info_iter)?,
+                };
 
-    let (gameplay_token_mint_pda, _) =
-        GameplayTokenMeta::token_mint_pda(gameplay_token_meta.random_seed);
-    if gameplay_token_mint_pda != gameplay_token_meta.token_mint_pda {
-        return Err(PlaceError::InvalidGameplayTokenMetaPDA.into());
-    }
+                process_cancel_ownership_transfer(program_id, acct_args, &args)
+            }
+            PlaceInstruction::InitializePlace(args) => {
+                msg!("PlaceIX: InitializePlace");
+                let acct_info_iter = &mut accounts.iter();
 
-    let gameplay_token_ata =
-        TokenAccount::unpack_from_slice(&gameplay_token_ata_acct.data.borrow())?;
+                let acct_args = InitializePlaceAccountArgs {
+                    payer_acct: next_account_info(acct_info_iter)?,
+                    upgrade_authority_acct: next_account_info(acct_info_iter)?,
+                    place_state_pda_acct: next_account_info(acct_info_iter)?,
+                    program_data_acct: next_account_info(acct_info_iter)?,
+                    system_prog_acct: next_account_info(acct_info_iter)?,
+                };
 
-    msg!("desered gpt ata");
-    if gameplay_token_ata.amount != 1 {
-        return Err(PlaceError::InvalidGameplayTokenAccountBalance.into());
-    }
-    // this also probably not strictly necessary
-    if gameplay_token_ata.owner != *claimer_acct.key {
-        return Err(PlaceError::GameplayTokenATADidNotMatchSigner.into());
-    }
+                process_initialize_place(program_id, acct_args, args)
+            }
+            PlaceInstruction::MigrateAccount(args) => {
+                msg!("PlaceIX: MigrateAccount");
+                let acct_info_iter = &mut accounts.iter();
 
-    if gameplay_token_ata.mint != gameplay_token_meta.token_mint_pda {
-        return Err(PlaceError::GameplayTokenATAMintDidNotMatch.into());
-    }
+                let acct_args = MigrateAccountAccountArgs {
+                    payer_acct: next_account_info(acct_info_iter)?,
+                    migrated_acct: next_account_info(acct_info_iter)?,
+                    system_prog_acct: next_account_info(acct_info_iter)?,
+                    destination_acct: next_account_info(acct_info_iter).ok(),
+                };
 
-    let place_token_ata =
-        TokenAccount::unpack_from_slice(&place_token_dest_ata_acct.data.borrow())?;
-    msg!("desered place token ata");
+                process_migrate_account(program_id, acct_args, &args)
+            }
+            PlaceInstruction::ClearRegion(args) => {
+                msg!("PlaceIX: ClearRegion");
+                let acct_info_iter = &mut accounts.iter();
 
-    if place_token_mint_pda != place_token_ata.mint {
-        return Err(PlaceError::InvalidPlaceTokenDestinationATA.into());
-    }
+                let acct_args = ClearRegionAccountArgs {
+                    moderator_acct: next_account_info(acct_info_iter)?,
+                    place_state_pda_acct: next_account_info(acct_info_iter)?,
+                    canvas_snapshot_pda_acct: next_account_info(acct_info_iter)?,
+                    patch_pda_accts: acct_info_iter.as_slice(),
+                };
 
-    let place_state_acct_pda_seeds = &[PlaceState::PREFIX.as_bytes(), &[place_state_pda_bump]];
+                process_clear_region(program_id, acct_args, &args)
+            }
+            PlaceInstruction::BanGameplayToken(args) => {
+                msg!("PlaceIX: BanGameplayToken");
+                let acct_info_iter = &mut accounts.iter();
 
-    // TODO(will): check if dest account owned by claimer?
-    // check anything else?
+                let acct_args = BanGameplayTokenAccountArgs {
+                    moderator_acct: next_account_info(acct_info_iter)?,
+                    place_state_pda_acct: next_account_info(acct_info_iter)?,
+                    gameplay_token_meta_acct: next_account_info(acct_info_iter)?,
+                };
 
-    msg!("TAP: Minting place tokens into dest ata");
-    let mint_tokens_ix = spl_token::instruction::mint_to(
-        token_prog_acct.key,
-        place_token_mint_acct.key,
-        place_token_dest_ata_acct.key,
-        place_state_pda_acct.key,
-        &[place_state_pda_acct.key],
-        gameplay_token_meta.place_tokens_owed as u64,
-    )?;
+                process_ban_gameplay_token(program_id, acct_args, &args)
+            }
+            PlaceInstruction::StartNewSeason(args) => {
+                msg!("PlaceIX: StartNewSeason");
+                let acct_info_iter = &mut accounts.iter();
 
-    invoke_signed(
-        &mint_tokens_ix,
-        &[
-            (*token_prog_acct).clone(),
-            (*place_token_mint_acct).clone(),
-            (*place_token_dest_ata_acct).clone(),
-            (*place_state_pda_acct).clone(),
-        ],
-        &[place_state_acct_pda_seeds],
-    )?;
+                let acct_args = StartNewSeasonAccountArgs {
+                    owner_acct: next_account_info(acct_info_iter)?,
+                    place_state_pda_acct: next_account_info(acct_info_iter)?,
+                    canvas_snapshot_pda_acct: next_account_info(acct_info_iter)?,
+                    season_archive_pda_acct: next_account_info(acct_info_iter)?,
+                    system_prog_acct: next_account_info(acct_info_iter)?,
+                };
 
-    gameplay_token_meta.place_tokens_owed = 0;
-    gameplay_token_meta.serialize(&mut *gameplay_token_pda_acct.data.borrow_mut())?;
+                process_start_new_season(program_id, acct_args, &args)
+            }
+            PlaceInstruction::ResetPatches(args) => {
+                msg!("PlaceIX: ResetPatches");
+                let acct_info_iter = &mut accounts.iter();
 
-    Ok(())
-}
+                let acct_args = ResetPatchesAccountArgs {
+                    owner_acct: next_account_info(acct_info_iter)?,
+                    place_state_pda_acct: next_account_info(acct_info_iter)?,
+                    patch_pda_accts: acct_info_iter.as_slice(),
+                };
 
-fn process_init_mint(
-    program_id: &Pubkey,
-    acct_args: InitMintAccountArgs,
-    data_args: InitMintDataArgs,
-) -> ProgramResult {
-    let InitMintAccountArgs {
-        owner_acct,
-        place_state_pda_acct,
-        place_token_mint_pda_acct,
-        place_token_mint_mpl_pda_acct,
-        mpl_metadata_prog_acct,
-        token_prog_acct,
-        system_prog_acct,
-        rent_sysvar_acct,
-    } = acct_args;
+                process_reset_patches(program_id, acct_args, &args)
+            }
+            PlaceInstruction::InitPatchAttribution(args) => {
+                msg!("PlaceIX: InitPatchAttribution");
+                let acct_info_iter = &mut accounts.iter();
 
-    assert_signer(owner_acct)?;
-    assert_system_prog(system_prog_acct)?;
-    assert_token_prog(token_prog_acct)?;
-    assert_mpl_metadata_prog(mpl_metadata_prog_acct)?;
+                let acct_args = InitPatchAttributionAccountArgs {
+                    payer_acct: next_account_info(acct_info_iter)?,
+                    place_state_pda_acct: next_account_info(acct_info_iter)?,
+                    patch_pda_acct: next_account_info(acct_info_iter)?,
+                    patch_attribution_pda_acct: next_account_info(acct_info_iter)?,
+                    system_acct: next_account_info(acct_info_iter)?,
+                };
 
-    let (place_state_pda, place_state_pda_bump) = PlaceState::pda();
-    if place_state_pda != *place_state_pda_acct.key {
-        return Err(PlaceError::InvalidAccountArgument.into());
-    }
+                process_init_patch_attribution(program_id, acct_args, &args)
+            }
+            PlaceInstruction::DetonateBomb(args) => {
+                msg!("PlaceIX: DetonateBomb");
+                let acct_info_iter = &mut accounts.iter();
 
-    let (place_token_mpl_meta_pda, _) = PlaceState::token_mint_mpl_metadata_pda();
-    if place_token_mpl_meta_pda != *place_token_mint_mpl_pda_acct.key {
-        return Err(PlaceError::InvalidPlaceTokenMPLMetadataPDA.into());
-    }
+                let acct_args = DetonateBombAccountArgs {
+                    payer_acct: next_account_info(acct_info_iter)?,
+                    gameplay_token_meta_acct: next_account_info(acct_info_iter)?,
+                    payer_gameplay_token_acct: next_account_info(acct_info_iter)?,
+                    place_state_pda_acct: next_account_info(acct_info_iter)?,
+                    patch_pda_accts: acct_info_iter.as_slice(),
+                };
 
-    let place_state = PlaceState::from_account_info(place_state_pda_acct)?;
+                process_detonate_bomb(program_id, acct_args, &args)
+            }
+            PlaceInstruction::SealCanvas(args) => {
+                msg!("PlaceIX: SealCanvas");
+                let acct_info_iter = &mut accounts.iter();
 
-    if place_state.owner != *owner_acct.key {
-        return Err(PlaceError::InvalidOwner.into());
-    }
+                let acct_args = SealCanvasAccountArgs {
+                    owner_acct: next_account_info(acct_info_iter)?,
+                    place_state_pda_acct: next_account_info(acct_info_iter)?,
+                    canvas_snapshot_pda_acct: next_account_info(acct_info_iter)?,
+                    system_prog_acct: next_account_info(acct_info_iter)?,
+                    patch_pda_accts: acct_info_iter.as_slice(),
+                };
 
-    let (place_token_mint_pda, place_token_mint_pda_bump) = PlaceState::token_mint_pda();
-    if place_token_mint_pda != *place_token_mint_pda_acct.key {
-        return Err(PlaceError::InvalidPlaceTokenMintPDA.into());
-    }
+                process_seal_canvas(program_id, acct_args, args)
+            }
+            PlaceInstruction::MintRegionNft(args) => {
+                msg!("PlaceIX: MintRegionNft");
+                let acct_info_iter = &mut accounts.iter();
 
-    if !place_token_mint_pda_acct.data_is_empty() {
-        return Err(PlaceError::PlaceTokenMintAlreadyInitialized.into());
-    }
+                let acct_args = MintRegionNftAccountArgs {
+                    owner_acct: next_account_info(acct_info_iter)?,
+                    place_state_pda_acct: next_account_info(acct_info_iter)?,
+                    region_meta_pda_acct: next_account_info(acct_info_iter)?,
+                    region_token_mint_pda_acct: next_account_info(acct_info_iter)?,
+                    region_token_ata_acct: next_account_info(acct_info_iter)?,
+                    region_token_mpl_meta_acct: next_account_info(acct_info_iter)?,
+                    mpl_metadata_prog_acct: next_account_info(acct_info_iter)?,
+                    token_prog_acct: next_account_info(acct_info_iter)?,
+                    ata_prog_acct: next_account_info(acct_info_iter)?,
+                    system_prog_acct: next_account_info(acct_info_iter)?,
+                    rent_sysvar_acct: next_account_info(acct_info_iter)?,
+                    patch_pda_accts: acct_info_iter.as_slice(),
+                };
 
-    let place_token_mint_seeds = &[
-        PlaceState::PREFIX.as_bytes(),
-        PlaceState::TOKEN_MINT_PREFIX.as_bytes(),
-        &[place_token_mint_pda_bump],
-    ];
+                process_mint_region_nft(program_id, acct_args, args)
+            }
+            PlaceInstruction::ActivateShield(args) => {
+                msg!("PlaceIX: ActivateShield");
+                let acct_info_iter = &mut accounts.iter();
 
-    msg!("TAP: Allocating Token Mint Account");
+                let acct_args = ActivateShieldAccountArgs {
+                    payer_acct: next_account_info(acct_info_iter)?,
+                    patch_pda_acct: next_account_info(acct_info_iter)?,
+                    gameplay_token_meta_acct: next_account_info(acct_info_iter)?,
+                    payer_gameplay_token_acct: next_account_info(acct_info_iter)?,
+                    place_state_pda_acct: next_account_info(acct_info_iter)?,
+                };
 
-    create_or_allocate_account_raw(
-        *token_prog_acct.key,
-        place_token_mint_pda_acct,
-        system_prog_acct,
-        owner_acct,
-        Mint::LEN,
-        place_token_mint_seeds,
-    )?;
+                process_activate_shield(program_id, acct_args, &args)
+            }
+            PlaceInstruction::WithdrawTreasury(args) => {
+                msg!("PlaceIX: WithdrawTreasury");
+                let acct_info_iter = &mut accounts.iter();
 
-    msg!("TAP: Init token mint");
+                let acct_args = WithdrawTreasuryAccountArgs {
+                    owner_acct: next_account_info(acct_info_iter)?,
+                    place_state_pda_acct: next_account_info(acct_info_iter)?,
+                    treasury_pda_acct: next_account_info(acct_info_iter)?,
+                    destination_acct: next_account_info(acct_info_iter)?,
+                    system_prog_acct: next_account_info(acct_info_iter)?,
+                };
 
-    let init_mint_ix = initialize_mint(
+                process_withdraw_treasury(program_id, acct_args, &args)
+            }
+            PlaceInstruction::SweepGameplayTokens(args) => {
+                msg!("PlaceIX: SweepGameplayTokens");
+                let acct_info_iter = &mut accounts.iter();
+
+                let acct_args = SweepGameplayTokensAccountArgs {
+                    place_state_pda_acct: next_account_info(acct_info_iter)?,
+                    treasury_pda_acct: next_account_info(acct_info_iter)?,
+                    gameplay_token_meta_accts: acct_info_iter.as_slice(),
+                };
+
+                process_sweep_gameplay_tokens(program_id, acct_args, &args)
+            }
+            PlaceInstruction::RetireGameplayToken(args) => {
+                msg!("PlaceIX: RetireGameplayToken");
+                let acct_info_iter = &mut accounts.iter();
+
+                let acct_args = RetireGameplayTokenAccountArgs {
+                    holder_acct: next_account_info(acct_info_iter)?,
+                    gameplay_token_pda_acct: next_account_info(acct_info_iter)?,
+                    gameplay_token_mint_acct: next_account_info(acct_info_iter)?,
+                    gameplay_token_ata_acct: next_account_info(acct_info_iter)?,
+                    place_token_mint_acct: next_account_info(acct_info_iter)?,
+                    place_token_dest_ata_acct: next_account_info(acct_info_iter)?,
+                    place_state_pda_acct: next_account_info(acct_info_iter)?,
+                    treasury_pda_acct: next_account_info(acct_info_iter)?,
+                    token_prog_acct: next_account_info(acct_info_iter)?,
+                    system_prog_acct: next_account_info(acct_info_iter)?,
+                };
+
+                process_retire_gameplay_token(program_id, acct_args, &args)
+            }
+            PlaceInstruction::UpgradeGameplayToken(args) => {
+                msg!("PlaceIX: UpgradeGameplayToken");
+                let acct_info_iter = &mut accounts.iter();
+
+                let acct_args = UpgradeGameplayTokenAccountArgs {
+                    holder_acct: next_account_info(acct_info_iter)?,
+                    gameplay_token_meta_acct: next_account_info(acct_info_iter)?,
+                    gameplay_token_ata_acct: next_account_info(acct_info_iter)?,
+                    place_token_mint_acct: next_account_info(acct_info_iter)?,
+                    place_token_ata_acct: next_account_info(acct_info_iter)?,
+                    place_state_pda_acct: next_account_info(acct_info_iter)?,
+                    token_prog_acct: next_account_info(acct_info_iter)?,
+                };
+
+                process_upgrade_gameplay_token(program_id, acct_args, &args)
+            }
+        }
+    }
+}
+
+fn process_claim_tokens(
+    program_id: &Pubkey,
+    acct_args: ClaimTokensAccountArgs,
+    data_args: ClaimTokensDataArgs,
+) -> ProgramResult {
+    let ClaimTokensAccountArgs {
+        claimer_acct,
+        gameplay_token_pda_acct,
+        gameplay_token_ata_acct,
+        place_token_mint_acct,
+        place_token_dest_ata_acct,
+        place_state_pda_acct,
+        token_prog_acct,
+    } = acct_args;
+
+    let ClaimTokensDataArgs { canvas_id } = data_args;
+
+    let mut state = assert_claim_accounts(
+        canvas_id,
+        claimer_acct,
+        place_token_mint_acct,
+        place_token_dest_ata_acct,
+        place_state_pda_acct,
+        token_prog_acct,
+    )?;
+    if state.paused.contains(PauseFlags::CLAIMS) {
+        return Err(PlaceError::ClaimsPaused.into());
+    }
+
+    let mut gameplay_token_meta = load_claimable_gameplay_token(
+        &state,
+        claimer_acct,
+        gameplay_token_pda_acct,
+        gameplay_token_ata_acct,
+    )?;
+
+    if gameplay_token_meta.place_tokens_owed == 0 {
+        return Err(PlaceError::NoTokensToBeClaimed.into());
+    }
+
+    // TODO(will): check if dest account owned by claimer?
+    // check anything else?
+
+    // whatever the emission schedule doesn't allow yet stays owed
+    let budget = place_token_budget(&state, place_token_mint_acct)?;
+    let amount = (gameplay_token_meta.place_tokens_owed as u64).min(budget);
+    if amount == 0 {
+        return Err(PlaceError::PlaceTokenEmissionExhausted.into());
+    }
+
+    msg!(
+        "TAP: Minting {} of {} owed place tokens into dest ata",
+        amount,
+        gameplay_token_meta.place_tokens_owed
+    );
+    mint_place_tokens(
+        canvas_id,
+        &mut state,
+        place_state_pda_acct,
+        place_token_mint_acct,
+        place_token_dest_ata_acct,
+        token_prog_acct,
+        amount,
+    )?;
+
+    gameplay_token_meta.place_tokens_owed -= amount as u32;
+    gameplay_token_meta.serialize(&mut *gameplay_token_pda_acct.data.borrow_mut())?;
+
+    Ok(())
+}
+
+fn process_claim_tokens_batch(
+    _program_id: &Pubkey,
+    acct_args: ClaimTokensBatchAccountArgs,
+    data_args: &ClaimTokensBatchDataArgs,
+) -> ProgramResult {
+    let ClaimTokensBatchAccountArgs {
+        claimer_acct,
+        place_token_mint_acct,
+        place_token_dest_ata_acct,
+        place_state_pda_acct,
+        token_prog_acct,
+        gameplay_token_accts,
+    } = acct_args;
+
+    let canvas_id = data_args.canvas_id;
+
+    let mut state = assert_claim_accounts(
+        canvas_id,
+        claimer_acct,
+        place_token_mint_acct,
+        place_token_dest_ata_acct,
+        place_state_pda_acct,
+        token_prog_acct,
+    )?;
+    if state.paused.contains(PauseFlags::CLAIMS) {
+        return Err(PlaceError::ClaimsPaused.into());
+    }
+
+    if gameplay_token_accts.len() % 2 != 0 {
+        return Err(PlaceError::InvalidAccountArgument.into());
+    }
+
+    let mut budget = place_token_budget(&state, place_token_mint_acct)?;
+    let mut any_owed = false;
+    let mut total_amount: u64 = 0;
+    for gameplay_token_pair in gameplay_token_accts.chunks_exact(2) {
+        let gameplay_token_pda_acct = &gameplay_token_pair[0];
+        let mut gameplay_token_meta = load_claimable_gameplay_token(
+            &state,
+            claimer_acct,
+            gameplay_token_pda_acct,
+            &gameplay_token_pair[1],
+        )?;
+
+        if gameplay_token_meta.place_tokens_owed == 0 {
+            continue;
+        }
+        any_owed = true;
+
+        // once the emission budget runs out the remaining pairs are still validated,
+        // but everything they are owed stays owed
+        let amount = (gameplay_token_meta.place_tokens_owed as u64).min(budget);
+        if amount == 0 {
+            continue;
+        }
+
+        budget -= amount;
+        total_amount += amount;
+
+        // written right away so a meta passed twice is only paid once
+        gameplay_token_meta.place_tokens_owed -= amount as u32;
+        gameplay_token_meta.serialize(&mut *gameplay_token_pda_acct.data.borrow_mut())?;
+    }
+
+    if !any_owed {
+        return Err(PlaceError::NoTokensToBeClaimed.into());
+    }
+
+    if total_amount == 0 {
+        return Err(PlaceError::PlaceTokenEmissionExhausted.into());
+    }
+
+    msg!(
+        "TAP: Minting {} place tokens from {} gameplay tokens into dest ata",
+        total_amount,
+        gameplay_token_accts.len() / 2
+    );
+    mint_place_tokens(
+        canvas_id,
+        &mut state,
+        place_state_pda_acct,
+        place_token_mint_acct,
+        place_token_dest_ata_acct,
+        token_prog_acct,
+        total_amount,
+    )
+}
+
+/// Checks the accounts shared by every claim, the gameplay tokens are checked separately
+fn assert_claim_accounts(
+    canvas_id: u64,
+    claimer_acct: &AccountInfo,
+    place_token_mint_acct: &AccountInfo,
+    place_token_dest_ata_acct: &AccountInfo,
+    place_state_pda_acct: &AccountInfo,
+    token_prog_acct: &AccountInfo,
+) -> Result<PlaceState, ProgramError> {
+    // Theres a certain amount of paranoia with all these validations
+    // not sure all checks are necessary, but erring on the side of over checking
+
+    // not strictly necessary
+    assert_signer(claimer_acct)?;
+    assert_token_prog(token_prog_acct)?;
+    assert_owned_by_token_prog(place_token_mint_acct)?;
+
+    // the place state owns all the accounts, so need to check against it's pda
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+
+    if *place_state_pda_acct.key != place_state_pda {
+        return Err(PlaceError::IncorrectPlaceStatePDA.into());
+    }
+
+    // claims stay open while frozen, it is the last chance to claim before a new season
+    // resets what gameplay tokens are owed
+    let state = PlaceState::from_account_info(place_state_pda_acct)?;
+
+    let (place_token_mint_pda, _) = PlaceState::token_mint_pda(canvas_id);
+
+    if *place_token_mint_acct.key != place_token_mint_pda {
+        return Err(PlaceError::InvalidPlaceTokenMintPDA.into());
+    }
+
+    let place_token_ata =
+        TokenAccount::unpack_from_slice(&place_token_dest_ata_acct.data.borrow())?;
+
+    if place_token_mint_pda != place_token_ata.mint {
+        return Err(PlaceError::InvalidPlaceTokenDestinationATA.into());
+    }
+
+    Ok(state)
+}
+
+/// Loads a gameplay token meta the claimer can claim place tokens from, checking the
+/// claimer holds the NFT for it
+fn load_claimable_gameplay_token(
+    state: &PlaceState,
+    claimer_acct: &AccountInfo,
+    gameplay_token_pda_acct: &AccountInfo,
+    gameplay_token_ata_acct: &AccountInfo,
+) -> Result<GameplayTokenMeta, ProgramError> {
+    if *gameplay_token_pda_acct.owner != crate::id() {
+        return Err(PlaceError::InvalidGameplayTokenMetaPDAOwner.into());
+    }
+
+    let mut gameplay_token_meta = GameplayTokenMeta::from_account_info(gameplay_token_pda_acct)?;
+
+    let (gameplay_token_pda, _) = gameplay_token_meta.pda_for_instance();
+    if gameplay_token_pda != *gameplay_token_pda_acct.key {
+        return Err(PlaceError::InvalidGameplayTokenMetaPDA.into());
+    }
+
+    // tokens are only earned on the canvas the gameplay token was purchased for
+    if gameplay_token_meta.canvas_id != state.canvas_id {
+        return Err(PlaceError::GameplayTokenCanvasMismatch.into());
+    }
+
+    if gameplay_token_meta.is_banned {
+        return Err(PlaceError::GameplayTokenBanned.into());
+    }
+
+    gameplay_token_meta.start_season(state.season);
+
+    let (gameplay_token_mint_pda, _) =
+        GameplayTokenMeta::token_mint_pda(gameplay_token_meta.random_seed);
+    if gameplay_token_mint_pda != gameplay_token_meta.token_mint_pda {
+        return Err(PlaceError::InvalidGameplayTokenMetaPDA.into());
+    }
+
+    let gameplay_token_ata =
+        TokenAccount::unpack_from_slice(&gameplay_token_ata_acct.data.borrow())?;
+
+    if gameplay_token_ata.amount != 1 {
+        return Err(PlaceError::InvalidGameplayTokenAccountBalance.into());
+    }
+    // this also probably not strictly necessary
+    if gameplay_token_ata.owner != *claimer_acct.key {
+        return Err(PlaceError::GameplayTokenATADidNotMatchSigner.into());
+    }
+
+    if gameplay_token_ata.mint != gameplay_token_meta.token_mint_pda {
+        return Err(PlaceError::GameplayTokenATAMintDidNotMatch.into());
+    }
+
+    Ok(gameplay_token_meta)
+}
+
+/// Place tokens claims can mint right now under the emission schedule
+fn place_token_budget(
+    state: &PlaceState,
+    place_token_mint_acct: &AccountInfo,
+) -> Result<u64, ProgramError> {
+    let place_token_mint = Mint::unpack_from_slice(&place_token_mint_acct.data.borrow())?;
+    let clock = Clock::get()?;
+    Ok(state.place_token_budget(place_token_mint.supply, clock.slot))
+}
+
+/// Mints place tokens into the destination account, signed by the place state pda, and
+/// counts them against the emission schedule. Callers check the amount fits the budget.
+fn mint_place_tokens<'a>(
+    canvas_id: u64,
+    state: &mut PlaceState,
+    place_state_pda_acct: &AccountInfo<'a>,
+    place_token_mint_acct: &AccountInfo<'a>,
+    place_token_dest_ata_acct: &AccountInfo<'a>,
+    token_prog_acct: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    let (_, place_state_pda_bump) = PlaceState::pda(canvas_id);
+    let place_state_acct_pda_seeds = &[
+        PlaceState::PREFIX.as_bytes(),
+        &canvas_id.to_le_bytes(),
+        &[place_state_pda_bump],
+    ];
+
+    let mint_tokens_ix = spl_token::instruction::mint_to(
+        token_prog_acct.key,
+        place_token_mint_acct.key,
+        place_token_dest_ata_acct.key,
+        place_state_pda_acct.key,
+        &[place_state_pda_acct.key],
+        amount,
+    )?;
+
+    invoke_signed(
+        &mint_tokens_ix,
+        &[
+            (*token_prog_acct).clone(),
+            (*place_token_mint_acct).clone(),
+            (*place_token_dest_ata_acct).clone(),
+            (*place_state_pda_acct).clone(),
+        ],
+        &[place_state_acct_pda_seeds],
+    )?;
+
+    state.record_emission(amount, Clock::get()?.slot);
+    state.serialize(&mut *place_state_pda_acct.data.borrow_mut())?;
+
+    Ok(())
+}
+
+fn process_init_mint(
+    program_id: &Pubkey,
+    acct_args: InitMintAccountArgs,
+    data_args: InitMintDataArgs,
+) -> ProgramResult {
+    let InitMintAccountArgs {
+        owner_acct,
+        place_state_pda_acct,
+        place_token_mint_pda_acct,
+        place_token_mint_mpl_pda_acct,
+        mpl_metadata_prog_acct,
+        token_prog_acct,
+        system_prog_acct,
+        rent_sysvar_acct,
+    } = acct_args;
+
+    let InitMintDataArgs { canvas_id } = data_args;
+
+    assert_signer(owner_acct)?;
+    assert_system_prog(system_prog_acct)?;
+    assert_token_prog(token_prog_acct)?;
+    assert_mpl_metadata_prog(mpl_metadata_prog_acct)?;
+
+    let (place_state_pda, place_state_pda_bump) = PlaceState::pda(canvas_id);
+    if place_state_pda != *place_state_pda_acct.key {
+        return Err(PlaceError::InvalidAccountArgument.into());
+    }
+
+    let (place_token_mpl_meta_pda, _) = PlaceState::token_mint_mpl_metadata_pda(canvas_id);
+    if place_token_mpl_meta_pda != *place_token_mint_mpl_pda_acct.key {
+        return Err(PlaceError::InvalidPlaceTokenMPLMetadataPDA.into());
+    }
+
+    let place_state = PlaceState::from_account_info(place_state_pda_acct)?;
+
+    assert_role(&place_state, PlaceRole::SuperAdmin, owner_acct)?;
+
+    let (place_token_mint_pda, place_token_mint_pda_bump) = PlaceState::token_mint_pda(canvas_id);
+    if place_token_mint_pda != *place_token_mint_pda_acct.key {
+        return Err(PlaceError::InvalidPlaceTokenMintPDA.into());
+    }
+
+    if !place_token_mint_pda_acct.data_is_empty() {
+        return Err(PlaceError::PlaceTokenMintAlreadyInitialized.into());
+    }
+
+    let place_token_mint_seeds = &[
+        PlaceState::PREFIX.as_bytes(),
+        &canvas_id.to_le_bytes(),
+        PlaceState::TOKEN_MINT_PREFIX.as_bytes(),
+        &[place_token_mint_pda_bump],
+    ];
+
+    msg!("TAP: Allocating Token Mint Account");
+
+    create_or_allocate_account_raw(
+        *token_prog_acct.key,
+        place_token_mint_pda_acct,
+        system_prog_acct,
+        owner_acct,
+        Mint::LEN,
+        place_token_mint_seeds,
+    )?;
+
+    msg!("TAP: Init token mint");
+
+    let init_mint_ix = initialize_mint(
+        &spl_token::id(),
+        &place_token_mint_pda,
+        &place_state_pda,
+        None,
+        0,
+    )?;
+
+    invoke_signed(
+        &init_mint_ix,
+        &[
+            (*token_prog_acct).clone(),
+            (*place_state_pda_acct).clone(),
+            (*place_token_mint_pda_acct).clone(),
+            (*rent_sysvar_acct).clone(),
+        ],
+        &[place_token_mint_seeds],
+    )?;
+
+    msg!("TAP: alloc token mint MPL metadata account");
+
+    let create_metadata_ix = create_metadata_accounts_v2(
+        mpl_token_metadata::id(),
+        place_token_mpl_meta_pda,
+        place_token_mint_pda.clone(),
+        place_state_pda.clone(),
+        owner_acct.key.clone(),
+        place_state_pda.clone(),
+        String::from("Tapestry Coin"),
+        String::from("TPSTRY"),
+        String::from(""),
+        None,
+        0,
+        true,
+        false,
+        None,
+        None,
+    );
+
+    let place_token_mint_pda_seeds = &[
+        PlaceState::PREFIX.as_bytes(),
+        &canvas_id.to_le_bytes(),
+        PlaceState::TOKEN_MINT_PREFIX.as_bytes(),
+        &[place_token_mint_pda_bump],
+    ];
+
+    let place_state_acct_pda_seeds = &[
+        PlaceState::PREFIX.as_bytes(),
+        &canvas_id.to_le_bytes(),
+        &[place_state_pda_bump],
+    ];
+
+    invoke_signed(
+        &create_metadata_ix,
+        &[
+            (*mpl_metadata_prog_acct).clone(),
+            (*place_token_mint_pda_acct).clone(),
+            (*place_token_mint_mpl_pda_acct).clone(),
+            (*place_state_pda_acct).clone(),
+            (*owner_acct).clone(),
+            (*rent_sysvar_acct).clone(),
+            (*system_prog_acct).clone(),
+        ],
+        &[place_token_mint_pda_seeds, place_state_acct_pda_seeds],
+    )?;
+
+    Ok(())
+}
+fn process_initialize_place(
+    program_id: &Pubkey,
+    acct_args: InitializePlaceAccountArgs,
+    data_args: InitializePlaceDataArgs,
+) -> ProgramResult {
+    let InitializePlaceDataArgs {
+        canvas_id,
+        owner,
+        paintbrush_price,
+        paintbrush_cooldown,
+        bomb_price,
+        width_px,
+        height_px,
+        patch_size_px,
+        pixel_format,
+        shield_price,
+        shield_duration,
+        price_curve,
+        retire_refund_bps,
+        upgrade_schedule,
+        place_token_pricing,
+        bomb_cooldown,
+        shield_cooldown,
+        paintbrush_rewards,
+        bomb_rewards,
+        shield_rewards,
+        emission_schedule,
+        purchase_phases,
+        paused,
+    } = data_args;
+
+    let InitializePlaceAccountArgs {
+        payer_acct,
+        upgrade_authority_acct,
+        place_state_pda_acct,
+        program_data_acct,
+        system_prog_acct,
+    } = acct_args;
+
+    assert_signer(payer_acct)?;
+    assert_system_prog(system_prog_acct)?;
+
+    // only the upgrade authority can create places, so nobody can front-run a fresh deploy
+    assert_upgrade_authority(program_id, program_data_acct, upgrade_authority_acct)?;
+
+    let (place_state_pda, place_state_pda_bump) = PlaceState::pda(canvas_id);
+    if place_state_pda != *place_state_pda_acct.key {
+        return Err(PlaceError::IncorrectPlaceStatePDA.into());
+    }
+
+    if !place_state_pda_acct.data_is_empty() {
+        return Err(PlaceError::PlaceStateAlreadyInitialized.into());
+    }
+
+    create_or_allocate_account_raw(
+        *program_id,
+        place_state_pda_acct,
+        system_prog_acct,
+        payer_acct,
+        PlaceState::LEN,
+        &[
+            PlaceState::PREFIX.as_bytes(),
+            &canvas_id.to_le_bytes(),
+            &[place_state_pda_bump],
+        ],
+    )?;
+
+    let mut state: PlaceState = try_from_slice_unchecked(&place_state_pda_acct.data.borrow_mut())?;
+    state.acct_type = PlaceAccountType::PlaceState;
+    state.version = CURRENT_ACCOUNT_VERSION;
+    state.canvas_id = canvas_id;
+    state.owner = owner;
+    state.paused = paused.unwrap_or(crate::state::DEFAULT_PAUSED);
+    if !state.paused.is_valid() {
+        return Err(PlaceError::InvalidPauseFlags.into());
+    }
+    if state.is_frozen() {
+        state.frozen_at_slot = Clock::get()?.slot;
+    }
+    state.paintbrush_price = paintbrush_price.unwrap_or(crate::state::DEFAULT_PAINTBRUSH_PRICE);
+    state.paintbrush_cooldown =
+        paintbrush_cooldown.unwrap_or(crate::state::DEFAULT_PAINTBRUSH_COOLDOWN);
+    state.bomb_price = bomb_price.unwrap_or(crate::state::DEFAULT_BOMB_PRICE);
+    state.width_px = width_px.unwrap_or(crate::state::DEFAULT_PLACE_WIDTH_PX);
+    state.height_px = height_px.unwrap_or(crate::state::DEFAULT_PLACE_HEIGHT_PX);
+    state.patch_size_px = patch_size_px.unwrap_or(crate::state::DEFAULT_PATCH_SIZE_PX);
+    state.pixel_format = pixel_format.unwrap_or(crate::state::DEFAULT_PIXEL_FORMAT);
+    state.shield_price = shield_price.unwrap_or(crate::state::DEFAULT_SHIELD_PRICE);
+    state.shield_duration = shield_duration.unwrap_or(crate::state::DEFAULT_SHIELD_DURATION);
+    state.price_curve = price_curve.unwrap_or_default();
+    state.retire_refund_bps = retire_refund_bps.unwrap_or(crate::state::DEFAULT_RETIRE_REFUND_BPS);
+    state.upgrade_schedule = upgrade_schedule.unwrap_or_default();
+    state.place_token_pricing = place_token_pricing.unwrap_or_default();
+    state.bomb_cooldown = bomb_cooldown.unwrap_or(crate::state::DEFAULT_BOMB_COOLDOWN);
+    state.shield_cooldown = shield_cooldown.unwrap_or(crate::state::DEFAULT_SHIELD_COOLDOWN);
+    state.paintbrush_rewards =
+        paintbrush_rewards.unwrap_or(crate::state::DEFAULT_PAINTBRUSH_REWARDS);
+    state.bomb_rewards = bomb_rewards.unwrap_or(crate::state::DEFAULT_BOMB_REWARDS);
+    state.shield_rewards = shield_rewards.unwrap_or(crate::state::DEFAULT_SHIELD_REWARDS);
+    state.emission_schedule = emission_schedule.unwrap_or_default();
+    state.purchase_phases = purchase_phases.unwrap_or_default();
+    state.emission_window = 0;
+    state.emitted_in_window = 0;
+    state.paintbrush_sales = GameplayTokenSales::default();
+    state.bomb_sales = GameplayTokenSales::default();
+    state.shield_sales = GameplayTokenSales::default();
+
+    if !PlaceState::dimensions_are_valid(state.width_px, state.height_px, state.patch_size_px) {
+        return Err(PlaceError::InvalidPlaceDimensions.into());
+    }
+    if state.retire_refund_bps > 10_000 {
+        return Err(PlaceError::InvalidRetireRefund.into());
+    }
+
+    state.serialize(&mut *place_state_pda_acct.data.borrow_mut())?;
+
+    Ok(())
+}
+
+fn process_update_place_state(
+    _program_id: &Pubkey,
+    acct_args: UpdatePlaceStateAccountArgs,
+    data_args: UpdatePlaceStateDataArgs,
+) -> ProgramResult {
+    let UpdatePlaceStateDataArgs {
+        canvas_id,
+        paintbrush_price,
+        paintbrush_cooldown,
+        bomb_price,
+        shield_price,
+        shield_duration,
+        price_curve,
+        retire_refund_bps,
+        upgrade_schedule,
+        place_token_pricing,
+        bomb_cooldown,
+        shield_cooldown,
+        paintbrush_rewards,
+        bomb_rewards,
+        shield_rewards,
+        emission_schedule,
+        purchase_phases,
+        paused,
+    } = data_args;
+
+    let UpdatePlaceStateAccountArgs {
+        current_owner_acct,
+        place_state_pda_acct,
+    } = acct_args;
+
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    if place_state_pda != *place_state_pda_acct.key {
+        return Err(PlaceError::IncorrectPlaceStatePDA.into());
+    }
+
+    assert_signer(&current_owner_acct)?;
+
+    if place_state_pda_acct.data_is_empty() {
+        return Err(PlaceError::PlaceStateNotInitialized.into());
+    }
+
+    let mut state = PlaceState::from_account_info(place_state_pda_acct)?;
+
+    // each group of fields needs its own role, the owner holds all of them
+    let updates_freeze = paused.is_some();
+    if updates_freeze {
+        assert_role(&state, PlaceRole::FreezeAdmin, current_owner_acct)?;
+    }
+    let updates_pricing = paintbrush_price.is_some()
+        || paintbrush_cooldown.is_some()
+        || bomb_price.is_some()
+        || shield_price.is_some()
+        || shield_duration.is_some()
+        || price_curve.is_some()
+        || retire_refund_bps.is_some()
+        || upgrade_schedule.is_some()
+        || place_token_pricing.is_some()
+        || bomb_cooldown.is_some()
+        || shield_cooldown.is_some()
+        || paintbrush_rewards.is_some()
+        || bomb_rewards.is_some()
+        || shield_rewards.is_some()
+        || emission_schedule.is_some()
+        || purchase_phases.is_some();
+    if updates_pricing {
+        assert_role(&state, PlaceRole::PricingAdmin, current_owner_acct)?;
+    }
+    // updates without any role gated field, empty ones included, are left to the owner
+    if !updates_freeze && !updates_pricing {
+        assert_role(&state, PlaceRole::SuperAdmin, current_owner_acct)?;
+    }
+
+    if let Some(paintbrush_price) = paintbrush_price {
+        state.paintbrush_price = paintbrush_price;
+    }
+    if let Some(paintbrush_cooldown) = paintbrush_cooldown {
+        state.paintbrush_cooldown = paintbrush_cooldown;
+    }
+    if let Some(bomb_price) = bomb_price {
+        state.bomb_price = bomb_price;
+    }
+    if let Some(shield_price) = shield_price {
+        state.shield_price = shield_price;
+    }
+    if let Some(shield_duration) = shield_duration {
+        state.shield_duration = shield_duration;
+    }
+    if let Some(price_curve) = price_curve {
+        state.price_curve = price_curve;
+    }
+    if let Some(retire_refund_bps) = retire_refund_bps {
+        if retire_refund_bps > 10_000 {
+            return Err(PlaceError::InvalidRetireRefund.into());
+        }
+        state.retire_refund_bps = retire_refund_bps;
+    }
+    if let Some(upgrade_schedule) = upgrade_schedule {
+        state.upgrade_schedule = upgrade_schedule;
+    }
+    if let Some(place_token_pricing) = place_token_pricing {
+        state.place_token_pricing = place_token_pricing;
+    }
+    if let Some(bomb_cooldown) = bomb_cooldown {
+        state.bomb_cooldown = bomb_cooldown;
+    }
+    if let Some(shield_cooldown) = shield_cooldown {
+        state.shield_cooldown = shield_cooldown;
+    }
+    if let Some(paintbrush_rewards) = paintbrush_rewards {
+        state.paintbrush_rewards = paintbrush_rewards;
+    }
+    if let Some(bomb_rewards) = bomb_rewards {
+        state.bomb_rewards = bomb_rewards;
+    }
+    if let Some(shield_rewards) = shield_rewards {
+        state.shield_rewards = shield_rewards;
+    }
+    if let Some(emission_schedule) = emission_schedule {
+        state.emission_schedule = emission_schedule;
+    }
+    if let Some(purchase_phases) = purchase_phases {
+        state.purchase_phases = purchase_phases;
+    }
+    if let Some(paused) = paused {
+        if !paused.is_valid() {
+            return Err(PlaceError::InvalidPauseFlags.into());
+        }
+        let is_frozen = paused.contains(PauseFlags::FROZEN);
+        // the last season's art has to be gone before anyone can paint the new one
+        if !is_frozen && state.patches_to_reset > 0 {
+            return Err(PlaceError::SeasonResetIncomplete.into());
+        }
+        if is_frozen && !state.is_frozen() {
+            state.frozen_at_slot = Clock::get()?.slot;
+        }
+        if paused != state.paused {
+            msg!(
+                "TAP: paused operations changed from {:#07b} to {:#07b}",
+                state.paused.0,
+                paused.0
+            );
+        }
+        state.paused = paused;
+    }
+
+    state.serialize(&mut *place_state_pda_acct.data.borrow_mut())?;
+    Ok(())
+}
+
+fn process_init_patch(
+    program_id: &Pubkey,
+    acct_args: InitPatchAccountArgs,
+    data_args: &InitPatchDataArgs,
+) -> ProgramResult {
+    let InitPatchAccountArgs {
+        payer_acct,
+        patch_pda_acct,
+        system_acct,
+        place_state_pda_acct,
+        patch_attribution_pda_acct,
+    } = acct_args;
+
+    let InitPatchDataArgs {
+        canvas_id,
+        x_patch,
+        y_patch,
+    } = data_args;
+
+    // TODO(will): check owner, validate inputs more
+
+    if *system_acct.key != solana_program::system_program::id() {
+        return Err(PlaceError::InvalidAccountArgument.into());
+    }
+
+    let state = assert_not_frozen(place_state_pda_acct, *canvas_id)?;
+    if state.paused.contains(PauseFlags::PATCH_CREATION) {
+        return Err(PlaceError::PatchCreationPaused.into());
+    }
+
+    if !state.is_valid_patch(*x_patch, *y_patch) {
+        return Err(InvalidPatchCoordinates.into());
+    }
+
+    let (patch_pda_key, patch_pda_bump) =
+        find_address_for_patch(*canvas_id, *x_patch, *y_patch, program_id);
+
+    if patch_pda_key != *patch_pda_acct.key {
+        return Err(IncorrectPatchPDA.into());
+    }
+
+    if !patch_pda_acct.data_is_empty() {
+        return Err(PatchAccountAlreadyInitialized.into());
+    }
+
+    let (patch_attribution_pda, _) = PatchAttribution::pda(*canvas_id, *x_patch, *y_patch);
+
+    if patch_attribution_pda != *patch_attribution_pda_acct.key {
+        return Err(PlaceError::IncorrectPatchAttributionPDA.into());
+    }
+
+    // Allocate space for the tapestry state in the PDA account, subtracting the necessary rent from the payer
+    create_or_allocate_account_raw(
+        *program_id,
+        patch_pda_acct,
+        system_acct,
+        payer_acct,
+        Patch::len_for_size(state.patch_size_px, state.pixel_format),
+        &[
+            PATCH_PDA_PREFIX.as_bytes(),
+            &canvas_id.to_le_bytes(),
+            &x_patch.to_le_bytes(),
+            &y_patch.to_le_bytes(),
+            &[patch_pda_bump],
+        ],
+    )?;
+
+    let mut patch: Patch = try_from_slice_unchecked(&patch_pda_acct.try_borrow_data()?)?;
+
+    patch.acct_type = PlaceAccountType::Patch;
+    patch.version = CURRENT_ACCOUNT_VERSION;
+    patch.canvas_id = *canvas_id;
+    patch.format = state.pixel_format;
+    patch.x = *x_patch;
+    patch.y = *y_patch;
+    patch.shield = PatchShield::default();
+    patch.pixels = vec![0; state.pixel_bytes_per_patch()];
+
+    patch.serialize(&mut *patch_pda_acct.data.borrow_mut())?;
+
+    create_patch_attribution(
+        program_id,
+        &state,
+        &patch,
+        patch_attribution_pda_acct,
+        system_acct,
+        payer_acct,
+    )?;
+
+    return Ok(());
+}
+
+/// Allocates the companion account which tracks who painted each pixel of a patch,
+/// the pda of the attribution account must already have been checked
+fn create_patch_attribution<'a>(
+    program_id: &Pubkey,
+    state: &PlaceState,
+    patch: &Patch,
+    patch_attribution_pda_acct: &AccountInfo<'a>,
+    system_acct: &AccountInfo<'a>,
+    payer_acct: &AccountInfo<'a>,
+) -> ProgramResult {
+    let (_, patch_attribution_pda_bump) = PatchAttribution::pda(patch.canvas_id, patch.x, patch.y);
+
+    create_or_allocate_account_raw(
+        *program_id,
+        patch_attribution_pda_acct,
+        system_acct,
+        payer_acct,
+        PatchAttribution::len_for_size(state.patch_size_px),
+        &[
+            Patch::PREFIX.as_bytes(),
+            &patch.canvas_id.to_le_bytes(),
+            &patch.x.to_le_bytes(),
+            &patch.y.to_le_bytes(),
+            PatchAttribution::PREFIX.as_bytes(),
+            &[patch_attribution_pda_bump],
+        ],
+    )?;
+
+    let patch_attribution = PatchAttribution {
+        acct_type: PlaceAccountType::PatchAttribution,
+        canvas_id: patch.canvas_id,
+        x: patch.x,
+        y: patch.y,
+        pixels: vec![PixelAttribution::default(); state.pixels_per_patch()],
+    };
+
+    patch_attribution.serialize(&mut *patch_attribution_pda_acct.data.borrow_mut())?;
+    Ok(())
+}
+
+fn process_init_patch_attribution(
+    program_id: &Pubkey,
+    acct_args: InitPatchAttributionAccountArgs,
+    data_args: &InitPatchAttributionDataArgs,
+) -> ProgramResult {
+    let InitPatchAttributionAccountArgs {
+        payer_acct,
+        place_state_pda_acct,
+        patch_pda_acct,
+        patch_attribution_pda_acct,
+        system_acct,
+    } = acct_args;
+
+    let InitPatchAttributionDataArgs {
+        canvas_id,
+        x_patch,
+        y_patch,
+    } = data_args;
+
+    assert_signer(payer_acct)?;
+    assert_system_prog(system_acct)?;
+
+    let (place_state_pda, _) = PlaceState::pda(*canvas_id);
+    if place_state_pda != *place_state_pda_acct.key {
+        return Err(PlaceError::IncorrectPlaceStatePDA.into());
+    }
+    let state = PlaceState::from_account_info(place_state_pda_acct)?;
+
+    let (patch_pda, _) = Patch::pda(*canvas_id, *x_patch, *y_patch);
+    if patch_pda != *patch_pda_acct.key || patch_pda_acct.data_is_empty() {
+        return Err(IncorrectPatchPDA.into());
+    }
+    let patch = Patch::from_account_info(patch_pda_acct)?;
+
+    let (patch_attribution_pda, _) = PatchAttribution::pda(*canvas_id, *x_patch, *y_patch);
+    if patch_attribution_pda != *patch_attribution_pda_acct.key {
+        return Err(PlaceError::IncorrectPatchAttributionPDA.into());
+    }
+
+    if !patch_attribution_pda_acct.data_is_empty() {
+        return Err(PlaceError::PatchAttributionAlreadyInitialized.into());
+    }
+
+    create_patch_attribution(
+        program_id,
+        &state,
+        &patch,
+        patch_attribution_pda_acct,
+        system_acct,
+        payer_acct,
+    )
+}
+
+fn process_purchase_gameplay_token(
+    program_id: &Pubkey,
+    acct_args: PurchaseGameplayTokenAccountArgs,
+    data_args: PurchaseGameplayTokenDataArgs,
+) -> ProgramResult {
+    let PurchaseGameplayTokenAccountArgs {
+        payer_acct,
+        place_state_pda_acct,
+        gameplay_meta_pda_acct,
+        gameplay_token_mint_pda_acct,
+        gameplay_token_ata_acct,
+        gameplay_token_mpl_meta_acct,
+        mpl_metadata_prog_acct,
+        token_prog_acct,
+        ata_prog_acct,
+        system_prog_acct,
+        rent_sysvar_acct,
+        treasury_pda_acct,
+        allowlist_receipt_pda_acct,
+        place_token_mint_acct,
+        payer_place_token_ata_acct,
+        treasury_place_token_ata_acct,
+    } = acct_args;
+
+    let PurchaseGameplayTokenDataArgs {
+        canvas_id,
+        token_type,
+        random_seed,
+        desired_price,
+        payment,
+        allowlist_proof,
+    } = data_args;
+
+    assert_signer(payer_acct)?;
+
+    let (gameplay_meta_pda, gameplay_meta_pda_bump) = GameplayTokenMeta::pda(random_seed);
+    if gameplay_meta_pda != *gameplay_meta_pda_acct.key {
+        return Err(PlaceError::IncorrectGameplayTokenMetaPDA.into());
+    }
+
+    let (place_state_pda, place_state_pda_bump) = PlaceState::pda(canvas_id);
+    if place_state_pda != *place_state_pda_acct.key {
+        return Err(PlaceError::IncorrectPlaceStatePDA.into());
+    }
+
+    let (gameplay_token_mint_pda, gameplay_token_mint_pda_bump) =
+        GameplayTokenMeta::token_mint_pda(random_seed);
+    if gameplay_token_mint_pda != *gameplay_token_mint_pda_acct.key {
+        return Err(PlaceError::InvalidGameplayTokenMintPDA.into());
+    }
+
+    let (treasury_pda, _) = PlaceState::treasury_pda(canvas_id);
+    if treasury_pda != *treasury_pda_acct.key {
+        return Err(PlaceError::IncorrectTreasuryPDA.into());
+    }
+
+    let (gameplay_token_mpl_meta_pda, _) = GameplayTokenMeta::token_metadata_pda(random_seed);
+    if *gameplay_token_mpl_meta_acct.key != gameplay_token_mpl_meta_pda {
+        return Err(PlaceError::InvalidAccountArgument.into());
+    }
+
+    if *system_prog_acct.key != solana_program::system_program::id() {
+        return Err(PlaceError::InvalidAccountArgument.into());
+    }
+
+    if *token_prog_acct.key != spl_token::id() {
+        return Err(PlaceError::InvalidAccountArgument.into());
+    }
+
+    if *ata_prog_acct.key != spl_associated_token_account::id() {
+        return Err(PlaceError::InvalidAccountArgument.into());
+    }
+
+    if *mpl_metadata_prog_acct.key != mpl_token_metadata::id() {
+        return Err(PlaceError::InvalidAccountArgument.into());
+    }
+
+    if *rent_sysvar_acct.key != sysvar::rent::id() {
+        return Err(PlaceError::InvalidAccountArgument.into());
+    }
+
+    if !gameplay_meta_pda_acct.data_is_empty() {
+        return Err(PlaceError::GameplayTokenAlreadyPurchased.into());
+    }
+
+    let mut state = PlaceState::from_account_info(place_state_pda_acct)?;
+    if state.is_frozen() {
+        return Err(PlaceError::PlaceFrozen.into());
+    }
+    if state.paused.contains(PauseFlags::PURCHASES) {
+        return Err(PlaceError::PurchasesPaused.into());
+    }
+
+    let clock = Clock::get()?;
+
+    match state.purchase_phases.phase(clock.slot) {
+        PurchasePhase::Closed => return Err(PlaceError::PurchasesNotOpen.into()),
+        PurchasePhase::Allowlist => record_allowlist_purchase(
+            program_id,
+            &state,
+            payer_acct,
+            allowlist_receipt_pda_acct,
+            system_prog_acct,
+            allowlist_proof.as_ref(),
+        )?,
+        PurchasePhase::Public => {}
+    }
+
+    let price = match payment {
+        PaymentMethod::Lamports => state.current_price(token_type, clock.slot),
+        PaymentMethod::PlaceTokens => state.place_token_pricing.price(token_type),
+    };
+
+    if payment == PaymentMethod::PlaceTokens && price == 0 {
+        return Err(PlaceError::PlaceTokenPaymentDisabled.into());
+    }
+
+    if price != desired_price {
+        return Err(PlaceError::DesiredPriceDifferentFromCurrentPrice.into());
+    }
+
+    // place token purchases count as demand for the lamport price too
+    state.record_sale(token_type, clock.slot);
+    state.serialize(&mut *place_state_pda_acct.data.borrow_mut())?;
+
+    // -- pay for the token
+    msg!("TAP: Paying for token");
+
+    let purchase_price = match payment {
+        PaymentMethod::Lamports => {
+            invoke(
+                &system_instruction::transfer(&payer_acct.key, &treasury_pda, price),
+                &[
+                    (*payer_acct).clone(),
+                    (*treasury_pda_acct).clone(),
+                    (*system_prog_acct).clone(),
+                ],
+            )?;
+
+            price
+        }
+        PaymentMethod::PlaceTokens => {
+            let (place_token_mint_acct, payer_place_token_ata_acct, treasury_place_token_ata_acct) =
+                match (
+                    place_token_mint_acct,
+                    payer_place_token_ata_acct,
+                    treasury_place_token_ata_acct,
+                ) {
+                    (Some(mint), Some(payer_ata), Some(treasury_ata)) => {
+                        (mint, payer_ata, treasury_ata)
+                    }
+                    _ => return Err(PlaceError::InvalidAccountArgument.into()),
+                };
+
+            let (place_token_mint_pda, _) = PlaceState::token_mint_pda(canvas_id);
+            if *place_token_mint_acct.key != place_token_mint_pda {
+                return Err(PlaceError::InvalidPlaceTokenMintPDA.into());
+            }
+            assert_owned_by_token_prog(place_token_mint_acct)?;
+
+            // the token program checks the payer owns the place token account and holds enough
+            if state.place_token_pricing.burn_payments {
+                msg!("TAP: Burning {} place tokens", price);
+                invoke(
+                    &spl_token::instruction::burn(
+                        token_prog_acct.key,
+                        payer_place_token_ata_acct.key,
+                        place_token_mint_acct.key,
+                        payer_acct.key,
+                        &[],
+                        price,
+                    )?,
+                    &[
+                        (*token_prog_acct).clone(),
+                        (*payer_place_token_ata_acct).clone(),
+                        (*place_token_mint_acct).clone(),
+                        (*payer_acct).clone(),
+                    ],
+                )?;
+            } else {
+                if *treasury_place_token_ata_acct.key
+                    != PlaceState::treasury_place_token_ata(canvas_id)
+                {
+                    return Err(PlaceError::IncorrectTreasuryPlaceTokenATA.into());
+                }
+
+                if treasury_place_token_ata_acct.data_is_empty() {
+                    msg!("TAP: Creating treasury place token account");
+                    invoke(
+                        &create_associated_token_account(
+                            payer_acct.key,
+                            &treasury_pda,
+                            &place_token_mint_pda,
+                        ),
+                        &[
+                            (*payer_acct).clone(),
+                            (*treasury_place_token_ata_acct).clone(),
+                            (*treasury_pda_acct).clone(),
+                            (*place_token_mint_acct).clone(),
+                            (*system_prog_acct).clone(),
+                            (*token_prog_acct).clone(),
+                            (*rent_sysvar_acct).clone(),
+                            (*ata_prog_acct).clone(),
+                        ],
+                    )?;
+                }
+
+                msg!("TAP: Sending {} place tokens to the treasury", price);
+                invoke(
+                    &spl_token::instruction::transfer(
+                        token_prog_acct.key,
+                        payer_place_token_ata_acct.key,
+                        treasury_place_token_ata_acct.key,
+                        payer_acct.key,
+                        &[],
+                        price,
+                    )?,
+                    &[
+                        (*token_prog_acct).clone(),
+                        (*payer_place_token_ata_acct).clone(),
+                        (*treasury_place_token_ata_acct).clone(),
+                        (*payer_acct).clone(),
+                    ],
+                )?;
+            }
+
+            // nothing in lamports to refund when the token is retired
+            0
+        }
+    };
+
+    // -- Allocate space for the gameplay token account and initialize its state
+    msg!("TAP: Allocating gameplay token");
+
+    let gameplay_meta_pda_seeds = &[
+        GameplayTokenMeta::PREFIX.as_bytes(),
+        &random_seed.to_le_bytes(),
+        &[gameplay_meta_pda_bump],
+    ];
+
+    create_or_allocate_account_raw(
+        *program_id,
+        gameplay_meta_pda_acct,
+        system_prog_acct,
+        payer_acct,
+        GameplayTokenMeta::LEN,
+        gameplay_meta_pda_seeds,
+    )?;
+
+    let gameplay_token_meta = GameplayTokenMeta {
+        acct_type: PlaceAccountType::GameplayTokenMeta,
+        version: CURRENT_ACCOUNT_VERSION,
+        canvas_id,
+        gameplay_type: token_type,
+        created_at_slot: clock.slot,
+        random_seed: random_seed,
+        token_mint_pda: gameplay_token_mint_pda,
+        update_allowed_slot: clock.slot,
+        cooldown_duration: state.cooldown(token_type),
+        place_tokens_owed: state.rewards(token_type).purchase_bonus,
+        purchase_price,
+        upgrade_level: 0,
+        is_banned: false,
+        season: state.season,
+        reserved: [0; GAMEPLAY_TOKEN_META_RESERVED_LEN],
+    };
+
+    // -- Allocate space for the token mint and initialize it
+    msg!("TAP: Allocating token mint");
+
+    let gameplay_token_mint_pda_seeds = &[
+        GameplayTokenMeta::PREFIX.as_bytes(),
+        &random_seed.to_le_bytes(),
+        GameplayTokenMeta::MINT_PREFIX.as_bytes(),
+        &[gameplay_token_mint_pda_bump],
+    ];
+
+    let place_state_acct_pda_seeds = &[
+        PlaceState::PREFIX.as_bytes(),
+        &canvas_id.to_le_bytes(),
+        &[place_state_pda_bump],
+    ];
+
+    create_or_allocate_account_raw(
+        *token_prog_acct.key,
+        gameplay_token_mint_pda_acct,
+        system_prog_acct,
+        payer_acct,
+        Mint::LEN,
+        gameplay_token_mint_pda_seeds,
+    )?;
+
+    let init_mint_ix = initialize_mint(
+        &spl_token::id(),
+        &gameplay_token_mint_pda,
+        &place_state_pda,
+        None,
+        0,
+    )?;
+
+    invoke_signed(
+        &init_mint_ix,
+        &[
+            (*token_prog_acct).clone(),
+            (*place_state_pda_acct).clone(),
+            (*gameplay_token_mint_pda_acct).clone(),
+            (*rent_sysvar_acct).clone(),
+        ],
+        &[place_state_acct_pda_seeds],
+    )?;
+
+    // -- Create associated token account
+    msg!("TAP: Creating ATA");
+
+    // TODO(will): figure out what hapens if this account already exists
+    let create_ata_ix =
+        create_associated_token_account(payer_acct.key, payer_acct.key, &gameplay_token_mint_pda);
+
+    invoke_signed(
+        &create_ata_ix,
+        &[
+            (*gameplay_token_ata_acct).clone(),
+            (*payer_acct).clone(),
+            (*gameplay_token_mint_pda_acct).clone(),
+            (*ata_prog_acct).clone(),
+            (*rent_sysvar_acct).clone(),
+            (*system_prog_acct).clone(),
+        ],
+        &[],
+    )?;
+
+    // -- Mint NFT into ATA
+    msg!("TAP: Minting NFT into ATA");
+
+    let mint_token_ix = spl_token::instruction::mint_to(
+        token_prog_acct.key,
+        gameplay_token_mint_pda_acct.key,
+        gameplay_token_ata_acct.key,
+        place_state_pda_acct.key,
+        &[place_state_pda_acct.key],
+        1,
+    )?;
+
+    invoke_signed(
+        &mint_token_ix,
+        &[
+            (*token_prog_acct).clone(),
+            (*gameplay_token_mint_pda_acct).clone(),
+            (*gameplay_token_ata_acct).clone(),
+            (*place_state_pda_acct).clone(),
+            // Do I need to add this? we will find out (*payer_acct).clone(),
+        ],
+        &[place_state_acct_pda_seeds],
+    )?;
+
+    // -- Create the metaplex token metadata
+    msg!("TAP: Creating mpl metadata");
+
+    let token_name = match token_type {
+        GameplayTokenType::Bomb => String::from("Tapestry Bomb"),
+        GameplayTokenType::PaintBrush => String::from("Tapestry Paintbrush"),
+        GameplayTokenType::Shield => String::from("Tapestry Shield"),
+    };
+
+    let token_uri = match token_type {
+        GameplayTokenType::Bomb => String::from("http://localhost:8080/bomb.json"),
+        GameplayTokenType::PaintBrush => String::from("http://localhost:8080/paintbrush.json"),
+        GameplayTokenType::Shield => String::from("http://localhost:8080/shield.json"),
+    };
+
+    let create_mpl_meta_ix = create_metadata_accounts_v2(
+        mpl_token_metadata::id(),
+        gameplay_token_mpl_meta_pda,
+        gameplay_token_mint_pda.clone(),
+        place_state_pda.clone(),
+        payer_acct.key.clone(),
+        place_state_pda.clone(),
+        token_name,
+        String::from("Tapestry"),
+        token_uri,
+        None,
+        0,
+        true,
+        false,
+        Some(Collection {
+            verified: false, // have to do this via a separate instruction
+            key: place_state_pda.clone(),
+        }),
+        None, // this is "uses", can this be leveraged for bombs?
+    );
+
+    invoke_signed(
+        &create_mpl_meta_ix,
+        &[
+            (*mpl_metadata_prog_acct).clone(),
+            (*gameplay_token_mint_pda_acct).clone(),
+            (*payer_acct).clone(),
+            (*place_state_pda_acct).clone(),
+            (*rent_sysvar_acct).clone(),
+            (*system_prog_acct).clone(),
+            (*gameplay_token_mpl_meta_acct).clone(),
+        ],
+        &[gameplay_token_mint_pda_seeds, place_state_acct_pda_seeds],
+    )?;
+
+    // TODO(will): maybe verify the colleciton using verify_collection ix?
+
+    // TODO(will): maybe remove authority to hard limit supply to one
+
+    // IMPORTANT - save the game state, for some reason if i do this earlier the transfer
+    // of SOL to this account fails
+
+    gameplay_token_meta.serialize(&mut *gameplay_meta_pda_acct.data.borrow_mut())?;
+
+    Ok(())
+}
+
+// Thought process on question of storing the "cooldown" time in the global state account
+// versus storing on each gameplay token.
+// Store on token meta accounts:
+//   PRO: fewer account args to set pixel
+//   CON: duplicate a u64 across all tokens (more space)
+//   PRO: save compute on set pixel by not parsing an additional account
+//   PRO or CON: gameplay tokens are locked in to their cooldown rate
+// Store on global state account:
+//   CON: less bytes for each token purchase
+//   PRO or CON: could slow down or speed up the game by changing one variable in global state
+//
+// Based on this, seems better to store in the token account than use global state
+
+/// Checks the payer is on the allowlist and under their cap, and counts the purchase
+/// in their receipt, creating it on their first purchase
+fn record_allowlist_purchase<'a>(
+    program_id: &Pubkey,
+    state: &PlaceState,
+    payer_acct: &AccountInfo<'a>,
+    allowlist_receipt_pda_acct: &AccountInfo<'a>,
+    system_prog_acct: &AccountInfo<'a>,
+    allowlist_proof: Option<&AllowlistProof>,
+) -> ProgramResult {
+    let allowlist_proof = allowlist_proof.ok_or(PlaceError::AllowlistProofRequired)?;
+
+    let allowlist_root = state.purchase_phases.allowlist_root;
+    let leaf = allowlist_leaf(payer_acct.key, allowlist_proof.max_purchases);
+    if !verify_allowlist_proof(&allowlist_root, leaf, &allowlist_proof.proof) {
+        return Err(PlaceError::InvalidAllowlistProof.into());
+    }
+
+    let (allowlist_receipt_pda, allowlist_receipt_pda_bump) =
+        AllowlistReceipt::pda(state.canvas_id, payer_acct.key);
+    if allowlist_receipt_pda != *allowlist_receipt_pda_acct.key {
+        return Err(PlaceError::IncorrectAllowlistReceiptPDA.into());
+    }
+
+    if allowlist_receipt_pda_acct.data_is_empty() {
+        create_or_allocate_account_raw(
+            *program_id,
+            allowlist_receipt_pda_acct,
+            system_prog_acct,
+            payer_acct,
+            AllowlistReceipt::LEN,
+            &[
+                AllowlistReceipt::PREFIX.as_bytes(),
+                &state.canvas_id.to_le_bytes(),
+                payer_acct.key.as_ref(),
+                &[allowlist_receipt_pda_bump],
+            ],
+        )?;
+    }
+
+    let mut receipt = AllowlistReceipt::from_account_info(allowlist_receipt_pda_acct)?;
+    if receipt.acct_type == PlaceAccountType::Uninitialized
+        || receipt.allowlist_root != allowlist_root
+    {
+        receipt.acct_type = PlaceAccountType::AllowlistReceipt;
+        receipt.canvas_id = state.canvas_id;
+        receipt.wallet = *payer_acct.key;
+        receipt.allowlist_root = allowlist_root;
+        receipt.purchases = 0;
+    }
+
+    if receipt.purchases >= allowlist_proof.max_purchases {
+        return Err(PlaceError::AllowlistPurchaseCapReached.into());
+    }
+
+    receipt.purchases += 1;
+    receipt.serialize(&mut *allowlist_receipt_pda_acct.data.borrow_mut())?;
+
+    msg!(
+        "TAP: allowlist purchase {} of {} for {}",
+        receipt.purchases,
+        allowlist_proof.max_purchases,
+        payer_acct.key
+    );
+
+    Ok(())
+}
+
+fn process_set_pixel(
+    program_id: &Pubkey,
+    acct_args: SetPixelAccountArgs,
+    data_args: &SetPixelDataArgs,
+) -> ProgramResult {
+    let SetPixelAccountArgs {
+        payer_acct,
+        patch_pda_acct,
+        gameplay_token_meta_acct,
+        payer_gameplay_token_acct,
+        system_acct,
+        place_state_pda_acct,
+        patch_attribution_pda_acct,
+    } = acct_args;
+
+    let SetPixelDataArgs {
+        canvas_id,
+        x,
+        y,
+        x_offset,
+        y_offset,
+        pixel,
+    } = data_args;
+
+    // everything is based on the signer owning a token account with a balance of 1,
+    // and with a mint that matches the mint in the gameplay token meta
+    // so payer had better be signer.
+    assert_signer(payer_acct)?;
+
+    // TODO(will): check token acct is owned by token program or whatever
+    // also maybe check ownership on other accounts
+
+    if *system_acct.key != solana_program::system_program::id() {
+        return Err(PlaceError::InvalidAccountArgument.into());
+    }
+
+    let state = assert_not_frozen(place_state_pda_acct, *canvas_id)?;
+    if state.paused.contains(PauseFlags::PAINTING) {
+        return Err(PlaceError::PaintingPaused.into());
+    }
+
+    if !state.is_valid_offset(*x_offset, *y_offset) {
+        return Err(PlaceError::InvalidPixelOffset.into());
+    }
+
+    if !state.pixel_format.is_valid_pixel(*pixel) {
+        return Err(PlaceError::InvalidPixelValue.into());
+    }
+
+    // Parse and validate account arguments
+
+    if patch_pda_acct.data_is_empty() {
+        return Err(PlaceError::PatchNotInitialized.into());
+    }
+    let mut patch: Patch = Patch::from_account_info(patch_pda_acct)?;
+    let (patch_pda, _) = patch.pda_for_instance();
+    if patch_pda != *patch_pda_acct.key {
+        return Err(PlaceError::IncorrectPatchPDA.into());
+    }
+
+    if *canvas_id != patch.canvas_id || *x != patch.x || *y != patch.y {
+        return Err(PlaceError::IncorrectPatchPDA.into());
+    }
+
+    let mut patch_attribution = load_patch_attribution(patch_attribution_pda_acct, &patch)?;
+
+    let mut gameplay_token = load_gameplay_token(
+        &state,
+        payer_acct,
+        gameplay_token_meta_acct,
+        payer_gameplay_token_acct,
+    )?;
+
+    // check the gameplay token meta to make sure it is "ready"
+
+    let clock = Clock::get()?;
+    let current_slot = clock.slot;
+    if gameplay_token.update_allowed_slot > current_slot {
+        return Err(PlaceError::GameplayTokenNotReady.into());
+    }
+
+    if patch.shield.protects(*x_offset, *y_offset, current_slot) {
+        return Err(PlaceError::PixelShielded.into());
+    }
+
+    msg!(
+        "TAP: current_slot: {}, allowed_after: {}",
+        current_slot,
+        gameplay_token.update_allowed_slot
+    );
+
+    // update the cooldown for the token
+    gameplay_token.update_allowed_slot = current_slot + gameplay_token.cooldown_duration;
+    gameplay_token.place_tokens_owed = gameplay_token
+        .place_tokens_owed
+        .saturating_add(state.rewards(gameplay_token.gameplay_type).use_reward);
+    gameplay_token.serialize(&mut *gameplay_token_meta_acct.data.borrow_mut())?;
+
+    // Change the pixel
+
+    let y_offset_usize = *y_offset as usize;
+    let x_offset_usize = *x_offset as usize;
+
+    let idx = (y_offset_usize * state.patch_size()) + x_offset_usize;
+    patch.set_pixel_at(idx, *pixel);
+    patch.serialize(&mut *patch_pda_acct.data.borrow_mut())?;
+
+    patch_attribution.record(
+        *x_offset,
+        *y_offset,
+        gameplay_token.random_seed,
+        current_slot,
+    );
+    patch_attribution.serialize(&mut *patch_attribution_pda_acct.data.borrow_mut())?;
+
+    Ok(())
+}
+
+fn process_set_pixels(
+    program_id: &Pubkey,
+    acct_args: SetPixelsAccountArgs,
+    data_args: &SetPixelsDataArgs,
+) -> ProgramResult {
+    let SetPixelsAccountArgs {
+        payer_acct,
+        patch_pda_acct,
+        gameplay_token_meta_acct,
+        payer_gameplay_token_acct,
+        system_acct,
+        place_state_pda_acct,
+        patch_attribution_pda_acct,
+    } = acct_args;
+
+    let SetPixelsDataArgs {
+        canvas_id,
+        x,
+        y,
+        pixels,
+    } = data_args;
+
+    assert_signer(payer_acct)?;
+    assert_system_prog(system_acct)?;
+    let state = assert_not_frozen(place_state_pda_acct, *canvas_id)?;
+    if state.paused.contains(PauseFlags::PAINTING) {
+        return Err(PlaceError::PaintingPaused.into());
+    }
+
+    if pixels.is_empty() {
+        return Err(PlaceError::EmptyPixelList.into());
+    }
+
+    // validate every write up front so a bad entry can't leave a half written patch
+    for write in pixels.iter() {
+        if !state.is_valid_offset(write.x_offset, write.y_offset) {
+            return Err(PlaceError::InvalidPixelOffset.into());
+        }
+        if !state.pixel_format.is_valid_pixel(write.pixel) {
+            return Err(PlaceError::InvalidPixelValue.into());
+        }
+    }
+
+    if patch_pda_acct.data_is_empty() {
+        return Err(PlaceError::PatchNotInitialized.into());
+    }
+    let mut patch: Patch = Patch::from_account_info(patch_pda_acct)?;
+    let (patch_pda, _) = patch.pda_for_instance();
+    if patch_pda != *patch_pda_acct.key {
+        return Err(PlaceError::IncorrectPatchPDA.into());
+    }
+
+    if *canvas_id != patch.canvas_id || *x != patch.x || *y != patch.y {
+        return Err(PlaceError::IncorrectPatchPDA.into());
+    }
+
+    let mut patch_attribution = load_patch_attribution(patch_attribution_pda_acct, &patch)?;
+
+    let mut gameplay_token = load_gameplay_token(
+        &state,
+        payer_acct,
+        gameplay_token_meta_acct,
+        payer_gameplay_token_acct,
+    )?;
+
+    let clock = Clock::get()?;
+    let current_slot = clock.slot;
+    if gameplay_token.update_allowed_slot > current_slot {
+        return Err(PlaceError::GameplayTokenNotReady.into());
+    }
+
+    for write in pixels.iter() {
+        if patch
+            .shield
+            .protects(write.x_offset, write.y_offset, current_slot)
+        {
+            return Err(PlaceError::PixelShielded.into());
+        }
+    }
+
+    // each pixel costs the same cooldown and earns the same reward as a single SetPixel
+    let pixel_count = pixels.len() as u64;
+    let cooldown = gameplay_token
+        .cooldown_duration
+        .checked_mul(pixel_count)
+        .ok_or(PlaceError::InvalidInstruction)?;
+
+    msg!(
+        "TAP: setting {} pixels, current_slot: {}, cooldown: {}",
+        pixel_count,
+        current_slot,
+        cooldown
+    );
+
+    let reward = state
+        .rewards(gameplay_token.gameplay_type)
+        .use_reward
+        .saturating_mul(pixel_count as u32);
+
+    gameplay_token.update_allowed_slot = current_slot + cooldown;
+    gameplay_token.place_tokens_owed = gameplay_token.place_tokens_owed.saturating_add(reward);
+    gameplay_token.serialize(&mut *gameplay_token_meta_acct.data.borrow_mut())?;
+
+    for write in pixels.iter() {
+        let idx = (write.y_offset as usize * state.patch_size()) + write.x_offset as usize;
+        patch.set_pixel_at(idx, write.pixel);
+        patch_attribution.record(
+            write.x_offset,
+            write.y_offset,
+            gameplay_token.random_seed,
+            current_slot,
+        );
+    }
+    patch.serialize(&mut *patch_pda_acct.data.borrow_mut())?;
+    patch_attribution.serialize(&mut *patch_attribution_pda_acct.data.borrow_mut())?;
+
+    Ok(())
+}
+
+/// Loads the place state and fails if the place has been frozen
+fn assert_not_frozen(
+    place_state_pda_acct: &AccountInfo,
+    canvas_id: u64,
+) -> Result<PlaceState, ProgramError> {
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    if place_state_pda != *place_state_pda_acct.key {
+        return Err(PlaceError::IncorrectPlaceStatePDA.into());
+    }
+
+    let state = PlaceState::from_account_info(place_state_pda_acct)?;
+    if state.is_frozen() {
+        return Err(PlaceError::PlaceFrozen.into());
+    }
+
+    Ok(state)
+}
+
+/// Loads the attribution account for a patch, checking it belongs to that patch
+fn load_patch_attribution(
+    patch_attribution_pda_acct: &AccountInfo,
+    patch: &Patch,
+) -> Result<PatchAttribution, ProgramError> {
+    if patch_attribution_pda_acct.data_is_empty() {
+        return Err(PlaceError::PatchAttributionNotInitialized.into());
+    }
+
+    let patch_attribution = PatchAttribution::from_account_info(patch_attribution_pda_acct)?;
+    if patch_attribution.acct_type != PlaceAccountType::PatchAttribution
+        || patch_attribution.canvas_id != patch.canvas_id
+        || patch_attribution.x != patch.x
+        || patch_attribution.y != patch.y
+    {
+        return Err(PlaceError::IncorrectPatchAttributionPDA.into());
+    }
+
+    let (patch_attribution_pda, _) = patch_attribution.pda_for_instance();
+    if patch_attribution_pda != *patch_attribution_pda_acct.key {
+        return Err(PlaceError::IncorrectPatchAttributionPDA.into());
+    }
+
+    Ok(patch_attribution)
+}
+
+/// Loads the gameplay token meta account and checks that the payer holds the NFT
+/// associated with it in the given token account, and that it belongs to the canvas.
+fn load_gameplay_token(
+    state: &PlaceState,
+    payer_acct: &AccountInfo,
+    gameplay_token_meta_acct: &AccountInfo,
+    payer_gameplay_token_acct: &AccountInfo,
+) -> Result<GameplayTokenMeta, ProgramError> {
+    let mut gameplay_token: GameplayTokenMeta =
+        GameplayTokenMeta::from_account_info(gameplay_token_meta_acct)?;
+    let (gameplay_token_pda, _) = gameplay_token.pda_for_instance();
+    if gameplay_token_pda != *gameplay_token_meta_acct.key {
+        return Err(PlaceError::IncorrectGameplayTokenMetaPDA.into());
+    }
+
+    if gameplay_token.canvas_id != state.canvas_id {
+        return Err(PlaceError::GameplayTokenCanvasMismatch.into());
+    }
+
+    if gameplay_token.is_banned {
+        return Err(PlaceError::GameplayTokenBanned.into());
+    }
+
+    // the token carries over into the new season, what it was owed last season doesn't
+    gameplay_token.start_season(state.season);
+
+    // check the token account looks good
+    let gameplay_ata = TokenAccount::unpack_from_slice(&payer_gameplay_token_acct.data.borrow())?;
+    if gameplay_ata.owner != *payer_acct.key {
+        return Err(PlaceError::InvalidGameplayTokenAccountOwner.into());
+    }
+    if gameplay_ata.amount != 1 {
+        return Err(PlaceError::InvalidGameplayTokenAccountBalance.into());
+    }
+    if gameplay_ata.mint != gameplay_token.token_mint_pda {
+        return Err(PlaceError::InvalidGameplayTokenAccountMint.into());
+    }
+
+    Ok(gameplay_token)
+}
+
+fn process_detonate_bomb(
+    program_id: &Pubkey,
+    acct_args: DetonateBombAccountArgs,
+    data_args: &DetonateBombDataArgs,
+) -> ProgramResult {
+    let DetonateBombAccountArgs {
+        payer_acct,
+        gameplay_token_meta_acct,
+        payer_gameplay_token_acct,
+        place_state_pda_acct,
+        patch_pda_accts,
+    } = acct_args;
+
+    let DetonateBombDataArgs {
+        canvas_id,
+        x,
+        y,
+        x_offset,
+        y_offset,
+        pixel,
+    } = data_args;
+
+    assert_signer(payer_acct)?;
+    let state = assert_not_frozen(place_state_pda_acct, *canvas_id)?;
+    if state.paused.contains(PauseFlags::PAINTING) {
+        return Err(PlaceError::PaintingPaused.into());
+    }
+
+    let mut gameplay_token = load_gameplay_token(
+        &state,
+        payer_acct,
+        gameplay_token_meta_acct,
+        payer_gameplay_token_acct,
+    )?;
+
+    if gameplay_token.gameplay_type != GameplayTokenType::Bomb {
+        return Err(PlaceError::InvalidGameplayTokenType.into());
+    }
+
+    if !state.is_valid_offset(*x_offset, *y_offset) {
+        return Err(PlaceError::InvalidPixelOffset.into());
+    }
+
+    if !state.pixel_format.is_valid_pixel(*pixel) {
+        return Err(PlaceError::InvalidPixelValue.into());
+    }
+
+    if !state.is_valid_patch(*x, *y) {
+        return Err(InvalidPatchCoordinates.into());
+    }
+
+    let clock = Clock::get()?;
+    let current_slot = clock.slot;
+    if gameplay_token.update_allowed_slot > current_slot {
+        return Err(PlaceError::GameplayTokenNotReady.into());
+    }
+
+    // every patch touched by the blast must be passed in, so validate and parse all
+    // of them before any pixels are written
+
+    let blast_patches = state.bomb_blast_patches(*x, *y, *x_offset, *y_offset);
+    if blast_patches.len() * 2 != patch_pda_accts.len() {
+        return Err(PlaceError::InvalidBlastPatches.into());
+    }
+
+    let (x_min, y_min, x_max, y_max) = state.bomb_blast_bounds(*x, *y, *x_offset, *y_offset);
+    let patch_size = state.patch_size();
+
+    let mut patches: Vec<(Patch, PatchAttribution)> = Vec::with_capacity(blast_patches.len());
+    for (patch_accts, (patch_x, patch_y)) in patch_pda_accts.chunks(2).zip(blast_patches) {
+        let patch_pda_acct = &patch_accts[0];
+        let patch_attribution_pda_acct = &patch_accts[1];
+
+        if patch_pda_acct.data_is_empty() {
+            return Err(PlaceError::PatchNotInitialized.into());
+        }
+        let patch = Patch::from_account_info(patch_pda_acct)?;
+        if patch.canvas_id != *canvas_id || patch.x != patch_x || patch.y != patch_y {
+            return Err(PlaceError::InvalidBlastPatches.into());
+        }
+
+        let (patch_pda, _) = patch.pda_for_instance();
+        if patch_pda != *patch_pda_acct.key {
+            return Err(PlaceError::IncorrectPatchPDA.into());
+        }
+
+        let patch_attribution = load_patch_attribution(patch_attribution_pda_acct, &patch)?;
+
+        // the whole blast is rejected if any of it lands on a shielded area
+        if patch.shield.is_active(current_slot) {
+            let patch_x_tl = patch.x as usize * patch_size;
+            let patch_y_tl = patch.y as usize * patch_size;
+            if x_min <= patch_x_tl + patch.shield.x_max as usize
+                && x_max >= patch_x_tl + patch.shield.x_min as usize
+                && y_min <= patch_y_tl + patch.shield.y_max as usize
+                && y_max >= patch_y_tl + patch.shield.y_min as usize
+            {
+                return Err(PlaceError::PixelShielded.into());
+            }
+        }
+
+        patches.push((patch, patch_attribution));
+    }
+
+    msg!(
+        "TAP: detonating bomb at patch ({}, {}) offset ({}, {})",
+        x,
+        y,
+        x_offset,
+        y_offset
+    );
+
+    for (patch_accts, (mut patch, mut patch_attribution)) in patch_pda_accts.chunks(2).zip(patches)
+    {
+        let patch_x_tl = patch.x as usize * patch_size;
+        let patch_y_tl = patch.y as usize * patch_size;
+
+        for place_y in y_min.max(patch_y_tl)..=y_max.min(patch_y_tl + patch_size - 1) {
+            for place_x in x_min.max(patch_x_tl)..=x_max.min(patch_x_tl + patch_size - 1) {
+                let patch_x_offset = place_x - patch_x_tl;
+                let patch_y_offset = place_y - patch_y_tl;
+                let idx = (patch_y_offset * patch_size) + patch_x_offset;
+                patch.set_pixel_at(idx, *pixel);
+                patch_attribution.record(
+                    patch_x_offset as u8,
+                    patch_y_offset as u8,
+                    gameplay_token.random_seed,
+                    current_slot,
+                );
+            }
+        }
+
+        patch.serialize(&mut *patch_accts[0].data.borrow_mut())?;
+        patch_attribution.serialize(&mut *patch_accts[1].data.borrow_mut())?;
+    }
+
+    // bombs are reusable, but go on cooldown like any other gameplay token
+    gameplay_token.update_allowed_slot = current_slot + gameplay_token.cooldown_duration;
+    gameplay_token.place_tokens_owed = gameplay_token
+        .place_tokens_owed
+        .saturating_add(state.rewards(GameplayTokenType::Bomb).use_reward);
+    gameplay_token.serialize(&mut *gameplay_token_meta_acct.data.borrow_mut())?;
+
+    Ok(())
+}
+
+fn process_seal_canvas(
+    program_id: &Pubkey,
+    acct_args: SealCanvasAccountArgs,
+    data_args: SealCanvasDataArgs,
+) -> ProgramResult {
+    let SealCanvasAccountArgs {
+        owner_acct,
+        place_state_pda_acct,
+        canvas_snapshot_pda_acct,
+        system_prog_acct,
+        patch_pda_accts,
+    } = acct_args;
+
+    let SealCanvasDataArgs { canvas_id } = data_args;
+
+    assert_signer(owner_acct)?;
+    assert_system_prog(system_prog_acct)?;
+
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    if place_state_pda != *place_state_pda_acct.key {
+        return Err(PlaceError::IncorrectPlaceStatePDA.into());
+    }
+
+    let state = PlaceState::from_account_info(place_state_pda_acct)?;
+    assert_role(&state, PlaceRole::FreezeAdmin, owner_acct)?;
+
+    if !state.is_frozen() {
+        return Err(PlaceError::PlaceNotFrozen.into());
+    }
+
+    let (canvas_snapshot_pda, canvas_snapshot_pda_bump) = CanvasSnapshot::pda(canvas_id);
+    if canvas_snapshot_pda != *canvas_snapshot_pda_acct.key {
+        return Err(PlaceError::IncorrectCanvasSnapshotPDA.into());
+    }
+
+    if canvas_snapshot_pda_acct.data_is_empty() {
+        msg!("TAP: Allocating canvas snapshot");
+        create_or_allocate_account_raw(
+            *program_id,
+            canvas_snapshot_pda_acct,
+            system_prog_acct,
+            owner_acct,
+            CanvasSnapshot::LEN,
+            &[
+                PlaceState::PREFIX.as_bytes(),
+                &canvas_id.to_le_bytes(),
+                CanvasSnapshot::PREFIX.as_bytes(),
+                &[canvas_snapshot_pda_bump],
+            ],
+        )?;
+    }
+
+    let mut snapshot = CanvasSnapshot::from_account_info(canvas_snapshot_pda_acct)?;
+
+    // a freshly allocated snapshot, or the place was frozen again since the last
+    // snapshot, so start the chain over
+    if snapshot.acct_type != PlaceAccountType::CanvasSnapshot
+        || snapshot.frozen_at_slot != state.frozen_at_slot
+    {
+        snapshot = CanvasSnapshot {
+            acct_type: PlaceAccountType::CanvasSnapshot,
+            frozen_at_slot: state.frozen_at_slot,
+            next_patch: 0,
+            hash: [0; 32],
+            is_sealed: false,
+        };
+    }
+
+    if snapshot.is_sealed {
+        return Err(PlaceError::CanvasAlreadySealed.into());
+    }
+
+    let blank_pixels = vec![0u8; state.pixel_bytes_per_patch()];
+    for patch_pda_acct in patch_pda_accts.iter() {
+        if snapshot.next_patch >= state.patch_count() {
+            return Err(PlaceError::InvalidSnapshotPatches.into());
+        }
+
+        let (patch_x, patch_y) = state.patch_coords(snapshot.next_patch);
+        let (patch_pda, _) = Patch::pda(canvas_id, patch_x, patch_y);
+        if patch_pda != *patch_pda_acct.key {
+            return Err(PlaceError::InvalidSnapshotPatches.into());
+        }
+
+        snapshot.hash = if patch_pda_acct.data_is_empty() {
+            CanvasSnapshot::chain_patch_hash(&snapshot.hash, patch_x, patch_y, &blank_pixels)
+        } else {
+            let patch = Patch::from_account_info(patch_pda_acct)?;
+            CanvasSnapshot::chain_patch_hash(&snapshot.hash, patch_x, patch_y, &patch.pixels)
+        };
+
+        snapshot.next_patch += 1;
+    }
+
+    if snapshot.next_patch == state.patch_count() {
+        snapshot.is_sealed = true;
+        msg!(
+            "TAP: canvas sealed, frozen_at_slot: {}, hash: {}",
+            snapshot.frozen_at_slot,
+            Hash::new_from_array(snapshot.hash)
+        );
+    }
+
+    snapshot.serialize(&mut *canvas_snapshot_pda_acct.data.borrow_mut())?;
+
+    Ok(())
+}
+
+fn process_mint_region_nft(
+    program_id: &Pubkey,
+    acct_args: MintRegionNftAccountArgs,
+    data_args: MintRegionNftDataArgs,
+) -> ProgramResult {
+    let MintRegionNftAccountArgs {
+        owner_acct,
+        place_state_pda_acct,
+        region_meta_pda_acct,
+        region_token_mint_pda_acct,
+        region_token_ata_acct,
+        region_token_mpl_meta_acct,
+        mpl_metadata_prog_acct,
+        token_prog_acct,
+        ata_prog_acct,
+        system_prog_acct,
+        rent_sysvar_acct,
+        patch_pda_accts,
+    } = acct_args;
+
+    let MintRegionNftDataArgs {
+        canvas_id,
+        x_min,
+        y_min,
+        x_max,
+        y_max,
+    } = data_args;
+
+    assert_signer(owner_acct)?;
+    assert_system_prog(system_prog_acct)?;
+    assert_token_prog(token_prog_acct)?;
+    assert_mpl_metadata_prog(mpl_metadata_prog_acct)?;
+
+    if *ata_prog_acct.key != spl_associated_token_account::id() {
+        return Err(PlaceError::InvalidAccountArgument.into());
+    }
+
+    if *rent_sysvar_acct.key != sysvar::rent::id() {
+        return Err(PlaceError::InvalidAccountArgument.into());
+    }
+
+    let (place_state_pda, place_state_pda_bump) = PlaceState::pda(canvas_id);
+    if place_state_pda != *place_state_pda_acct.key {
+        return Err(PlaceError::IncorrectPlaceStatePDA.into());
+    }
+
+    let state = PlaceState::from_account_info(place_state_pda_acct)?;
+    assert_role(&state, PlaceRole::SuperAdmin, owner_acct)?;
+
+    if !state.is_frozen() {
+        return Err(PlaceError::PlaceNotFrozen.into());
+    }
+
+    if !state.is_valid_region(x_min, y_min, x_max, y_max) {
+        return Err(PlaceError::InvalidRegionBounds.into());
+    }
+
+    let frozen_at_slot = state.frozen_at_slot;
+    let (region_meta_pda, region_meta_pda_bump) =
+        RegionNftMeta::pda(canvas_id, frozen_at_slot, x_min, y_min, x_max, y_max);
+    if region_meta_pda != *region_meta_pda_acct.key {
+        return Err(PlaceError::IncorrectRegionNftPDA.into());
+    }
+
+    let (region_token_mint_pda, region_token_mint_pda_bump) =
+        RegionNftMeta::token_mint_pda(canvas_id, frozen_at_slot, x_min, y_min, x_max, y_max);
+    if region_token_mint_pda != *region_token_mint_pda_acct.key {
+        return Err(PlaceError::IncorrectRegionNftPDA.into());
+    }
+
+    let (region_token_mpl_meta_pda, _) =
+        RegionNftMeta::token_metadata_pda(canvas_id, frozen_at_slot, x_min, y_min, x_max, y_max);
+    if region_token_mpl_meta_pda != *region_token_mpl_meta_acct.key {
+        return Err(PlaceError::IncorrectRegionNftPDA.into());
+    }
+
+    if !region_meta_pda_acct.data_is_empty() {
+        return Err(PlaceError::RegionAlreadyMinted.into());
+    }
+
+    // -- hash the pixels of every patch in the region, the patches can't change while frozen
+
+    let region_patches = state.region_patches(x_min, y_min, x_max, y_max);
+    if region_patches.len() != patch_pda_accts.len() {
+        return Err(PlaceError::InvalidRegionPatches.into());
+    }
+
+    let blank_pixels = vec![0u8; state.pixel_bytes_per_patch()];
+    let mut pixel_hash = [0u8; 32];
+    for (patch_pda_acct, (patch_x, patch_y)) in patch_pda_accts.iter().zip(region_patches) {
+        let (patch_pda, _) = Patch::pda(canvas_id, patch_x, patch_y);
+        if patch_pda != *patch_pda_acct.key {
+            return Err(PlaceError::InvalidRegionPatches.into());
+        }
+
+        pixel_hash = if patch_pda_acct.data_is_empty() {
+            CanvasSnapshot::chain_patch_hash(&pixel_hash, patch_x, patch_y, &blank_pixels)
+        } else {
+            let patch = Patch::from_account_info(patch_pda_acct)?;
+            CanvasSnapshot::chain_patch_hash(&pixel_hash, patch_x, patch_y, &patch.pixels)
+        };
+    }
+
+    let region_meta = RegionNftMeta {
+        acct_type: PlaceAccountType::RegionNftMeta,
+        canvas_id,
+        frozen_at_slot,
+        x_min,
+        y_min,
+        x_max,
+        y_max,
+        pixel_hash,
+        token_mint_pda: region_token_mint_pda,
+    };
+
+    msg!(
+        "TAP: minting region ({}, {}) to ({}, {}), pixel hash: {}",
+        x_min,
+        y_min,
+        x_max,
+        y_max,
+        Hash::new_from_array(pixel_hash)
+    );
+
+    // -- Allocate the region meta account
+
+    let canvas_id_bytes = canvas_id.to_le_bytes();
+    let frozen_at_slot_bytes = frozen_at_slot.to_le_bytes();
+    let bounds = [x_min, y_min, x_max, y_max];
+
+    create_or_allocate_account_raw(
+        *program_id,
+        region_meta_pda_acct,
+        system_prog_acct,
+        owner_acct,
+        RegionNftMeta::LEN,
+        &[
+            RegionNftMeta::PREFIX.as_bytes(),
+            &canvas_id_bytes,
+            &frozen_at_slot_bytes,
+            &bounds,
+            &[region_meta_pda_bump],
+        ],
+    )?;
+
+    region_meta.serialize(&mut *region_meta_pda_acct.data.borrow_mut())?;
+
+    // -- Allocate space for the token mint and initialize it
+    msg!("TAP: Allocating region token mint");
+
+    let region_token_mint_pda_seeds = &[
+        RegionNftMeta::PREFIX.as_bytes(),
+        &canvas_id_bytes,
+        &frozen_at_slot_bytes,
+        &bounds,
+        RegionNftMeta::MINT_PREFIX.as_bytes(),
+        &[region_token_mint_pda_bump],
+    ];
+
+    let place_state_acct_pda_seeds = &[
+        PlaceState::PREFIX.as_bytes(),
+        &canvas_id_bytes,
+        &[place_state_pda_bump],
+    ];
+
+    create_or_allocate_account_raw(
+        *token_prog_acct.key,
+        region_token_mint_pda_acct,
+        system_prog_acct,
+        owner_acct,
+        Mint::LEN,
+        region_token_mint_pda_seeds,
+    )?;
+
+    let init_mint_ix = initialize_mint(
         &spl_token::id(),
-        &place_token_mint_pda,
+        &region_token_mint_pda,
         &place_state_pda,
         None,
         0,
     )?;
 
-    invoke_signed(
-        &init_mint_ix,
-        &[
-            (*token_prog_acct).clone(),
-            (*place_state_pda_acct).clone(),
-            (*place_token_mint_pda_acct).clone(),
-            (*rent_sysvar_acct).clone(),
-        ],
-        &[place_token_mint_seeds],
+    invoke_signed(
+        &init_mint_ix,
+        &[
+            (*token_prog_acct).clone(),
+            (*place_state_pda_acct).clone(),
+            (*region_token_mint_pda_acct).clone(),
+            (*rent_sysvar_acct).clone(),
+        ],
+        &[place_state_acct_pda_seeds],
+    )?;
+
+    // -- Create the owner's associated token account and mint the NFT into it
+    msg!("TAP: Minting region NFT into ATA");
+
+    let create_ata_ix =
+        create_associated_token_account(owner_acct.key, owner_acct.key, &region_token_mint_pda);
+
+    invoke(
+        &create_ata_ix,
+        &[
+            (*region_token_ata_acct).clone(),
+            (*owner_acct).clone(),
+            (*region_token_mint_pda_acct).clone(),
+            (*ata_prog_acct).clone(),
+            (*rent_sysvar_acct).clone(),
+            (*system_prog_acct).clone(),
+        ],
+    )?;
+
+    let mint_token_ix = spl_token::instruction::mint_to(
+        token_prog_acct.key,
+        region_token_mint_pda_acct.key,
+        region_token_ata_acct.key,
+        place_state_pda_acct.key,
+        &[place_state_pda_acct.key],
+        1,
+    )?;
+
+    invoke_signed(
+        &mint_token_ix,
+        &[
+            (*token_prog_acct).clone(),
+            (*region_token_mint_pda_acct).clone(),
+            (*region_token_ata_acct).clone(),
+            (*place_state_pda_acct).clone(),
+        ],
+        &[place_state_acct_pda_seeds],
+    )?;
+
+    // -- Create the metaplex token metadata
+    msg!("TAP: Creating region mpl metadata");
+
+    let create_mpl_meta_ix = create_metadata_accounts_v2(
+        mpl_token_metadata::id(),
+        region_token_mpl_meta_pda,
+        region_token_mint_pda,
+        place_state_pda,
+        *owner_acct.key,
+        place_state_pda,
+        region_meta.token_name(),
+        String::from("Tapestry"),
+        region_meta.token_uri(),
+        None,
+        0,
+        true,
+        false,
+        Some(Collection {
+            verified: false,
+            key: place_state_pda,
+        }),
+        None,
+    );
+
+    invoke_signed(
+        &create_mpl_meta_ix,
+        &[
+            (*mpl_metadata_prog_acct).clone(),
+            (*region_token_mint_pda_acct).clone(),
+            (*owner_acct).clone(),
+            (*place_state_pda_acct).clone(),
+            (*rent_sysvar_acct).clone(),
+            (*system_prog_acct).clone(),
+            (*region_token_mpl_meta_acct).clone(),
+        ],
+        &[region_token_mint_pda_seeds, place_state_acct_pda_seeds],
+    )?;
+
+    // -- Remove the mint authority so the region can never have more than one token
+    msg!("TAP: Removing region mint authority");
+
+    let remove_authority_ix = set_authority(
+        token_prog_acct.key,
+        region_token_mint_pda_acct.key,
+        None,
+        AuthorityType::MintTokens,
+        place_state_pda_acct.key,
+        &[place_state_pda_acct.key],
+    )?;
+
+    invoke_signed(
+        &remove_authority_ix,
+        &[
+            (*token_prog_acct).clone(),
+            (*region_token_mint_pda_acct).clone(),
+            (*place_state_pda_acct).clone(),
+        ],
+        &[place_state_acct_pda_seeds],
+    )?;
+
+    Ok(())
+}
+
+fn process_activate_shield(
+    program_id: &Pubkey,
+    acct_args: ActivateShieldAccountArgs,
+    data_args: &ActivateShieldDataArgs,
+) -> ProgramResult {
+    let ActivateShieldAccountArgs {
+        payer_acct,
+        patch_pda_acct,
+        gameplay_token_meta_acct,
+        payer_gameplay_token_acct,
+        place_state_pda_acct,
+    } = acct_args;
+
+    let ActivateShieldDataArgs {
+        canvas_id,
+        x,
+        y,
+        x_offset,
+        y_offset,
+    } = data_args;
+
+    assert_signer(payer_acct)?;
+    let state = assert_not_frozen(place_state_pda_acct, *canvas_id)?;
+    if state.paused.contains(PauseFlags::PAINTING) {
+        return Err(PlaceError::PaintingPaused.into());
+    }
+
+    let mut gameplay_token = load_gameplay_token(
+        &state,
+        payer_acct,
+        gameplay_token_meta_acct,
+        payer_gameplay_token_acct,
     )?;
 
-    msg!("TAP: alloc token mint MPL metadata account");
+    if gameplay_token.gameplay_type != GameplayTokenType::Shield {
+        return Err(PlaceError::InvalidGameplayTokenType.into());
+    }
 
-    let create_metadata_ix = create_metadata_accounts_v2(
-        mpl_token_metadata::id(),
-        place_token_mpl_meta_pda,
-        place_token_mint_pda.clone(),
-        place_state_pda.clone(),
-        owner_acct.key.clone(),
-        place_state_pda.clone(),
-        String::from("Tapestry Coin"),
-        String::from("TPSTRY"),
-        String::from(""),
-        None,
-        0,
-        true,
-        false,
-        None,
-        None,
+    if !state.is_valid_offset(*x_offset, *y_offset) {
+        return Err(PlaceError::InvalidPixelOffset.into());
+    }
+
+    if patch_pda_acct.data_is_empty() {
+        return Err(PlaceError::PatchNotInitialized.into());
+    }
+    let mut patch: Patch = Patch::from_account_info(patch_pda_acct)?;
+    let (patch_pda, _) = patch.pda_for_instance();
+    if patch_pda != *patch_pda_acct.key {
+        return Err(PlaceError::IncorrectPatchPDA.into());
+    }
+
+    if *canvas_id != patch.canvas_id || *x != patch.x || *y != patch.y {
+        return Err(PlaceError::IncorrectPatchPDA.into());
+    }
+
+    let clock = Clock::get()?;
+    let current_slot = clock.slot;
+    if gameplay_token.update_allowed_slot > current_slot {
+        return Err(PlaceError::GameplayTokenNotReady.into());
+    }
+
+    // only one shield per patch, otherwise a patch could be covered entirely
+    if patch.shield.is_active(current_slot) {
+        return Err(PlaceError::PatchAlreadyShielded.into());
+    }
+
+    let (x_min, y_min, x_max, y_max) = state.shield_bounds(*x_offset, *y_offset);
+    patch.shield = PatchShield {
+        x_min,
+        y_min,
+        x_max,
+        y_max,
+        expires_at_slot: current_slot + state.shield_duration,
+    };
+
+    msg!(
+        "TAP: shielding patch ({}, {}) offsets ({}, {}) to ({}, {}) until slot {}",
+        x,
+        y,
+        x_min,
+        y_min,
+        x_max,
+        y_max,
+        patch.shield.expires_at_slot
     );
 
-    let place_token_mint_pda_seeds = &[
+    patch.serialize(&mut *patch_pda_acct.data.borrow_mut())?;
+
+    gameplay_token.update_allowed_slot = current_slot + gameplay_token.cooldown_duration;
+    gameplay_token.place_tokens_owed = gameplay_token
+        .place_tokens_owed
+        .saturating_add(state.rewards(GameplayTokenType::Shield).use_reward);
+    gameplay_token.serialize(&mut *gameplay_token_meta_acct.data.borrow_mut())?;
+
+    Ok(())
+}
+
+fn process_withdraw_treasury(
+    _program_id: &Pubkey,
+    acct_args: WithdrawTreasuryAccountArgs,
+    data_args: &WithdrawTreasuryDataArgs,
+) -> ProgramResult {
+    let WithdrawTreasuryAccountArgs {
+        owner_acct,
+        place_state_pda_acct,
+        treasury_pda_acct,
+        destination_acct,
+        system_prog_acct,
+    } = acct_args;
+
+    let WithdrawTreasuryDataArgs { canvas_id, amount } = data_args;
+
+    assert_signer(owner_acct)?;
+    assert_system_prog(system_prog_acct)?;
+
+    let (place_state_pda, _) = PlaceState::pda(*canvas_id);
+    if place_state_pda != *place_state_pda_acct.key {
+        return Err(PlaceError::IncorrectPlaceStatePDA.into());
+    }
+
+    let state = PlaceState::from_account_info(place_state_pda_acct)?;
+    assert_role(&state, PlaceRole::Treasurer, owner_acct)?;
+
+    let (treasury_pda, treasury_pda_bump) = PlaceState::treasury_pda(*canvas_id);
+    if treasury_pda != *treasury_pda_acct.key {
+        return Err(PlaceError::IncorrectTreasuryPDA.into());
+    }
+
+    if *amount > treasury_pda_acct.lamports() {
+        return Err(PlaceError::InsufficientTreasuryFunds.into());
+    }
+
+    msg!("TAP: withdrawing {} lamports from the treasury", amount);
+
+    let treasury_pda_seeds = &[
         PlaceState::PREFIX.as_bytes(),
-        PlaceState::TOKEN_MINT_PREFIX.as_bytes(),
-        &[place_token_mint_pda_bump],
+        &canvas_id.to_le_bytes(),
+        PlaceState::TREASURY_PREFIX.as_bytes(),
+        &[treasury_pda_bump],
     ];
 
-    let place_state_acct_pda_seeds = &[PlaceState::PREFIX.as_bytes(), &[place_state_pda_bump]];
-
     invoke_signed(
-        &create_metadata_ix,
+        &system_instruction::transfer(&treasury_pda, destination_acct.key, *amount),
         &[
-            (*mpl_metadata_prog_acct).clone(),
-            (*place_token_mint_pda_acct).clone(),
-            (*place_token_mint_mpl_pda_acct).clone(),
-            (*place_state_pda_acct).clone(),
-            (*owner_acct).clone(),
-            (*rent_sysvar_acct).clone(),
+            (*treasury_pda_acct).clone(),
+            (*destination_acct).clone(),
             (*system_prog_acct).clone(),
         ],
-        &[place_token_mint_pda_seeds, place_state_acct_pda_seeds],
+        &[treasury_pda_seeds],
     )?;
 
     Ok(())
 }
-fn process_update_place_state(
-    program_id: &Pubkey,
-    acct_args: UpdatePlaceStateAccountArgs,
-    data_args: UpdatePlaceStateDataArgs,
+
+fn process_sweep_gameplay_tokens(
+    _program_id: &Pubkey,
+    acct_args: SweepGameplayTokensAccountArgs,
+    data_args: &SweepGameplayTokensDataArgs,
 ) -> ProgramResult {
-    let UpdatePlaceStateDataArgs {
-        new_owner,
-        is_frozen,
-        paintbrush_price,
-        paintbrush_cooldown,
-        bomb_price,
+    let SweepGameplayTokensAccountArgs {
+        place_state_pda_acct,
+        treasury_pda_acct,
+        gameplay_token_meta_accts,
+    } = acct_args;
+
+    let SweepGameplayTokensDataArgs {
+        canvas_id,
+        random_seeds,
     } = data_args;
 
-    let UpdatePlaceStateAccountArgs {
-        current_owner_acct,
+    let (place_state_pda, _) = PlaceState::pda(*canvas_id);
+    if place_state_pda != *place_state_pda_acct.key {
+        return Err(PlaceError::IncorrectPlaceStatePDA.into());
+    }
+
+    let (treasury_pda, _) = PlaceState::treasury_pda(*canvas_id);
+    if treasury_pda != *treasury_pda_acct.key {
+        return Err(PlaceError::IncorrectTreasuryPDA.into());
+    }
+
+    if random_seeds.len() != gameplay_token_meta_accts.len() {
+        return Err(PlaceError::InvalidAccountArgument.into());
+    }
+
+    let rent = Rent::get()?;
+    let mut total_swept: u64 = 0;
+    for (gameplay_token_meta_acct, random_seed) in
+        gameplay_token_meta_accts.iter().zip(random_seeds)
+    {
+        if *gameplay_token_meta_acct.owner != crate::id() {
+            return Err(PlaceError::InvalidGameplayTokenMetaPDAOwner.into());
+        }
+
+        // only gameplay token metas are created at these pdas, so the layout doesn't matter
+        let (gameplay_token_meta_pda, _) = GameplayTokenMeta::pda(*random_seed);
+        if gameplay_token_meta_pda != *gameplay_token_meta_acct.key {
+            return Err(PlaceError::IncorrectGameplayTokenMetaPDA.into());
+        }
+
+        if gameplay_token_meta_acct.data_is_empty() {
+            return Err(PlaceError::InvalidAccountArgument.into());
+        }
+
+        // metas created before canvas ids existed hold the stranded lamports and don't
+        // record a canvas, current ones have to belong to this canvas
+        if gameplay_token_meta_acct.data_len() == GameplayTokenMeta::LEN {
+            let gameplay_token_meta =
+                GameplayTokenMeta::from_account_info(gameplay_token_meta_acct)?;
+            if gameplay_token_meta.canvas_id != *canvas_id {
+                return Err(PlaceError::GameplayTokenCanvasMismatch.into());
+            }
+        }
+
+        // the account has to stay rent exempt, so only lamports above that are moved
+        let rent_exempt_lamports = rent.minimum_balance(gameplay_token_meta_acct.data_len());
+        let excess = gameplay_token_meta_acct
+            .lamports()
+            .saturating_sub(rent_exempt_lamports);
+        if excess == 0 {
+            continue;
+        }
+
+        **gameplay_token_meta_acct.lamports.borrow_mut() -= excess;
+        **treasury_pda_acct.lamports.borrow_mut() += excess;
+        total_swept += excess;
+    }
+
+    msg!("TAP: swept {} lamports into the treasury", total_swept);
+
+    Ok(())
+}
+
+fn process_retire_gameplay_token(
+    _program_id: &Pubkey,
+    acct_args: RetireGameplayTokenAccountArgs,
+    data_args: &RetireGameplayTokenDataArgs,
+) -> ProgramResult {
+    let RetireGameplayTokenAccountArgs {
+        holder_acct,
+        gameplay_token_pda_acct,
+        gameplay_token_mint_acct,
+        gameplay_token_ata_acct,
+        place_token_mint_acct,
+        place_token_dest_ata_acct,
         place_state_pda_acct,
-        system_acct,
+        treasury_pda_acct,
+        token_prog_acct,
+        system_prog_acct,
+    } = acct_args;
+
+    let canvas_id = data_args.canvas_id;
+
+    assert_signer(holder_acct)?;
+    assert_token_prog(token_prog_acct)?;
+    assert_system_prog(system_prog_acct)?;
+
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    if *place_state_pda_acct.key != place_state_pda {
+        return Err(PlaceError::IncorrectPlaceStatePDA.into());
+    }
+
+    let mut state = assert_not_frozen(place_state_pda_acct, canvas_id)?;
+
+    // retiring pays out the place tokens owed, the same as a claim
+    if state.paused.contains(PauseFlags::CLAIMS) {
+        return Err(PlaceError::ClaimsPaused.into());
+    }
+
+    let (treasury_pda, treasury_pda_bump) = PlaceState::treasury_pda(canvas_id);
+    if treasury_pda != *treasury_pda_acct.key {
+        return Err(PlaceError::IncorrectTreasuryPDA.into());
+    }
+
+    let (place_token_mint_pda, _) = PlaceState::token_mint_pda(canvas_id);
+    if *place_token_mint_acct.key != place_token_mint_pda {
+        return Err(PlaceError::InvalidPlaceTokenMintPDA.into());
+    }
+
+    if *gameplay_token_pda_acct.owner != crate::id() {
+        return Err(PlaceError::InvalidGameplayTokenMetaPDAOwner.into());
+    }
+
+    let mut gameplay_token_meta = GameplayTokenMeta::from_account_info(gameplay_token_pda_acct)?;
+
+    let (gameplay_token_pda, _) = gameplay_token_meta.pda_for_instance();
+    if gameplay_token_pda != *gameplay_token_pda_acct.key {
+        return Err(PlaceError::InvalidGameplayTokenMetaPDA.into());
+    }
+
+    if gameplay_token_meta.canvas_id != canvas_id {
+        return Err(PlaceError::GameplayTokenCanvasMismatch.into());
+    }
+
+    // retiring pays out the place tokens owed, which a banned token can't claim
+    if gameplay_token_meta.is_banned {
+        return Err(PlaceError::GameplayTokenBanned.into());
+    }
+
+    gameplay_token_meta.start_season(state.season);
+
+    if gameplay_token_meta.token_mint_pda != *gameplay_token_mint_acct.key {
+        return Err(PlaceError::InvalidGameplayTokenMintPDA.into());
+    }
+
+    let gameplay_token_ata =
+        TokenAccount::unpack_from_slice(&gameplay_token_ata_acct.data.borrow())?;
+
+    if gameplay_token_ata.amount != 1 {
+        return Err(PlaceError::InvalidGameplayTokenAccountBalance.into());
+    }
+
+    if gameplay_token_ata.owner != *holder_acct.key {
+        return Err(PlaceError::GameplayTokenATADidNotMatchSigner.into());
+    }
+
+    if gameplay_token_ata.mint != gameplay_token_meta.token_mint_pda {
+        return Err(PlaceError::GameplayTokenATAMintDidNotMatch.into());
+    }
+
+    let refund = state.retire_refund(gameplay_token_meta.purchase_price);
+    if refund > treasury_pda_acct.lamports() {
+        return Err(PlaceError::InsufficientTreasuryFunds.into());
+    }
+
+    // -- pay out anything still owed, nothing can be claimed once the meta account is closed
+
+    if gameplay_token_meta.place_tokens_owed > 0 {
+        assert_owned_by_token_prog(place_token_mint_acct)?;
+
+        let place_token_ata =
+            TokenAccount::unpack_from_slice(&place_token_dest_ata_acct.data.borrow())?;
+        if place_token_mint_pda != place_token_ata.mint {
+            return Err(PlaceError::InvalidPlaceTokenDestinationATA.into());
+        }
+
+        // a partial payout would lose the rest along with the meta account
+        let budget = place_token_budget(&state, place_token_mint_acct)?;
+        if (gameplay_token_meta.place_tokens_owed as u64) > budget {
+            return Err(PlaceError::PlaceTokenEmissionExhausted.into());
+        }
+
+        msg!(
+            "TAP: Minting {} owed place tokens into dest ata",
+            gameplay_token_meta.place_tokens_owed
+        );
+        mint_place_tokens(
+            canvas_id,
+            &mut state,
+            place_state_pda_acct,
+            place_token_mint_acct,
+            place_token_dest_ata_acct,
+            token_prog_acct,
+            gameplay_token_meta.place_tokens_owed as u64,
+        )?;
+    }
+
+    // -- burn the NFT and close its token account, the rent goes back to the holder
+
+    msg!("TAP: Burning gameplay token");
+    invoke(
+        &spl_token::instruction::burn(
+            token_prog_acct.key,
+            gameplay_token_ata_acct.key,
+            gameplay_token_mint_acct.key,
+            holder_acct.key,
+            &[],
+            1,
+        )?,
+        &[
+            (*token_prog_acct).clone(),
+            (*gameplay_token_ata_acct).clone(),
+            (*gameplay_token_mint_acct).clone(),
+            (*holder_acct).clone(),
+        ],
+    )?;
+
+    invoke(
+        &spl_token::instruction::close_account(
+            token_prog_acct.key,
+            gameplay_token_ata_acct.key,
+            holder_acct.key,
+            holder_acct.key,
+            &[],
+        )?,
+        &[
+            (*token_prog_acct).clone(),
+            (*gameplay_token_ata_acct).clone(),
+            (*holder_acct).clone(),
+        ],
+    )?;
+
+    // -- refund a share of the purchase price from the treasury
+
+    if refund > 0 {
+        msg!("TAP: Refunding {} lamports from the treasury", refund);
+
+        let treasury_pda_seeds = &[
+            PlaceState::PREFIX.as_bytes(),
+            &canvas_id.to_le_bytes(),
+            PlaceState::TREASURY_PREFIX.as_bytes(),
+            &[treasury_pda_bump],
+        ];
+
+        invoke_signed(
+            &system_instruction::transfer(&treasury_pda, holder_acct.key, refund),
+            &[
+                (*treasury_pda_acct).clone(),
+                (*holder_acct).clone(),
+                (*system_prog_acct).clone(),
+            ],
+            &[treasury_pda_seeds],
+        )?;
+    }
+
+    // -- close the gameplay token meta account
+
+    msg!("TAP: Closing gameplay token meta");
+    let meta_lamports = gameplay_token_pda_acct.lamports();
+    **gameplay_token_pda_acct.lamports.borrow_mut() = 0;
+    **holder_acct.lamports.borrow_mut() = holder_acct
+        .lamports()
+        .checked_add(meta_lamports)
+        .ok_or(ProgramError::InvalidArgument)?;
+    gameplay_token_pda_acct.data.borrow_mut().fill(0);
+
+    Ok(())
+}
+
+fn process_upgrade_gameplay_token(
+    _program_id: &Pubkey,
+    acct_args: UpgradeGameplayTokenAccountArgs,
+    data_args: &UpgradeGameplayTokenDataArgs,
+) -> ProgramResult {
+    let UpgradeGameplayTokenAccountArgs {
+        holder_acct,
+        gameplay_token_meta_acct,
+        gameplay_token_ata_acct,
+        place_token_mint_acct,
+        place_token_ata_acct,
+        place_state_pda_acct,
+        token_prog_acct,
     } = acct_args;
 
-    let (place_state_pda, place_state_pda_bump) = PlaceState::pda();
-    if place_state_pda != *place_state_pda_acct.key {
-        return Err(PlaceError::IncorrectPlaceStatePDA.into());
+    let UpgradeGameplayTokenDataArgs {
+        canvas_id,
+        max_cost,
+    } = *data_args;
+
+    assert_signer(holder_acct)?;
+    assert_token_prog(token_prog_acct)?;
+
+    let state = assert_not_frozen(place_state_pda_acct, canvas_id)?;
+
+    let (place_token_mint_pda, _) = PlaceState::token_mint_pda(canvas_id);
+    if *place_token_mint_acct.key != place_token_mint_pda {
+        return Err(PlaceError::InvalidPlaceTokenMintPDA.into());
+    }
+
+    let mut gameplay_token = load_gameplay_token(
+        &state,
+        holder_acct,
+        gameplay_token_meta_acct,
+        gameplay_token_ata_acct,
+    )?;
+
+    let schedule = state.upgrade_schedule;
+    if gameplay_token.upgrade_level >= schedule.max_level {
+        return Err(PlaceError::GameplayTokenMaxUpgradeLevel.into());
     }
 
-    if *system_acct.key != solana_program::system_program::id() {
-        return Err(PlaceError::InvalidAccountArgument.into());
+    if gameplay_token.cooldown_duration <= schedule.min_cooldown {
+        return Err(PlaceError::GameplayTokenCooldownAtMinimum.into());
     }
 
-    assert_signer(&current_owner_acct)?;
+    let next_level = gameplay_token.upgrade_level + 1;
+    let cost = schedule.cost(next_level);
+    if cost > max_cost {
+        return Err(PlaceError::UpgradeCostAboveMax.into());
+    }
 
-    if place_state_pda_acct.data_is_empty() {
-        // TODO(will): consider just putting owner pubkey as static consant
-        // rather than relying on being first to call this instruction
+    msg!(
+        "TAP: Burning {} place tokens to upgrade to level {}",
+        cost,
+        next_level
+    );
 
-        create_or_allocate_account_raw(
-            *program_id,
-            place_state_pda_acct,
-            system_acct,
-            current_owner_acct,
-            PlaceState::LEN,
-            &[PlaceState::PREFIX.as_bytes(), &[place_state_pda_bump]],
-        )?;
+    // the burn fails if the holder doesn't have enough place tokens
+    invoke(
+        &spl_token::instruction::burn(
+            token_prog_acct.key,
+            place_token_ata_acct.key,
+            place_token_mint_acct.key,
+            holder_acct.key,
+            &[],
+            cost,
+        )?,
+        &[
+            (*token_prog_acct).clone(),
+            (*place_token_ata_acct).clone(),
+            (*place_token_mint_acct).clone(),
+            (*holder_acct).clone(),
+        ],
+    )?;
 
-        let mut state: PlaceState =
-            try_from_slice_unchecked(&place_state_pda_acct.data.borrow_mut())?;
-        state.acct_type = PlaceAccountType::PlaceState;
-        state.owner = new_owner.unwrap_or(*current_owner_acct.key);
-        state.is_frozen = is_frozen.unwrap_or(crate::state::DEFAULT_IS_FROZEN);
-        state.paintbrush_price = paintbrush_price.unwrap_or(crate::state::DEFAULT_PAINTBRUSH_PRICE);
-        state.paintbrush_cooldown =
-            paintbrush_cooldown.unwrap_or(crate::state::DEFAULT_PAINTBRUSH_COOLDOWN);
-        state.bomb_price = paintbrush_cooldown.unwrap_or(crate::state::DEFAULT_BOMB_PRICE);
+    gameplay_token.upgrade_level = next_level;
+    gameplay_token.cooldown_duration = schedule.upgraded_cooldown(gameplay_token.cooldown_duration);
+    gameplay_token.serialize(&mut *gameplay_token_meta_acct.data.borrow_mut())?;
 
-        state.serialize(&mut *place_state_pda_acct.data.borrow_mut())?;
+    Ok(())
+}
 
-        Ok(())
-    } else {
-        let mut state = PlaceState::from_account_info(place_state_pda_acct)?;
+/// Checks the signer holds the role, each role has its own error when it doesn't
+fn assert_role(state: &PlaceState, role: PlaceRole, signer_acct: &AccountInfo) -> ProgramResult {
+    assert_signer(signer_acct)?;
 
-        // Only owner can update state
-        if state.owner != *current_owner_acct.key {
-            return Err(PlaceError::InvalidOwner.into());
-        }
+    if state.has_role(role, signer_acct.key) {
+        return Ok(());
+    }
 
-        if let Some(new_owner) = new_owner {
-            state.owner = new_owner;
-        }
-        if let Some(is_frozen) = is_frozen {
-            state.is_frozen = is_frozen;
-        }
-        if let Some(paintbrush_price) = paintbrush_price {
-            state.paintbrush_price = paintbrush_price;
-        }
-        if let Some(paintbrush_cooldown) = paintbrush_cooldown {
-            state.paintbrush_cooldown = paintbrush_cooldown;
-        }
-        if let Some(bomb_price) = bomb_price {
-            state.bomb_price = bomb_price;
-        }
+    let err = match role {
+        PlaceRole::SuperAdmin => PlaceError::InvalidOwner,
+        PlaceRole::PricingAdmin => PlaceError::NotPricingAdmin,
+        PlaceRole::FreezeAdmin => PlaceError::NotFreezeAdmin,
+        PlaceRole::Moderator => PlaceError::NotModerator,
+        PlaceRole::Treasurer => PlaceError::NotTreasurer,
+    };
+    Err(err.into())
+}
+
+fn process_grant_role(
+    _program_id: &Pubkey,
+    acct_args: GrantRoleAccountArgs,
+    data_args: &GrantRoleDataArgs,
+) -> ProgramResult {
+    let GrantRoleAccountArgs {
+        owner_acct,
+        place_state_pda_acct,
+    } = acct_args;
 
-        state.serialize(&mut *place_state_pda_acct.data.borrow_mut())?;
-        Ok(())
+    let GrantRoleDataArgs {
+        canvas_id,
+        role,
+        grantee,
+    } = *data_args;
+
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    if place_state_pda != *place_state_pda_acct.key {
+        return Err(PlaceError::IncorrectPlaceStatePDA.into());
     }
+
+    let mut state = PlaceState::from_account_info(place_state_pda_acct)?;
+    assert_role(&state, PlaceRole::SuperAdmin, owner_acct)?;
+
+    let holder = state
+        .roles
+        .holder_mut(role)
+        .ok_or(PlaceError::RoleNotGrantable)?;
+    *holder = grantee;
+
+    msg!("TAP: Granted {:?} to {}", role, grantee);
+    state.serialize(&mut *place_state_pda_acct.data.borrow_mut())?;
+
+    Ok(())
 }
 
-fn process_init_patch(
-    program_id: &Pubkey,
-    acct_args: InitPatchAccountArgs,
-    data_args: &InitPatchDataArgs,
+fn process_revoke_role(
+    _program_id: &Pubkey,
+    acct_args: RevokeRoleAccountArgs,
+    data_args: &RevokeRoleDataArgs,
 ) -> ProgramResult {
-    let InitPatchAccountArgs {
-        payer_acct,
-        patch_pda_acct,
-        system_acct,
+    let RevokeRoleAccountArgs {
+        owner_acct,
+        place_state_pda_acct,
     } = acct_args;
 
-    let InitPatchDataArgs { x_patch, y_patch } = data_args;
+    let RevokeRoleDataArgs { canvas_id, role } = *data_args;
 
-    // TODO(will): check owner, validate inputs more
-
-    if *system_acct.key != solana_program::system_program::id() {
-        return Err(PlaceError::InvalidAccountArgument.into());
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    if place_state_pda != *place_state_pda_acct.key {
+        return Err(PlaceError::IncorrectPlaceStatePDA.into());
     }
 
-    let max_x_patch = (PLACE_WIDTH_PX as usize) / PATCH_SIZE_PX;
-    let max_y_patch = (PLACE_HEIGHT_PX as usize) / PATCH_SIZE_PX;
-    let invalid_x = (*x_patch as usize) > max_x_patch;
-    let invalid_y = (*y_patch as usize) > max_y_patch;
+    let mut state = PlaceState::from_account_info(place_state_pda_acct)?;
+    assert_role(&state, PlaceRole::SuperAdmin, owner_acct)?;
 
-    if invalid_x || invalid_y {
-        return Err(InvalidPatchCoordinates.into());
-    }
+    let holder = state
+        .roles
+        .holder_mut(role)
+        .ok_or(PlaceError::RoleNotGrantable)?;
+    *holder = Pubkey::default();
 
-    let (patch_pda_key, patch_pda_bump) = find_address_for_patch(*x_patch, *y_patch, program_id);
+    msg!("TAP: Revoked {:?}", role);
+    state.serialize(&mut *place_state_pda_acct.data.borrow_mut())?;
 
-    if patch_pda_key != *patch_pda_acct.key {
-        return Err(IncorrectPatchPDA.into());
-    }
+    Ok(())
+}
 
-    if !patch_pda_acct.data_is_empty() {
-        return Err(PatchAccountAlreadyInitialized.into());
-    }
+fn process_propose_owner(
+    _program_id: &Pubkey,
+    acct_args: ProposeOwnerAccountArgs,
+    data_args: &ProposeOwnerDataArgs,
+) -> ProgramResult {
+    let ProposeOwnerAccountArgs {
+        owner_acct,
+        place_state_pda_acct,
+    } = acct_args;
 
-    // Allocate space for the tapestry state in the PDA account, subtracting the necessary rent from the payer
-    create_or_allocate_account_raw(
-        *program_id,
-        patch_pda_acct,
-        system_acct,
-        payer_acct,
-        PATCH_DATA_LEN,
-        &[
-            PATCH_PDA_PREFIX.as_bytes(),
-            &x_patch.to_le_bytes(),
-            &y_patch.to_le_bytes(),
-            &[patch_pda_bump],
-        ],
-    )?;
+    let ProposeOwnerDataArgs {
+        canvas_id,
+        new_owner,
+    } = *data_args;
 
-    let mut patch: Patch = try_from_slice_unchecked(&patch_pda_acct.try_borrow_data()?)?;
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    if place_state_pda != *place_state_pda_acct.key {
+        return Err(PlaceError::IncorrectPlaceStatePDA.into());
+    }
 
-    patch.acct_type = PlaceAccountType::Patch;
-    patch.x = *x_patch;
-    patch.y = *y_patch;
-    patch.pixels = vec![0; PATCH_SIZE_PX * PATCH_SIZE_PX];
+    let mut state = PlaceState::from_account_info(place_state_pda_acct)?;
+    assert_role(&state, PlaceRole::SuperAdmin, owner_acct)?;
 
-    patch.serialize(&mut *patch_pda_acct.data.borrow_mut())?;
+    state.pending_owner = new_owner;
 
-    return Ok(());
+    msg!("TAP: Proposed {} as owner", new_owner);
+    state.serialize(&mut *place_state_pda_acct.data.borrow_mut())?;
+
+    Ok(())
 }
 
-fn process_purchase_gameplay_token(
-    program_id: &Pubkey,
-    acct_args: PurchaseGameplayTokenAccountArgs,
-    data_args: PurchaseGameplayTokenDataArgs,
+fn process_accept_ownership(
+    _program_id: &Pubkey,
+    acct_args: AcceptOwnershipAccountArgs,
+    data_args: &AcceptOwnershipDataArgs,
 ) -> ProgramResult {
-    let PurchaseGameplayTokenAccountArgs {
-        payer_acct,
+    let AcceptOwnershipAccountArgs {
+        pending_owner_acct,
         place_state_pda_acct,
-        gameplay_meta_pda_acct,
-        gameplay_token_mint_pda_acct,
-        gameplay_token_ata_acct,
-        gameplay_token_mpl_meta_acct,
-        mpl_metadata_prog_acct,
-        token_prog_acct,
-        ata_prog_acct,
-        system_prog_acct,
-        rent_sysvar_acct,
     } = acct_args;
 
-    let PurchaseGameplayTokenDataArgs {
-        token_type,
-        random_seed,
-        desired_price,
-    } = data_args;
-
-    assert_signer(payer_acct)?;
+    let canvas_id = data_args.canvas_id;
 
-    let (gameplay_meta_pda, gameplay_meta_pda_bump) = GameplayTokenMeta::pda(random_seed);
-    if gameplay_meta_pda != *gameplay_meta_pda_acct.key {
-        return Err(PlaceError::IncorrectGameplayTokenMetaPDA.into());
-    }
+    assert_signer(pending_owner_acct)?;
 
-    let (place_state_pda, place_state_pda_bump) = PlaceState::pda();
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
     if place_state_pda != *place_state_pda_acct.key {
         return Err(PlaceError::IncorrectPlaceStatePDA.into());
     }
 
-    let (gameplay_token_mint_pda, gameplay_token_mint_pda_bump) =
-        GameplayTokenMeta::token_mint_pda(random_seed);
-    if gameplay_token_mint_pda != *gameplay_token_mint_pda_acct.key {
-        return Err(PlaceError::InvalidGameplayTokenMintPDA.into());
-    }
+    let mut state = PlaceState::from_account_info(place_state_pda_acct)?;
 
-    let (gameplay_token_mpl_meta_pda, _) = GameplayTokenMeta::token_metadata_pda(random_seed);
-    if *gameplay_token_mpl_meta_acct.key != gameplay_token_mpl_meta_pda {
-        return Err(PlaceError::InvalidAccountArgument.into());
+    if state.pending_owner == Pubkey::default() {
+        return Err(PlaceError::NoPendingOwner.into());
     }
-
-    if *system_prog_acct.key != solana_program::system_program::id() {
-        return Err(PlaceError::InvalidAccountArgument.into());
+    if state.pending_owner != *pending_owner_acct.key {
+        return Err(PlaceError::NotPendingOwner.into());
     }
 
-    if *token_prog_acct.key != spl_token::id() {
-        return Err(PlaceError::InvalidAccountArgument.into());
-    }
+    msg!(
+        "TAP: Ownership moved from {} to {}",
+        state.owner,
+        state.pending_owner
+    );
+    state.owner = state.pending_owner;
+    state.pending_owner = Pubkey::default();
+    state.serialize(&mut *place_state_pda_acct.data.borrow_mut())?;
 
-    if *ata_prog_acct.key != spl_associated_token_account::id() {
-        return Err(PlaceError::InvalidAccountArgument.into());
-    }
+    Ok(())
+}
 
-    if *mpl_metadata_prog_acct.key != mpl_token_metadata::id() {
-        return Err(PlaceError::InvalidAccountArgument.into());
-    }
+fn process_cancel_ownership_transfer(
+    _program_id: &Pubkey,
+    acct_args: CancelOwnershipTransferAccountArgs,
+    data_args: &CancelOwnershipTransferDataArgs,
+) -> ProgramResult {
+    let CancelOwnershipTransferAccountArgs {
+        owner_acct,
+        place_state_pda_acct,
+    } = acct_args;
 
-    if *rent_sysvar_acct.key != sysvar::rent::id() {
-        return Err(PlaceError::InvalidAccountArgument.into());
-    }
+    let canvas_id = data_args.canvas_id;
 
-    if !gameplay_meta_pda_acct.data_is_empty() {
-        return Err(PlaceError::GameplayTokenAlreadyPurchased.into());
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    if place_state_pda != *place_state_pda_acct.key {
+        return Err(PlaceError::IncorrectPlaceStatePDA.into());
     }
 
-    let state = PlaceState::from_account_info(place_state_pda_acct)?;
-    let price: u64 = match token_type {
-        GameplayTokenType::PaintBrush => state.paintbrush_price,
-        GameplayTokenType::Bomb => state.bomb_price,
-    };
+    let mut state = PlaceState::from_account_info(place_state_pda_acct)?;
+    assert_role(&state, PlaceRole::SuperAdmin, owner_acct)?;
 
-    if price != desired_price {
-        return Err(PlaceError::DesiredPriceDifferentFromCurrentPrice.into());
+    if state.pending_owner == Pubkey::default() {
+        return Err(PlaceError::NoPendingOwner.into());
     }
 
-    // -- pay for the token
-    msg!("TAP: Paying for token");
-
-    // NOTE(will): we do this before the gameplay account allocation so save an invoke
-    // this has the effect of reducing our fee by the rent overhead of the gameplay token account
-    invoke(
-        &system_instruction::transfer(&payer_acct.key, &gameplay_meta_pda, price),
-        &[
-            (*payer_acct).clone(),
-            (*gameplay_meta_pda_acct).clone(),
-            (*system_prog_acct).clone(),
-        ],
-    )?;
-
-    // -- Allocate space for the gameplay token account and initialize its state
-    msg!("TAP: Allocating gameplay token");
+    msg!(
+        "TAP: Cancelled ownership transfer to {}",
+        state.pending_owner
+    );
+    state.pending_owner = Pubkey::default();
+    state.serialize(&mut *place_state_pda_acct.data.borrow_mut())?;
 
-    let gameplay_meta_pda_seeds = &[
-        GameplayTokenMeta::PREFIX.as_bytes(),
-        &random_seed.to_le_bytes(),
-        &[gameplay_meta_pda_bump],
-    ];
+    Ok(())
+}
 
-    create_or_allocate_account_raw(
-        *program_id,
-        gameplay_meta_pda_acct,
-        system_prog_acct,
+fn process_migrate_account(
+    program_id: &Pubkey,
+    acct_args: MigrateAccountAccountArgs,
+    _data_args: &MigrateAccountDataArgs,
+) -> ProgramResult {
+    let MigrateAccountAccountArgs {
         payer_acct,
-        GameplayTokenMeta::LEN,
-        gameplay_meta_pda_seeds,
-    )?;
+        migrated_acct,
+        system_prog_acct,
+        destination_acct,
+    } = acct_args;
 
-    let clock = Clock::get()?;
+    assert_signer(payer_acct)?;
+    assert_system_prog(system_prog_acct)?;
 
-    let gameplay_token_meta = GameplayTokenMeta {
-        acct_type: PlaceAccountType::GameplayTokenMeta,
-        gameplay_type: token_type,
-        created_at_slot: clock.slot,
-        random_seed: random_seed,
-        token_mint_pda: gameplay_token_mint_pda,
-        update_allowed_slot: clock.slot,
-        cooldown_duration: state.paintbrush_cooldown,
-        place_tokens_owed: 5, // maybe give some "free" tokens for buying a gameplay token
-    };
+    if *migrated_acct.owner != *program_id || migrated_acct.data_is_empty() {
+        return Err(PlaceError::InvalidAccountArgument.into());
+    }
 
-    // -- Allocate space for the token mint and initialize it
-    msg!("TAP: Allocating token mint");
+    // the readers understand every older layout, so migrating is reading the account
+    // and writing it back out at the current length and version
+    // version 0 place states and patches live at addresses derived without a canvas id, so
+    // they move to the pda of the same account in LEGACY_CANVAS_ID, along with its seeds
+    let acct_type = PlaceAccountType::try_from_slice(&migrated_acct.data.borrow()[..1])?;
+    let (len, data, destination) = match acct_type {
+        PlaceAccountType::PlaceState => {
+            let state = PlaceState::from_account_info(migrated_acct)?;
+            let destination = if *migrated_acct.key == PlaceState::legacy_pda().0 {
+                let (pda, bump) = state.pda_for_instance();
+                let seeds = vec![
+                    PlaceState::PREFIX.as_bytes().to_vec(),
+                    state.canvas_id.to_le_bytes().to_vec(),
+                    vec![bump],
+                ];
+                Some((pda, seeds))
+            } else {
+                None
+            };
+            (PlaceState::LEN, state.try_to_vec()?, destination)
+        }
+        PlaceAccountType::GameplayTokenMeta => {
+            let meta = GameplayTokenMeta::from_account_info(migrated_acct)?;
+            (GameplayTokenMeta::LEN, meta.try_to_vec()?, None)
+        }
+        PlaceAccountType::Patch => {
+            let patch = Patch::from_account_info(migrated_acct)?;
+            let len = Patch::len_for_size(patch.patch_size() as u8, patch.format);
+            let destination = if *migrated_acct.key == Patch::legacy_pda(patch.x, patch.y).0 {
+                let (pda, bump) = patch.pda_for_instance();
+                let seeds = vec![
+                    PATCH_PDA_PREFIX.as_bytes().to_vec(),
+                    patch.canvas_id.to_le_bytes().to_vec(),
+                    patch.x.to_le_bytes().to_vec(),
+                    patch.y.to_le_bytes().to_vec(),
+                    vec![bump],
+                ];
+                Some((pda, seeds))
+            } else {
+                None
+            };
+            (len, patch.try_to_vec()?, destination)
+        }
+        _ => return Err(PlaceError::AccountNotMigratable.into()),
+    };
 
-    let gameplay_token_mint_pda_seeds = &[
-        GameplayTokenMeta::PREFIX.as_bytes(),
-        &random_seed.to_le_bytes(),
-        GameplayTokenMeta::MINT_PREFIX.as_bytes(),
-        &[gameplay_token_mint_pda_bump],
-    ];
+    if let Some((destination_pda, destination_seeds)) = destination {
+        let destination_acct = match destination_acct {
+            Some(acct) if *acct.key == destination_pda && acct.data_is_empty() => acct,
+            _ => return Err(PlaceError::InvalidMigrationDestination.into()),
+        };
 
-    let place_state_acct_pda_seeds = &[PlaceState::PREFIX.as_bytes(), &[place_state_pda_bump]];
+        let destination_seeds: Vec<&[u8]> = destination_seeds
+            .iter()
+            .map(|seed| seed.as_slice())
+            .collect();
+        create_or_allocate_account_raw(
+            *program_id,
+            destination_acct,
+            system_prog_acct,
+            payer_acct,
+            len,
+            &destination_seeds,
+        )?;
+        destination_acct.data.borrow_mut().copy_from_slice(&data);
+
+        // nothing reads the legacy address anymore, its rent pays for the new account
+        let legacy_lamports = migrated_acct.lamports();
+        **payer_acct.lamports.borrow_mut() += legacy_lamports;
+        **migrated_acct.lamports.borrow_mut() = 0;
+        migrated_acct.realloc(0, false)?;
+
+        msg!(
+            "TAP: Moved {:?} account {} to {}",
+            acct_type,
+            migrated_acct.key,
+            destination_acct.key
+        );
+        return Ok(());
+    }
 
-    create_or_allocate_account_raw(
-        *token_prog_acct.key,
-        gameplay_token_mint_pda_acct,
-        system_prog_acct,
-        payer_acct,
-        Mint::LEN,
-        gameplay_token_mint_pda_seeds,
-    )?;
+    // older versions of the same length only differ in the version byte and defaults
+    if migrated_acct.data_len() == len && migrated_acct.data.borrow()[..] == data[..] {
+        return Err(PlaceError::AccountAlreadyMigrated.into());
+    }
 
-    let init_mint_ix = initialize_mint(
-        &spl_token::id(),
-        &gameplay_token_mint_pda,
-        &place_state_pda,
-        None,
-        0,
-    )?;
+    let rent = Rent::get()?;
+    let required_lamports = rent
+        .minimum_balance(len)
+        .saturating_sub(migrated_acct.lamports());
+    if required_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(payer_acct.key, migrated_acct.key, required_lamports),
+            &[
+                payer_acct.clone(),
+                migrated_acct.clone(),
+                system_prog_acct.clone(),
+            ],
+        )?;
+    }
 
-    invoke_signed(
-        &init_mint_ix,
-        &[
-            (*token_prog_acct).clone(),
-            (*place_state_pda_acct).clone(),
-            (*gameplay_token_mint_pda_acct).clone(),
-            (*rent_sysvar_acct).clone(),
-        ],
-        &[place_state_acct_pda_seeds],
-    )?;
+    msg!(
+        "TAP: Migrated {:?} account {} from {} to {} bytes",
+        acct_type,
+        migrated_acct.key,
+        migrated_acct.data_len(),
+        len
+    );
+    migrated_acct.realloc(len, false)?;
+    migrated_acct.data.borrow_mut().copy_from_slice(&data);
 
-    // -- Create associated token account
-    msg!("TAP: Creating ATA");
+    Ok(())
+}
 
-    // TODO(will): figure out what hapens if this account already exists
-    let create_ata_ix =
-        create_associated_token_account(payer_acct.key, payer_acct.key, &gameplay_token_mint_pda);
+fn process_clear_region(
+    _program_id: &Pubkey,
+    acct_args: ClearRegionAccountArgs,
+    data_args: &ClearRegionDataArgs,
+) -> ProgramResult {
+    let ClearRegionAccountArgs {
+        moderator_acct,
+        place_state_pda_acct,
+        canvas_snapshot_pda_acct,
+        patch_pda_accts,
+    } = acct_args;
 
-    invoke_signed(
-        &create_ata_ix,
-        &[
-            (*gameplay_token_ata_acct).clone(),
-            (*payer_acct).clone(),
-            (*gameplay_token_mint_pda_acct).clone(),
-            (*ata_prog_acct).clone(),
-            (*rent_sysvar_acct).clone(),
-            (*system_prog_acct).clone(),
-        ],
-        &[],
-    )?;
+    let ClearRegionDataArgs {
+        canvas_id,
+        x_min,
+        y_min,
+        x_max,
+        y_max,
+        pixel,
+    } = data_args;
 
-    // -- Mint NFT into ATA
-    msg!("TAP: Minting NFT into ATA");
+    let (place_state_pda, _) = PlaceState::pda(*canvas_id);
+    if place_state_pda != *place_state_pda_acct.key {
+        return Err(PlaceError::IncorrectPlaceStatePDA.into());
+    }
 
-    let mint_token_ix = spl_token::instruction::mint_to(
-        token_prog_acct.key,
-        gameplay_token_mint_pda_acct.key,
-        gameplay_token_ata_acct.key,
-        place_state_pda_acct.key,
-        &[place_state_pda_acct.key],
-        1,
-    )?;
+    // moderators can clean up a frozen place, freezing is often the first response
+    let state = PlaceState::from_account_info(place_state_pda_acct)?;
+    assert_role(&state, PlaceRole::Moderator, moderator_acct)?;
 
-    invoke_signed(
-        &mint_token_ix,
-        &[
-            (*token_prog_acct).clone(),
-            (*gameplay_token_mint_pda_acct).clone(),
-            (*gameplay_token_ata_acct).clone(),
-            (*place_state_pda_acct).clone(),
-            // Do I need to add this? we will find out (*payer_acct).clone(),
-        ],
-        &[place_state_acct_pda_seeds],
-    )?;
+    let (canvas_snapshot_pda, _) = CanvasSnapshot::pda(*canvas_id);
+    if canvas_snapshot_pda != *canvas_snapshot_pda_acct.key {
+        return Err(PlaceError::IncorrectCanvasSnapshotPDA.into());
+    }
 
-    // -- Create the metaplex token metadata
-    msg!("TAP: Creating mpl metadata");
+    // but not once sealing has started, patches already chained into the hash would no
+    // longer match it
+    if state.is_frozen() && !canvas_snapshot_pda_acct.data_is_empty() {
+        let snapshot = CanvasSnapshot::from_account_info(canvas_snapshot_pda_acct)?;
+        if snapshot.frozen_at_slot == state.frozen_at_slot
+            && (snapshot.is_sealed || snapshot.next_patch > 0)
+        {
+            return Err(PlaceError::CanvasSealed.into());
+        }
+    }
 
-    let token_name = match token_type {
-        GameplayTokenType::Bomb => String::from("Tapestry Bomb"),
-        GameplayTokenType::PaintBrush => String::from("Tapestry Paintbrush"),
-    };
+    if !state.is_valid_rect(*x_min, *y_min, *x_max, *y_max) {
+        return Err(PlaceError::InvalidClearBounds.into());
+    }
 
-    let token_uri = match token_type {
-        GameplayTokenType::Bomb => String::from("http://localhost:8080/bomb.json"),
-        GameplayTokenType::PaintBrush => String::from("http://localhost:8080/paintbrush.json"),
-    };
+    if !state.pixel_format.is_valid_pixel(*pixel) {
+        return Err(PlaceError::InvalidPixelValue.into());
+    }
 
-    let create_mpl_meta_ix = create_metadata_accounts_v2(
-        mpl_token_metadata::id(),
-        gameplay_token_mpl_meta_pda,
-        gameplay_token_mint_pda.clone(),
-        place_state_pda.clone(),
-        payer_acct.key.clone(),
-        place_state_pda.clone(),
-        token_name,
-        String::from("Tapestry"),
-        token_uri,
-        None,
-        0,
-        true,
-        false,
-        Some(Collection {
-            verified: false, // have to do this via a separate instruction
-            key: place_state_pda.clone(),
-        }),
-        None, // this is "uses", can this be leveraged for bombs?
+    let (x_min, y_min, x_max, y_max) = (
+        *x_min as usize,
+        *y_min as usize,
+        *x_max as usize,
+        *y_max as usize,
     );
 
-    invoke_signed(
-        &create_mpl_meta_ix,
-        &[
-            (*mpl_metadata_prog_acct).clone(),
-            (*gameplay_token_mint_pda_acct).clone(),
-            (*payer_acct).clone(),
-            (*place_state_pda_acct).clone(),
-            (*rent_sysvar_acct).clone(),
-            (*system_prog_acct).clone(),
-            (*gameplay_token_mpl_meta_acct).clone(),
-        ],
-        &[gameplay_token_mint_pda_seeds, place_state_acct_pda_seeds],
-    )?;
+    // parse every patch before writing any of them, shields don't apply to moderators
+    let rect_patches = state.patches_in_rect(x_min, y_min, x_max, y_max);
+    if rect_patches.len() != patch_pda_accts.len() {
+        return Err(PlaceError::InvalidClearPatches.into());
+    }
 
-    // TODO(will): maybe verify the colleciton using verify_collection ix?
+    let mut patches: Vec<Patch> = Vec::with_capacity(rect_patches.len());
+    for (patch_pda_acct, (patch_x, patch_y)) in patch_pda_accts.iter().zip(rect_patches) {
+        if patch_pda_acct.data_is_empty() {
+            return Err(PlaceError::PatchNotInitialized.into());
+        }
+        let patch = Patch::from_account_info(patch_pda_acct)?;
+        if patch.canvas_id != *canvas_id || patch.x != patch_x || patch.y != patch_y {
+            return Err(PlaceError::InvalidClearPatches.into());
+        }
 
-    // TODO(will): maybe remove authority to hard limit supply to one
+        let (patch_pda, _) = patch.pda_for_instance();
+        if patch_pda != *patch_pda_acct.key {
+            return Err(PlaceError::IncorrectPatchPDA.into());
+        }
 
-    // IMPORTANT - save the game state, for some reason if i do this earlier the transfer
-    // of SOL to this account fails
+        patches.push(patch);
+    }
 
-    gameplay_token_meta.serialize(&mut *gameplay_meta_pda_acct.data.borrow_mut())?;
+    // attribution is left alone, so whoever painted the cleared pixels can still be found
+    let patch_size = state.patch_size();
+    for (patch_pda_acct, mut patch) in patch_pda_accts.iter().zip(patches) {
+        let patch_x_tl = patch.x as usize * patch_size;
+        let patch_y_tl = patch.y as usize * patch_size;
+
+        for place_y in y_min.max(patch_y_tl)..=y_max.min(patch_y_tl + patch_size - 1) {
+            for place_x in x_min.max(patch_x_tl)..=x_max.min(patch_x_tl + patch_size - 1) {
+                let idx = ((place_y - patch_y_tl) * patch_size) + (place_x - patch_x_tl);
+                patch.set_pixel_at(idx, *pixel);
+            }
+        }
+
+        patch.serialize(&mut *patch_pda_acct.data.borrow_mut())?;
+    }
+
+    msg!(
+        "TAP: moderator {} cleared canvas {} from ({}, {}) to ({}, {}) with pixel {}",
+        moderator_acct.key,
+        canvas_id,
+        x_min,
+        y_min,
+        x_max,
+        y_max,
+        pixel
+    );
 
     Ok(())
 }
 
-// Thought process on question of storing the "cooldown" time in the global state account
-// versus storing on each gameplay token.
-// Store on token meta accounts:
-//   PRO: fewer account args to set pixel
-//   CON: duplicate a u64 across all tokens (more space)
-//   PRO: save compute on set pixel by not parsing an additional account
-//   PRO or CON: gameplay tokens are locked in to their cooldown rate
-// Store on global state account:
-//   CON: less bytes for each token purchase
-//   PRO or CON: could slow down or speed up the game by changing one variable in global state
-//
-// Based on this, seems better to store in the token account than use global state
-
-fn process_set_pixel(
+fn process_ban_gameplay_token(
     program_id: &Pubkey,
-    acct_args: SetPixelAccountArgs,
-    data_args: &SetPixelDataArgs,
+    acct_args: BanGameplayTokenAccountArgs,
+    data_args: &BanGameplayTokenDataArgs,
 ) -> ProgramResult {
-    let SetPixelAccountArgs {
-        payer_acct,
-        patch_pda_acct,
+    let BanGameplayTokenAccountArgs {
+        moderator_acct,
+        place_state_pda_acct,
         gameplay_token_meta_acct,
-        payer_gameplay_token_acct,
-        system_acct,
     } = acct_args;
 
-    let SetPixelDataArgs {
-        x,
-        y,
-        x_offset,
-        y_offset,
-        pixel,
+    let BanGameplayTokenDataArgs {
+        canvas_id,
+        is_banned,
     } = data_args;
 
-    // everything is based on the signer owning a token account with a balance of 1,
-    // and with a mint that matches the mint in the gameplay token meta
-    // so payer had better be signer.
-    assert_signer(payer_acct)?;
+    let (place_state_pda, _) = PlaceState::pda(*canvas_id);
+    if place_state_pda != *place_state_pda_acct.key {
+        return Err(PlaceError::IncorrectPlaceStatePDA.into());
+    }
 
-    // TODO(will): check token acct is owned by token program or whatever
-    // also maybe check ownership on other accounts
+    let state = PlaceState::from_account_info(place_state_pda_acct)?;
+    assert_role(&state, PlaceRole::Moderator, moderator_acct)?;
 
-    if *system_acct.key != solana_program::system_program::id() {
-        return Err(PlaceError::InvalidAccountArgument.into());
+    if *gameplay_token_meta_acct.owner != *program_id {
+        return Err(PlaceError::InvalidGameplayTokenMetaPDAOwner.into());
     }
 
-    // Parse and validate account arguments
+    let mut gameplay_token = GameplayTokenMeta::from_account_info(gameplay_token_meta_acct)?;
+    let (gameplay_token_pda, _) = gameplay_token.pda_for_instance();
+    if gameplay_token_pda != *gameplay_token_meta_acct.key {
+        return Err(PlaceError::IncorrectGameplayTokenMetaPDA.into());
+    }
 
-    let mut patch: Patch = Patch::from_account_info(patch_pda_acct)?;
-    let (patch_pda, _) = patch.pda_for_instance();
-    if patch_pda != *patch_pda_acct.key {
-        return Err(PlaceError::IncorrectPatchPDA.into());
+    // moderators of one canvas can't ban tokens belonging to another
+    if gameplay_token.canvas_id != *canvas_id {
+        return Err(PlaceError::GameplayTokenCanvasMismatch.into());
     }
 
-    if *x != patch.x || *y != patch.y {
-        return Err(PlaceError::IncorrectPatchPDA.into());
+    gameplay_token.is_banned = *is_banned;
+    gameplay_token.serialize(&mut *gameplay_token_meta_acct.data.borrow_mut())?;
+
+    msg!(
+        "TAP: moderator {} set banned to {} for gameplay token {} on canvas {}",
+        moderator_acct.key,
+        is_banned,
+        gameplay_token.random_seed,
+        canvas_id
+    );
+
+    Ok(())
+}
+
+fn process_start_new_season(
+    program_id: &Pubkey,
+    acct_args: StartNewSeasonAccountArgs,
+    data_args: &StartNewSeasonDataArgs,
+) -> ProgramResult {
+    let StartNewSeasonAccountArgs {
+        owner_acct,
+        place_state_pda_acct,
+        canvas_snapshot_pda_acct,
+        season_archive_pda_acct,
+        system_prog_acct,
+    } = acct_args;
+
+    let StartNewSeasonDataArgs { canvas_id } = *data_args;
+
+    assert_system_prog(system_prog_acct)?;
+
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    if place_state_pda != *place_state_pda_acct.key {
+        return Err(PlaceError::IncorrectPlaceStatePDA.into());
     }
 
-    let mut gameplay_token: GameplayTokenMeta =
-        GameplayTokenMeta::from_account_info(gameplay_token_meta_acct)?;
-    let (gameplay_token_pda, _) = gameplay_token.pda_for_instance();
-    if gameplay_token_pda != *gameplay_token_meta_acct.key {
-        return Err(PlaceError::IncorrectGameplayTokenMetaPDA.into());
+    let mut state = PlaceState::from_account_info(place_state_pda_acct)?;
+    assert_role(&state, PlaceRole::SuperAdmin, owner_acct)?;
+
+    if !state.is_frozen() {
+        return Err(PlaceError::PlaceNotFrozen.into());
     }
 
-    // check the token account looks good
-    let gameplay_ata = TokenAccount::unpack_from_slice(&payer_gameplay_token_acct.data.borrow())?;
-    if gameplay_ata.owner != *payer_acct.key {
-        return Err(PlaceError::InvalidGameplayTokenAccountOwner.into());
+    if state.patches_to_reset > 0 {
+        return Err(PlaceError::SeasonResetIncomplete.into());
     }
-    if gameplay_ata.amount != 1 {
-        return Err(PlaceError::InvalidGameplayTokenAccountBalance.into());
+
+    let (canvas_snapshot_pda, _) = CanvasSnapshot::pda(canvas_id);
+    if canvas_snapshot_pda != *canvas_snapshot_pda_acct.key {
+        return Err(PlaceError::IncorrectCanvasSnapshotPDA.into());
     }
-    if gameplay_ata.mint != gameplay_token.token_mint_pda {
-        return Err(PlaceError::InvalidGameplayTokenAccountMint.into());
+
+    if canvas_snapshot_pda_acct.data_is_empty() {
+        return Err(PlaceError::CanvasNotSealed.into());
     }
 
-    // check the gameplay token meta to make sure it is "ready"
+    let mut snapshot = CanvasSnapshot::from_account_info(canvas_snapshot_pda_acct)?;
+    if !snapshot.is_sealed || snapshot.frozen_at_slot != state.frozen_at_slot {
+        return Err(PlaceError::CanvasNotSealed.into());
+    }
 
-    let clock = Clock::get()?;
-    let current_slot = clock.slot;
-    if gameplay_token.update_allowed_slot > current_slot {
-        return Err(PlaceError::GameplayTokenNotReady.into());
+    let (season_archive_pda, season_archive_pda_bump) = SeasonArchive::pda(canvas_id, state.season);
+    if season_archive_pda != *season_archive_pda_acct.key {
+        return Err(PlaceError::IncorrectSeasonArchivePDA.into());
     }
 
+    create_or_allocate_account_raw(
+        *program_id,
+        season_archive_pda_acct,
+        system_prog_acct,
+        owner_acct,
+        SeasonArchive::LEN,
+        &[
+            PlaceState::PREFIX.as_bytes(),
+            &canvas_id.to_le_bytes(),
+            SeasonArchive::PREFIX.as_bytes(),
+            &state.season.to_le_bytes(),
+            &[season_archive_pda_bump],
+        ],
+    )?;
+
+    let clock = Clock::get()?;
+    let archive = SeasonArchive {
+        acct_type: PlaceAccountType::SeasonArchive,
+        canvas_id,
+        season: state.season,
+        frozen_at_slot: snapshot.frozen_at_slot,
+        snapshot_hash: snapshot.hash,
+        archived_at_slot: clock.slot,
+    };
+    archive.serialize(&mut *season_archive_pda_acct.data.borrow_mut())?;
+
     msg!(
-        "TAP: current_slot: {}, allowed_after: {}",
-        current_slot,
-        gameplay_token.update_allowed_slot
+        "TAP: archived season {} of canvas {}, hash: {}",
+        archive.season,
+        canvas_id,
+        Hash::new_from_array(archive.snapshot_hash)
     );
 
-    // update the cooldown for the token
-    gameplay_token.update_allowed_slot = current_slot + gameplay_token.cooldown_duration;
-    gameplay_token.place_tokens_owed += 1;
-    gameplay_token.serialize(&mut *gameplay_token_meta_acct.data.borrow_mut())?;
+    // the hash lives on in the archive, the blanked canvas has to be sealed again
+    // before the next season can be archived
+    snapshot.next_patch = 0;
+    snapshot.hash = [0; 32];
+    snapshot.is_sealed = false;
+    snapshot.serialize(&mut *canvas_snapshot_pda_acct.data.borrow_mut())?;
 
-    // Change the pixel
+    state.season += 1;
+    state.patches_to_reset = state.patch_count();
+    state.serialize(&mut *place_state_pda_acct.data.borrow_mut())?;
 
-    let y_offset_usize = *y_offset as usize;
-    let x_offset_usize = *x_offset as usize;
+    Ok(())
+}
 
-    let idx = (y_offset_usize * PATCH_SIZE_PX) + x_offset_usize;
-    patch.pixels[idx] = *pixel;
-    patch.serialize(&mut *patch_pda_acct.data.borrow_mut())?;
+fn process_reset_patches(
+    _program_id: &Pubkey,
+    acct_args: ResetPatchesAccountArgs,
+    data_args: &ResetPatchesDataArgs,
+) -> ProgramResult {
+    let ResetPatchesAccountArgs {
+        owner_acct,
+        place_state_pda_acct,
+        patch_pda_accts,
+    } = acct_args;
+
+    let ResetPatchesDataArgs { canvas_id } = *data_args;
+
+    let (place_state_pda, _) = PlaceState::pda(canvas_id);
+    if place_state_pda != *place_state_pda_acct.key {
+        return Err(PlaceError::IncorrectPlaceStatePDA.into());
+    }
+
+    let mut state = PlaceState::from_account_info(place_state_pda_acct)?;
+    assert_role(&state, PlaceRole::SuperAdmin, owner_acct)?;
+
+    if patch_pda_accts.len() % 2 != 0 {
+        return Err(PlaceError::InvalidResetPatches.into());
+    }
+
+    for patch_accts in patch_pda_accts.chunks(2) {
+        let patch_pda_acct = &patch_accts[0];
+        let patch_attribution_pda_acct = &patch_accts[1];
+
+        if state.patches_to_reset == 0 {
+            return Err(PlaceError::InvalidResetPatches.into());
+        }
+
+        let (patch_x, patch_y) = state.patch_coords(state.next_patch_to_reset());
+        let (patch_pda, _) = Patch::pda(canvas_id, patch_x, patch_y);
+        let (patch_attribution_pda, _) = PatchAttribution::pda(canvas_id, patch_x, patch_y);
+        if patch_pda != *patch_pda_acct.key
+            || patch_attribution_pda != *patch_attribution_pda_acct.key
+        {
+            return Err(PlaceError::InvalidResetPatches.into());
+        }
+
+        // patches that were never created are already blank
+        if !patch_pda_acct.data_is_empty() {
+            let mut patch = Patch::from_account_info(patch_pda_acct)?;
+            patch.pixels = vec![0; state.pixel_bytes_per_patch()];
+            patch.shield = PatchShield::default();
+            patch.serialize(&mut *patch_pda_acct.data.borrow_mut())?;
+
+            // nothing to clear for a patch that never had attribution allocated
+            if !patch_attribution_pda_acct.data_is_empty() {
+                let mut patch_attribution =
+                    load_patch_attribution(patch_attribution_pda_acct, &patch)?;
+                patch_attribution.clear();
+                patch_attribution.serialize(&mut *patch_attribution_pda_acct.data.borrow_mut())?;
+            }
+        }
+
+        state.patches_to_reset -= 1;
+    }
+
+    if state.patches_to_reset == 0 {
+        msg!("TAP: every patch is blank for season {}", state.season);
+    }
+
+    state.serialize(&mut *place_state_pda_acct.data.borrow_mut())?;
 
     Ok(())
 }
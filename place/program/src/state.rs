@@ -1,7 +1,7 @@
 use std::thread::AccessError;
 
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::{pubkey::Pubkey, account_info::AccountInfo, program_error::ProgramError, borsh::try_from_slice_unchecked, clock::Slot,};
+use solana_program::{pubkey::Pubkey, account_info::AccountInfo, program_error::ProgramError, borsh::try_from_slice_unchecked, clock::Slot, hash::{hashv, Hash},};
 use crate::error::PlaceError;
 
 // Identifies an Account type in the first byte of the account data
@@ -12,6 +12,11 @@ pub enum PlaceAccountType {
     PlaceState,
     Patch,
     GameplayTokenMeta,
+    CanvasSnapshot,
+    PatchAttribution,
+    RegionNftMeta,
+    AllowlistReceipt,
+    SeasonArchive,
 }
 
 pub fn try_from_slice_checked<T: BorshDeserialize>(
@@ -30,78 +35,1231 @@ pub fn try_from_slice_checked<T: BorshDeserialize>(
     Ok(result)
 }
 
+// Layout version written into the PlaceState, Patch and GameplayTokenMeta accounts.
+// Version 0 accounts are the original layouts, created before canvas ids, the version
+// byte and reserved padding existed.
+pub const CURRENT_ACCOUNT_VERSION: u8 = 1;
+
+// Canvas that version 0 accounts are upgraded into, they all belonged to the single place
+pub const LEGACY_CANVAS_ID: u64 = 0;
+
+// Zeroed bytes at the end of each versioned account, so fields can be added without
+// reallocating. A new field is carved from the front of `reserved`, which shrinks by the
+// size of the field, and CURRENT_ACCOUNT_VERSION goes up with it. Accounts written at
+// an older version hold zeroes where the field is, so readers check `version` and give
+// the field its default in those accounts. Once reserved runs out, accounts have to
+// grow through MigrateAccount instead.
+pub const PLACE_STATE_RESERVED_LEN: usize = 64;
+pub const GAMEPLAY_TOKEN_META_RESERVED_LEN: usize = 32;
+pub const PATCH_RESERVED_LEN: usize = 32;
+
 //////////////////////////////////////////////////////////////////////////////////
 ////////////////////////////// PLACE STATE ///////////////////////////////////////
 
-pub const DEFAULT_IS_FROZEN: bool = false;
-pub const DEFAULT_PAINTBRUSH_PRICE: u64 = 20_000_000;                   // units are lamports
-pub const DEFAULT_PAINTBRUSH_COOLDOWN: Slot = (5 * 60 * 1000) / 400;    // units are slots 
-pub const DEFAULT_BOMB_PRICE: u64 = 500_000_000;                        // units are lamports
+pub const DEFAULT_PAUSED: PauseFlags = PauseFlags::NONE;
+pub const DEFAULT_PAINTBRUSH_PRICE: u64 = 20_000_000;                   // units are lamports
+pub const DEFAULT_PAINTBRUSH_COOLDOWN: Slot = (5 * 60 * 1000) / 400;    // units are slots 
+pub const DEFAULT_BOMB_PRICE: u64 = 500_000_000;                        // units are lamports
+pub const DEFAULT_PLACE_WIDTH_PX: u16 = 1000;
+pub const DEFAULT_PLACE_HEIGHT_PX: u16 = 1000;
+pub const DEFAULT_PATCH_SIZE_PX: u8 = 20;
+pub const DEFAULT_PIXEL_FORMAT: PixelFormat = PixelFormat::Indexed8;
+pub const DEFAULT_SHIELD_PRICE: u64 = 200_000_000;                      // units are lamports
+pub const DEFAULT_SHIELD_DURATION: Slot = (60 * 60 * 1000) / 400;       // units are slots
+pub const DEFAULT_RETIRE_REFUND_BPS: u16 = 0;                           // units are basis points of the purchase price
+pub const DEFAULT_UPGRADE_BASE_COST: u64 = 10;                          // units are place tokens
+pub const DEFAULT_UPGRADE_COOLDOWN_STEP: Slot = (60 * 1000) / 400;      // units are slots
+pub const DEFAULT_MIN_COOLDOWN: Slot = (60 * 1000) / 400;               // units are slots
+pub const DEFAULT_MAX_UPGRADE_LEVEL: u8 = 4;
+pub const DEFAULT_BOMB_COOLDOWN: Slot = DEFAULT_PAINTBRUSH_COOLDOWN;    // units are slots
+pub const DEFAULT_SHIELD_COOLDOWN: Slot = DEFAULT_PAINTBRUSH_COOLDOWN;  // units are slots
+pub const DEFAULT_PAINTBRUSH_REWARDS: GameplayTokenRewards = GameplayTokenRewards { purchase_bonus: 5, use_reward: 1 };
+pub const DEFAULT_BOMB_REWARDS: GameplayTokenRewards = GameplayTokenRewards { purchase_bonus: 5, use_reward: 1 };
+pub const DEFAULT_SHIELD_REWARDS: GameplayTokenRewards = GameplayTokenRewards { purchase_bonus: 5, use_reward: 0 };
+
+// Fixed point scale used when compounding exponential price steps
+const PRICE_FACTOR_SCALE: u128 = 1_000_000_000;
+
+// Largest patch allowed, limited by the 10KB an account can be allocated with inside
+// an instruction, the patch attribution account is the largest per patch account
+pub const MAX_PATCH_SIZE_PX: u8 = 25;
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct PlaceState {
+    pub acct_type: PlaceAccountType,
+
+    // layout version of this account, see CURRENT_ACCOUNT_VERSION
+    pub version: u8,
+
+    // identifies this canvas, every account belonging to the canvas uses it as a pda seed
+    pub canvas_id: u64,
+
+    // The owner of the tapestry, unsure if there is a better way to handle this
+    pub owner: Pubkey,
+
+    // slot at which the tapestry was last frozen, see PauseFlags::FROZEN
+    pub frozen_at_slot: Slot,
+
+    // Current price of a gameplay token of type Paintbrush
+    pub paintbrush_price: u64,
+
+    // number of slots for the cooldown for new paintbrushes
+    pub paintbrush_cooldown: Slot,
+
+    // current price of a gameplay token of type Bomb
+    pub bomb_price: u64,
+
+    // width of the place in pixels, fixed at initialization
+    pub width_px: u16,
+
+    // height of the place in pixels, fixed at initialization
+    pub height_px: u16,
+
+    // width and height of a single patch in pixels, fixed at initialization
+    pub patch_size_px: u8,
+
+    // encoding of the pixels in every patch, fixed at initialization
+    pub pixel_format: PixelFormat,
+
+    // current price of a gameplay token of type Shield
+    pub shield_price: u64,
+
+    // number of slots an area stays protected after a shield is activated
+    pub shield_duration: Slot,
+
+    // how gameplay token prices move with demand, applied on top of the per type prices above
+    pub price_curve: PriceCurve,
+
+    // recent sales of gameplay tokens of type Paintbrush, drives the price curve
+    pub paintbrush_sales: GameplayTokenSales,
+
+    // recent sales of gameplay tokens of type Bomb, drives the price curve
+    pub bomb_sales: GameplayTokenSales,
+
+    // recent sales of gameplay tokens of type Shield, drives the price curve
+    pub shield_sales: GameplayTokenSales,
+
+    // share of the purchase price refunded from the treasury when a gameplay token is retired
+    pub retire_refund_bps: u16,
+
+    // cost and effect of spending place tokens to lower a gameplay token's cooldown
+    pub upgrade_schedule: UpgradeSchedule,
+
+    // prices of gameplay tokens bought with place tokens instead of lamports
+    pub place_token_pricing: PlaceTokenPricing,
+
+    // number of slots for the cooldown for new bombs
+    pub bomb_cooldown: Slot,
+
+    // number of slots for the cooldown for new shields
+    pub shield_cooldown: Slot,
+
+    // place tokens earned by gameplay tokens of type Paintbrush
+    pub paintbrush_rewards: GameplayTokenRewards,
+
+    // place tokens earned by gameplay tokens of type Bomb
+    pub bomb_rewards: GameplayTokenRewards,
+
+    // place tokens earned by gameplay tokens of type Shield
+    pub shield_rewards: GameplayTokenRewards,
+
+    // limits on how many place tokens claims can mint
+    pub emission_schedule: EmissionSchedule,
+
+    // index of the emission window the latest claim was in, slot / window_slots
+    pub emission_window: u64,
+
+    // place tokens minted by claims during the emission window
+    pub emitted_in_window: u64,
+
+    // keys holding the narrower admin roles, the owner is the super-admin
+    pub roles: PlaceRoles,
+
+    // the key proposed as the next owner, the default pubkey when there is no proposal
+    pub pending_owner: Pubkey,
+
+    // when purchases open, and who can buy before they open to everyone
+    pub purchase_phases: PurchasePhases,
+
+    // number of the current season, starts at 0 and goes up with every StartNewSeason
+    pub season: u32,
+
+    // patches still to be blanked by ResetPatches since the season started, the place
+    // can't be unfrozen until this is 0
+    pub patches_to_reset: u32,
+
+    // operations that are paused, either one by one during an incident or all at once
+    // when the tapestry is frozen for NFT creation and auction
+    pub paused: PauseFlags,
+
+    // room for new fields without reallocating, always zero until a new version uses it
+    pub reserved: [u8; PLACE_STATE_RESERVED_LEN],
+}
+
+/// The place state as it was first deployed, only read to upgrade it
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+struct PlaceStateV0 {
+    acct_type: PlaceAccountType,
+    owner: Pubkey,
+    is_frozen: bool,
+    paintbrush_price: u64,
+    paintbrush_cooldown: Slot,
+    bomb_price: u64,
+}
+
+impl PlaceStateV0 {
+    const LEN: usize = 0 +
+        1 + // acct_type
+        32 + // owner
+        1 + // is_frozen
+        8 + // paintbrush_price
+        8 + // paintbrush_cooldown
+        8; // bomb_price
+}
+
+impl PlaceState {
+    pub const LEN: usize = 0 +
+        1 + // acct_type
+        1 + // version
+        8 + // canvas_id
+        32 + // owner
+        8 + // frozen_at_slot
+        8 + // paintbrush_price
+        8 + // paintbrush_cooldown
+        8 + // bomb_price
+        2 + // width_px
+        2 + // height_px
+        1 + // patch_size_px
+        1 + // pixel_format
+        8 + // shield_price
+        8 + // shield_duration
+        PriceCurve::LEN + // price_curve
+        GameplayTokenSales::LEN + // paintbrush_sales
+        GameplayTokenSales::LEN + // bomb_sales
+        GameplayTokenSales::LEN + // shield_sales
+        2 + // retire_refund_bps
+        UpgradeSchedule::LEN + // upgrade_schedule
+        PlaceTokenPricing::LEN + // place_token_pricing
+        8 + // bomb_cooldown
+        8 + // shield_cooldown
+        GameplayTokenRewards::LEN + // paintbrush_rewards
+        GameplayTokenRewards::LEN + // bomb_rewards
+        GameplayTokenRewards::LEN + // shield_rewards
+        EmissionSchedule::LEN + // emission_schedule
+        8 + // emission_window
+        8 + // emitted_in_window
+        PlaceRoles::LEN + // roles
+        32 + // pending_owner
+        PurchasePhases::LEN + // purchase_phases
+        4 + // season
+        4 + // patches_to_reset
+        PauseFlags::LEN + // paused
+        PLACE_STATE_RESERVED_LEN; // reserved
+
+    pub const LEN_V0: usize = PlaceStateV0::LEN;
+
+    pub const PREFIX: &'static str = "place";
+    pub const TOKEN_MINT_PREFIX: &'static str = "tokes";
+    pub const TREASURY_PREFIX: &'static str = "treasury";
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<PlaceState, ProgramError> {
+        Self::from_bytes(&a.data.borrow())
+    }
+
+    /// Reads any layout version, older versions are upgraded to the current one in memory
+    pub fn from_bytes(b: &[u8]) -> Result<PlaceState, ProgramError> {
+        if b.len() == Self::LEN_V0 {
+            let v0: PlaceStateV0 = try_from_slice_checked(b, PlaceAccountType::PlaceState, Self::LEN_V0)?;
+            return Ok(Self::from_v0(v0));
+        }
+
+        let state: PlaceState = try_from_slice_checked(b, PlaceAccountType::PlaceState, Self::LEN)?;
+        if state.version > CURRENT_ACCOUNT_VERSION {
+            return Err(PlaceError::UnsupportedAccountVersion.into());
+        }
+        Ok(state)
+    }
+
+    /// Everything added since version 0 takes the value InitializePlace defaults it to
+    fn from_v0(v0: PlaceStateV0) -> PlaceState {
+        PlaceState {
+            acct_type: PlaceAccountType::PlaceState,
+            version: CURRENT_ACCOUNT_VERSION,
+            canvas_id: LEGACY_CANVAS_ID,
+            owner: v0.owner,
+            frozen_at_slot: 0,
+            paintbrush_price: v0.paintbrush_price,
+            paintbrush_cooldown: v0.paintbrush_cooldown,
+            bomb_price: v0.bomb_price,
+            width_px: DEFAULT_PLACE_WIDTH_PX,
+            height_px: DEFAULT_PLACE_HEIGHT_PX,
+            patch_size_px: DEFAULT_PATCH_SIZE_PX,
+            pixel_format: DEFAULT_PIXEL_FORMAT,
+            shield_price: DEFAULT_SHIELD_PRICE,
+            shield_duration: DEFAULT_SHIELD_DURATION,
+            price_curve: PriceCurve::default(),
+            paintbrush_sales: GameplayTokenSales::default(),
+            bomb_sales: GameplayTokenSales::default(),
+            shield_sales: GameplayTokenSales::default(),
+            retire_refund_bps: DEFAULT_RETIRE_REFUND_BPS,
+            upgrade_schedule: UpgradeSchedule::default(),
+            place_token_pricing: PlaceTokenPricing::default(),
+            bomb_cooldown: DEFAULT_BOMB_COOLDOWN,
+            shield_cooldown: DEFAULT_SHIELD_COOLDOWN,
+            paintbrush_rewards: DEFAULT_PAINTBRUSH_REWARDS,
+            bomb_rewards: DEFAULT_BOMB_REWARDS,
+            shield_rewards: DEFAULT_SHIELD_REWARDS,
+            emission_schedule: EmissionSchedule::default(),
+            emission_window: 0,
+            emitted_in_window: 0,
+            roles: PlaceRoles::default(),
+            pending_owner: Pubkey::default(),
+            purchase_phases: PurchasePhases::default(),
+            season: 0,
+            patches_to_reset: 0,
+            paused: if v0.is_frozen { PauseFlags::FROZEN } else { DEFAULT_PAUSED },
+            reserved: [0; PLACE_STATE_RESERVED_LEN],
+        }
+    }
+
+    pub fn pda(canvas_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                Self::PREFIX.as_bytes(),
+                &canvas_id.to_le_bytes(),
+            ],
+            &crate::id(),
+        )
+    }
+
+    pub fn pda_for_instance(&self) -> (Pubkey, u8) {
+        Self::pda(self.canvas_id)
+    }
+
+    /// Where the version 0 place state lives, before canvas ids were part of the seeds
+    pub fn legacy_pda() -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::PREFIX.as_bytes()], &crate::id())
+    }
+
+    pub fn token_mint_pda(canvas_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                Self::PREFIX.as_bytes(),
+                &canvas_id.to_le_bytes(),
+                Self::TOKEN_MINT_PREFIX.as_bytes(),
+            ],
+            &crate::id(),
+        )
+    }
+
+    /// System owned account holding the proceeds of gameplay token sales
+    pub fn treasury_pda(canvas_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                Self::PREFIX.as_bytes(),
+                &canvas_id.to_le_bytes(),
+                Self::TREASURY_PREFIX.as_bytes(),
+            ],
+            &crate::id(),
+        )
+    }
+
+    /// Place token account of the treasury, receives place token payments that aren't burned
+    pub fn treasury_place_token_ata(canvas_id: u64) -> Pubkey {
+        let (treasury_pda, _) = Self::treasury_pda(canvas_id);
+        let (token_mint_pda, _) = Self::token_mint_pda(canvas_id);
+        spl_associated_token_account::get_associated_token_address(&treasury_pda, &token_mint_pda)
+    }
+
+    pub fn token_mint_mpl_metadata_pda(canvas_id: u64) -> (Pubkey, u8) {
+        let (token_mint_pda, _) = Self::token_mint_pda(canvas_id);
+        mpl_token_metadata::pda::find_metadata_account(&token_mint_pda)
+    }
+
+    /// True if PauseFlags::FROZEN is set
+    pub fn is_frozen(&self) -> bool {
+        self.paused.contains(PauseFlags::FROZEN)
+    }
+
+    /// Dimensions must divide evenly into patches, and patch coordinates must fit in a u8
+    pub fn dimensions_are_valid(width_px: u16, height_px: u16, patch_size_px: u8) -> bool {
+        if patch_size_px == 0 || patch_size_px > MAX_PATCH_SIZE_PX || width_px == 0 || height_px == 0 {
+            return false;
+        }
+
+        let patch_size_px = patch_size_px as u16;
+        width_px % patch_size_px == 0
+            && height_px % patch_size_px == 0
+            && width_px / patch_size_px <= 256
+            && height_px / patch_size_px <= 256
+    }
+
+    pub fn patch_size(&self) -> usize {
+        self.patch_size_px as usize
+    }
+
+    pub fn pixels_per_patch(&self) -> usize {
+        self.patch_size() * self.patch_size()
+    }
+
+    /// Number of bytes needed to hold the pixels of a single patch
+    pub fn pixel_bytes_per_patch(&self) -> usize {
+        self.pixels_per_patch() * self.pixel_format.bytes_per_pixel()
+    }
+
+    /// Number of patches in a row of the place
+    pub fn patches_wide(&self) -> usize {
+        self.width_px as usize / self.patch_size()
+    }
+
+    /// Number of patches in a column of the place
+    pub fn patches_high(&self) -> usize {
+        self.height_px as usize / self.patch_size()
+    }
+
+    pub fn is_valid_patch(&self, x: u8, y: u8) -> bool {
+        (x as usize) < self.patches_wide() && (y as usize) < self.patches_high()
+    }
+
+    pub fn is_valid_offset(&self, x_offset: u8, y_offset: u8) -> bool {
+        (x_offset as usize) < self.patch_size() && (y_offset as usize) < self.patch_size()
+    }
+
+    /// Total number of patches in the place
+    pub fn patch_count(&self) -> u32 {
+        (self.patches_wide() * self.patches_high()) as u32
+    }
+
+    /// Index of the next patch ResetPatches blanks, in row major order
+    pub fn next_patch_to_reset(&self) -> u32 {
+        self.patch_count() - self.patches_to_reset
+    }
+
+    /// Patch coordinates for the patch at the given index in row major order
+    pub fn patch_coords(&self, index: u32) -> (u8, u8) {
+        let index = index as usize;
+        ((index % self.patches_wide()) as u8, (index / self.patches_wide()) as u8)
+    }
+
+    /// Price before the price curve is applied, as set through UpdatePlaceState
+    pub fn base_price(&self, token_type: GameplayTokenType) -> u64 {
+        match token_type {
+            GameplayTokenType::PaintBrush => self.paintbrush_price,
+            GameplayTokenType::Bomb => self.bomb_price,
+            GameplayTokenType::Shield => self.shield_price,
+        }
+    }
+
+    /// Cooldown given to new gameplay tokens of the type
+    pub fn cooldown(&self, token_type: GameplayTokenType) -> Slot {
+        match token_type {
+            GameplayTokenType::PaintBrush => self.paintbrush_cooldown,
+            GameplayTokenType::Bomb => self.bomb_cooldown,
+            GameplayTokenType::Shield => self.shield_cooldown,
+        }
+    }
+
+    pub fn rewards(&self, token_type: GameplayTokenType) -> &GameplayTokenRewards {
+        match token_type {
+            GameplayTokenType::PaintBrush => &self.paintbrush_rewards,
+            GameplayTokenType::Bomb => &self.bomb_rewards,
+            GameplayTokenType::Shield => &self.shield_rewards,
+        }
+    }
+
+    pub fn sales(&self, token_type: GameplayTokenType) -> &GameplayTokenSales {
+        match token_type {
+            GameplayTokenType::PaintBrush => &self.paintbrush_sales,
+            GameplayTokenType::Bomb => &self.bomb_sales,
+            GameplayTokenType::Shield => &self.shield_sales,
+        }
+    }
+
+    pub fn sales_mut(&mut self, token_type: GameplayTokenType) -> &mut GameplayTokenSales {
+        match token_type {
+            GameplayTokenType::PaintBrush => &mut self.paintbrush_sales,
+            GameplayTokenType::Bomb => &mut self.bomb_sales,
+            GameplayTokenType::Shield => &mut self.shield_sales,
+        }
+    }
+
+    /// Price of a gameplay token bought at the given slot, this is what PurchaseGameplayToken
+    /// charges and what clients should pass as the desired price
+    pub fn current_price(&self, token_type: GameplayTokenType, slot: Slot) -> u64 {
+        let recent_sales = self.price_curve.decayed_sales(self.sales(token_type), slot);
+        self.price_curve.price(self.base_price(token_type), recent_sales)
+    }
+
+    /// Lamports refunded from the treasury for retiring a gameplay token bought at the given price
+    pub fn retire_refund(&self, purchase_price: u64) -> u64 {
+        (purchase_price as u128 * self.retire_refund_bps as u128 / 10_000) as u64
+    }
+
+    /// Place tokens claims can still mint at the given slot, given the current supply of the mint
+    pub fn place_token_budget(&self, supply: u64, slot: Slot) -> u64 {
+        let mut budget = u64::MAX;
+
+        let schedule = &self.emission_schedule;
+        if schedule.max_supply > 0 {
+            budget = schedule.max_supply.saturating_sub(supply);
+        }
+
+        if schedule.window_slots > 0 {
+            let emitted = if slot / schedule.window_slots == self.emission_window {
+                self.emitted_in_window
+            } else {
+                0
+            };
+            budget = budget.min(schedule.tokens_per_window.saturating_sub(emitted));
+        }
+
+        budget
+    }
+
+    /// Counts place tokens minted by a claim at the given slot against the emission window
+    pub fn record_emission(&mut self, amount: u64, slot: Slot) {
+        if self.emission_schedule.window_slots == 0 {
+            return;
+        }
+
+        let window = slot / self.emission_schedule.window_slots;
+        if window != self.emission_window {
+            self.emission_window = window;
+            self.emitted_in_window = 0;
+        }
+        self.emitted_in_window = self.emitted_in_window.saturating_add(amount);
+    }
+
+    /// Whether the key holds the role, the owner holds every role
+    pub fn has_role(&self, role: PlaceRole, key: &Pubkey) -> bool {
+        if self.owner == *key {
+            return true;
+        }
+
+        match role {
+            PlaceRole::SuperAdmin => false,
+            _ => self.roles.holder(role) == Some(*key),
+        }
+    }
+
+    /// Counts a sale at the given slot towards future prices of the token type
+    pub fn record_sale(&mut self, token_type: GameplayTokenType, slot: Slot) {
+        let recent_sales = self.price_curve.decayed_sales(self.sales(token_type), slot);
+        let sales = self.sales_mut(token_type);
+        sales.recent_sales = recent_sales.saturating_add(1);
+        sales.last_sale_slot = slot;
+    }
+
+    /// Returns the inclusive pixel bounds (x_min, y_min, x_max, y_max) of a bomb blast
+    /// centered on the given pixel, clipped to the edges of the place
+    pub fn bomb_blast_bounds(&self, x: u8, y: u8, x_offset: u8, y_offset: u8) -> (usize, usize, usize, usize) {
+        let center_x = (x as usize * self.patch_size()) + x_offset as usize;
+        let center_y = (y as usize * self.patch_size()) + y_offset as usize;
+
+        let x_min = center_x.saturating_sub(BOMB_BLAST_RADIUS_PX);
+        let y_min = center_y.saturating_sub(BOMB_BLAST_RADIUS_PX);
+        let x_max = (center_x + BOMB_BLAST_RADIUS_PX).min(self.width_px as usize - 1);
+        let y_max = (center_y + BOMB_BLAST_RADIUS_PX).min(self.height_px as usize - 1);
+
+        (x_min, y_min, x_max, y_max)
+    }
+
+    /// A region is an inclusive rectangle of patches
+    pub fn is_valid_region(&self, x_min: u8, y_min: u8, x_max: u8, y_max: u8) -> bool {
+        x_min <= x_max && y_min <= y_max && self.is_valid_patch(x_max, y_max)
+    }
+
+    /// Patch coordinates inside a region, in row major order.
+    /// This is also the order the patch accounts must be passed to MintRegionNft
+    pub fn region_patches(&self, x_min: u8, y_min: u8, x_max: u8, y_max: u8) -> Vec<(u8, u8)> {
+        let mut patches = vec![];
+        for patch_y in y_min..=y_max {
+            for patch_x in x_min..=x_max {
+                patches.push((patch_x, patch_y));
+            }
+        }
+
+        patches
+    }
+
+    /// Returns the inclusive offsets (x_min, y_min, x_max, y_max) protected by a shield
+    /// centered on the given offset, clipped to the edges of the patch
+    pub fn shield_bounds(&self, x_offset: u8, y_offset: u8) -> (u8, u8, u8, u8) {
+        let max_offset = (self.patch_size() - 1) as u8;
+        (
+            x_offset.saturating_sub(SHIELD_RADIUS_PX),
+            y_offset.saturating_sub(SHIELD_RADIUS_PX),
+            x_offset.saturating_add(SHIELD_RADIUS_PX).min(max_offset),
+            y_offset.saturating_add(SHIELD_RADIUS_PX).min(max_offset),
+        )
+    }
+
+    /// Patch coordinates touched by a bomb blast, in row major order.
+    /// This is also the order the patch accounts must be passed to DetonateBomb
+    pub fn bomb_blast_patches(&self, x: u8, y: u8, x_offset: u8, y_offset: u8) -> Vec<(u8, u8)> {
+        let (x_min, y_min, x_max, y_max) = self.bomb_blast_bounds(x, y, x_offset, y_offset);
+        self.patches_in_rect(x_min, y_min, x_max, y_max)
+    }
+
+    /// A rectangle of pixels is inclusive bounds inside the place
+    pub fn is_valid_rect(&self, x_min: u16, y_min: u16, x_max: u16, y_max: u16) -> bool {
+        x_min <= x_max && y_min <= y_max && x_max < self.width_px && y_max < self.height_px
+    }
+
+    /// Patch coordinates touched by an inclusive rectangle of pixels, in row major order.
+    /// This is also the order the patch accounts must be passed to ClearRegion
+    pub fn patches_in_rect(&self, x_min: usize, y_min: usize, x_max: usize, y_max: usize) -> Vec<(u8, u8)> {
+        let patch_size = self.patch_size();
+
+        let mut patches = vec![];
+        for patch_y in (y_min / patch_size)..=(y_max / patch_size) {
+            for patch_x in (x_min / patch_size)..=(x_max / patch_size) {
+                patches.push((patch_x as u8, patch_y as u8));
+            }
+        }
+
+        patches
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy)]
+pub enum PriceCurveKind {
+    // the price is always the base price
+    Flat,
+    // every recent sale adds `step` lamports to the base price
+    Linear,
+    // every recent sale raises the price by `step` basis points, compounding
+    Exponential,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy)]
+pub struct PriceCurve {
+    pub kind: PriceCurveKind,
+
+    // lamports for Linear curves, basis points for Exponential curves
+    pub step: u64,
+
+    // number of slots without a sale for one recent sale to be forgotten, 0 never forgets
+    pub decay_slots: Slot,
+
+    // upper bound on the price in lamports, 0 means no bound
+    pub max_price: u64,
+}
+
+impl Default for PriceCurve {
+    fn default() -> Self {
+        PriceCurve {
+            kind: PriceCurveKind::Flat,
+            step: 0,
+            decay_slots: 0,
+            max_price: 0,
+        }
+    }
+}
+
+impl PriceCurve {
+    pub const LEN: usize = 0 +
+        1 + // kind
+        8 + // step
+        8 + // decay_slots
+        8; // max_price
+
+    /// Recent sales left after the slots since the last sale have been decayed away
+    pub fn decayed_sales(&self, sales: &GameplayTokenSales, slot: Slot) -> u64 {
+        if self.decay_slots == 0 {
+            return sales.recent_sales;
+        }
+
+        let elapsed_slots = slot.saturating_sub(sales.last_sale_slot);
+        sales.recent_sales.saturating_sub(elapsed_slots / self.decay_slots)
+    }
+
+    /// Price of the next sale given the base price and number of recent sales, saturates
+    /// instead of overflowing
+    pub fn price(&self, base_price: u64, recent_sales: u64) -> u64 {
+        let price = match self.kind {
+            PriceCurveKind::Flat => base_price,
+            PriceCurveKind::Linear => base_price.saturating_add(self.step.saturating_mul(recent_sales)),
+            PriceCurveKind::Exponential => {
+                // keeps both operands of every multiplication below 2^63 so the u128 can't overflow
+                let max_factor: u128 = 1 << 63;
+                let mut factor = PRICE_FACTOR_SCALE;
+                let mut step_factor = (PRICE_FACTOR_SCALE
+                    + (self.step as u128 * PRICE_FACTOR_SCALE / 10_000))
+                    .min(max_factor);
+                let mut exponent = recent_sales;
+                while exponent > 0 {
+                    if exponent & 1 == 1 {
+                        factor = (factor * step_factor / PRICE_FACTOR_SCALE).min(max_factor);
+                    }
+                    step_factor = (step_factor * step_factor / PRICE_FACTOR_SCALE).min(max_factor);
+                    exponent >>= 1;
+                }
+
+                (base_price as u128 * factor / PRICE_FACTOR_SCALE).min(u64::MAX as u128) as u64
+            }
+        };
+
+        if self.max_price > 0 {
+            price.min(self.max_price)
+        } else {
+            price
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy)]
+pub struct UpgradeSchedule {
+    // place tokens burned for the first upgrade, every later level costs this times the level
+    pub base_cost: u64,
+
+    // slots taken off the cooldown of a gameplay token by each upgrade
+    pub cooldown_step: Slot,
+
+    // upgrades never lower a cooldown below this many slots
+    pub min_cooldown: Slot,
+
+    // number of times a single gameplay token can be upgraded
+    pub max_level: u8,
+}
+
+impl Default for UpgradeSchedule {
+    fn default() -> Self {
+        UpgradeSchedule {
+            base_cost: DEFAULT_UPGRADE_BASE_COST,
+            cooldown_step: DEFAULT_UPGRADE_COOLDOWN_STEP,
+            min_cooldown: DEFAULT_MIN_COOLDOWN,
+            max_level: DEFAULT_MAX_UPGRADE_LEVEL,
+        }
+    }
+}
+
+impl UpgradeSchedule {
+    pub const LEN: usize = 0 +
+        8 + // base_cost
+        8 + // cooldown_step
+        8 + // min_cooldown
+        1; // max_level
+
+    /// Place tokens burned to reach the given level
+    pub fn cost(&self, level: u8) -> u64 {
+        self.base_cost.saturating_mul(level as u64)
+    }
+
+    /// Cooldown after one more upgrade, never below the minimum
+    pub fn upgraded_cooldown(&self, cooldown: Slot) -> Slot {
+        cooldown.saturating_sub(self.cooldown_step).max(self.min_cooldown)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy)]
+pub struct PlaceTokenPricing {
+    // place tokens for a gameplay token of type Paintbrush, 0 can't be bought with place tokens
+    pub paintbrush_price: u64,
+
+    // place tokens for a gameplay token of type Bomb, 0 can't be bought with place tokens
+    pub bomb_price: u64,
+
+    // place tokens for a gameplay token of type Shield, 0 can't be bought with place tokens
+    pub shield_price: u64,
+
+    // burn the payment, otherwise it goes to the treasury's place token account
+    pub burn_payments: bool,
+}
+
+impl Default for PlaceTokenPricing {
+    fn default() -> Self {
+        PlaceTokenPricing {
+            paintbrush_price: 0,
+            bomb_price: 0,
+            shield_price: 0,
+            burn_payments: true,
+        }
+    }
+}
+
+impl PlaceTokenPricing {
+    pub const LEN: usize = 0 +
+        8 + // paintbrush_price
+        8 + // bomb_price
+        8 + // shield_price
+        1; // burn_payments
+
+    pub fn price(&self, token_type: GameplayTokenType) -> u64 {
+        match token_type {
+            GameplayTokenType::PaintBrush => self.paintbrush_price,
+            GameplayTokenType::Bomb => self.bomb_price,
+            GameplayTokenType::Shield => self.shield_price,
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy, Default)]
+pub struct EmissionSchedule {
+    // the place token supply never goes above this, 0 means no cap
+    pub max_supply: u64,
+
+    // length in slots of an emission window, 0 means no limit per window
+    pub window_slots: Slot,
+
+    // place tokens claims can mint during a single emission window
+    pub tokens_per_window: u64,
+}
+
+impl EmissionSchedule {
+    pub const LEN: usize = 0 +
+        8 + // max_supply
+        8 + // window_slots
+        8; // tokens_per_window
+}
+
+/// Purchases can be limited to an allowlist of wallets for a while before they open to everyone
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy, Default)]
+pub struct PurchasePhases {
+    // root of the allowlist merkle tree, see allowlist::AllowlistTree. All zeroes means
+    // there are no phases and purchases are always open
+    pub allowlist_root: [u8; 32],
+
+    // first slot allowlisted wallets can purchase, nobody can purchase before it
+    pub allowlist_start_slot: Slot,
+
+    // first slot purchases are open to everyone
+    pub allowlist_end_slot: Slot,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum PurchasePhase {
+    Closed,
+    Allowlist,
+    Public,
+}
+
+impl PurchasePhases {
+    pub const LEN: usize = 0 +
+        32 + // allowlist_root
+        8 + // allowlist_start_slot
+        8; // allowlist_end_slot
+
+    pub fn phase(&self, slot: Slot) -> PurchasePhase {
+        if self.allowlist_root == [0; 32] || slot >= self.allowlist_end_slot {
+            PurchasePhase::Public
+        } else if slot >= self.allowlist_start_slot {
+            PurchasePhase::Allowlist
+        } else {
+            PurchasePhase::Closed
+        }
+    }
+}
+
+/// Set of operations that are paused, each bit stops one kind of instruction
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy, Default)]
+pub struct PauseFlags(pub u8);
+
+impl PauseFlags {
+    pub const LEN: usize = 1;
+
+    pub const NONE: PauseFlags = PauseFlags(0);
+    // PurchaseGameplayToken
+    pub const PURCHASES: PauseFlags = PauseFlags(1 << 0);
+    // SetPixel, SetPixels and DetonateBomb
+    pub const PAINTING: PauseFlags = PauseFlags(1 << 1);
+    // ClaimTokens and ClaimTokensBatch
+    pub const CLAIMS: PauseFlags = PauseFlags(1 << 2);
+    // InitPatch
+    pub const PATCH_CREATION: PauseFlags = PauseFlags(1 << 3);
+    // every instruction that changes the canvas or adds to what gameplay tokens are owed, so
+    // the canvas can be sealed. Claims stay open, owed place tokens reset with a new season.
+    // Moderators can still ClearRegion a frozen canvas until sealing starts.
+    // Can't be cleared while a new season's patches are being reset
+    pub const FROZEN: PauseFlags = PauseFlags(1 << 4);
+    pub const ALL: PauseFlags = PauseFlags(0b1_1111);
+
+    /// True if every operation in `flags` is paused
+    pub fn contains(&self, flags: PauseFlags) -> bool {
+        self.0 & flags.0 == flags.0
+    }
+
+    /// False if any bit doesn't stand for an operation
+    pub fn is_valid(&self) -> bool {
+        self.0 & !Self::ALL.0 == 0
+    }
+}
+
+impl std::ops::BitOr for PauseFlags {
+    type Output = PauseFlags;
+
+    fn bitor(self, rhs: PauseFlags) -> PauseFlags {
+        PauseFlags(self.0 | rhs.0)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy)]
+pub enum PlaceRole {
+    // the place owner, can do everything and hands out the other roles
+    SuperAdmin,
+
+    // updates prices, cooldowns, rewards and the emission schedule
+    PricingAdmin,
+
+    // freezes and unfreezes the canvas, seals it, and pauses operations
+    FreezeAdmin,
+
+    // moderates what is painted on the canvas
+    Moderator,
+
+    // withdraws from the treasury
+    Treasurer,
+}
+
+/// Keys holding each of the grantable roles, the default pubkey means nobody holds it
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy, Default)]
+pub struct PlaceRoles {
+    pub pricing_admin: Pubkey,
+    pub freeze_admin: Pubkey,
+    pub moderator: Pubkey,
+    pub treasurer: Pubkey,
+}
+
+impl PlaceRoles {
+    pub const LEN: usize = 0 +
+        32 + // pricing_admin
+        32 + // freeze_admin
+        32 + // moderator
+        32; // treasurer
+
+    /// The key holding a grantable role, None if nobody holds it or the role isn't grantable
+    pub fn holder(&self, role: PlaceRole) -> Option<Pubkey> {
+        let holder = match role {
+            PlaceRole::SuperAdmin => return None,
+            PlaceRole::PricingAdmin => self.pricing_admin,
+            PlaceRole::FreezeAdmin => self.freeze_admin,
+            PlaceRole::Moderator => self.moderator,
+            PlaceRole::Treasurer => self.treasurer,
+        };
+
+        if holder == Pubkey::default() {
+            None
+        } else {
+            Some(holder)
+        }
+    }
+
+    /// Mutable access to the key holding a grantable role, None for the super-admin
+    pub fn holder_mut(&mut self, role: PlaceRole) -> Option<&mut Pubkey> {
+        match role {
+            PlaceRole::SuperAdmin => None,
+            PlaceRole::PricingAdmin => Some(&mut self.pricing_admin),
+            PlaceRole::FreezeAdmin => Some(&mut self.freeze_admin),
+            PlaceRole::Moderator => Some(&mut self.moderator),
+            PlaceRole::Treasurer => Some(&mut self.treasurer),
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy)]
+pub struct GameplayTokenRewards {
+    // place tokens owed to a gameplay token as soon as it is purchased
+    pub purchase_bonus: u32,
+
+    // place tokens owed for every use, SetPixels and bombs earn it once per pixel or blast
+    pub use_reward: u32,
+}
+
+impl GameplayTokenRewards {
+    pub const LEN: usize = 0 +
+        4 + // purchase_bonus
+        4; // use_reward
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy, Default)]
+pub struct GameplayTokenSales {
+    // sales still counted by the price curve, decays as slots pass without a sale
+    pub recent_sales: u64,
+
+    // slot of the most recent sale
+    pub last_sale_slot: Slot,
+}
+
+impl GameplayTokenSales {
+    pub const LEN: usize = 0 +
+        8 + // recent_sales
+        8; // last_sale_slot
+}
+
+//////////////////////////////////////////////////////////////////////////////////
+////////////////////////////// CANVAS SNAPSHOT ///////////////////////////////////
+
+/// Seals the pixels of a frozen canvas with a hash chained over every patch in row major order.
+/// Sealing takes many transactions, so progress is tracked by `next_patch`
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct CanvasSnapshot {
+    pub acct_type: PlaceAccountType,
+
+    // the PlaceState::frozen_at_slot this snapshot was taken for
+    pub frozen_at_slot: Slot,
+
+    // index of the next patch to be chained into the hash, in row major order
+    pub next_patch: u32,
+
+    // hash chained over all patches up to next_patch
+    pub hash: [u8; 32],
+
+    // true once every patch has been chained into the hash
+    pub is_sealed: bool,
+}
+
+impl CanvasSnapshot {
+    pub const PREFIX: &'static str = "snapshot";
+
+    pub const LEN: usize = 0 +
+        1 + // acct_type
+        8 + // frozen_at_slot
+        4 + // next_patch
+        32 + // hash
+        1; // is_sealed
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<CanvasSnapshot, ProgramError> {
+        let snapshot: CanvasSnapshot =
+            try_from_slice_checked(&a.data.borrow_mut(), PlaceAccountType::CanvasSnapshot, Self::LEN)?;
+        Ok(snapshot)
+    }
+
+    pub fn from_bytes(b: &[u8]) -> Result<CanvasSnapshot, ProgramError> {
+        let snapshot: CanvasSnapshot =
+            try_from_slice_checked(b, PlaceAccountType::CanvasSnapshot, Self::LEN)?;
+        Ok(snapshot)
+    }
+
+    pub fn pda(canvas_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PlaceState::PREFIX.as_bytes(),
+                &canvas_id.to_le_bytes(),
+                Self::PREFIX.as_bytes(),
+            ],
+            &crate::id(),
+        )
+    }
+
+    /// Chain a patch onto the previous hash, patches which were never initialized
+    /// should be chained as all zero pixels
+    pub fn chain_patch_hash(prev: &[u8; 32], x: u8, y: u8, pixels: &[u8]) -> [u8; 32] {
+        hashv(&[&prev[..], &[x, y], pixels]).to_bytes()
+    }
+}
 
+//////////////////////////////////////////////////////////////////////////////////
+///////////////////////////// SEASON ARCHIVE /////////////////////////////////////
+
+/// Records the sealed artwork of a finished season, created by StartNewSeason
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
-pub struct PlaceState {
+pub struct SeasonArchive {
     pub acct_type: PlaceAccountType,
 
-    // The owner of the tapestry, unsure if there is a better way to handle this
-    pub owner: Pubkey,
+    // canvas the season was played on
+    pub canvas_id: u64,
 
-    // if the tapestry state has been frozen for NFT creation and auction
-    pub is_frozen: bool,
+    // the season that finished
+    pub season: u32,
 
-    // Current price of a gameplay token of type Paintbrush
-    pub paintbrush_price: u64,
+    // the PlaceState::frozen_at_slot the season was sealed at
+    pub frozen_at_slot: Slot,
 
-    // number of slots for the cooldown for new paintbrushes
-    pub paintbrush_cooldown: Slot,
+    // the CanvasSnapshot::hash of the finished canvas
+    pub snapshot_hash: [u8; 32],
 
-    // current price of a gameplay token of type Bomb
-    pub bomb_price: u64,
+    // slot the next season started at
+    pub archived_at_slot: Slot,
 }
 
-impl PlaceState {
+impl SeasonArchive {
+    pub const PREFIX: &'static str = "season";
+
     pub const LEN: usize = 0 +
         1 + // acct_type
-        32 + // owner
-        1 + // is_frozen
-        8 + // paintbrush_price
-        8 + // paintbrush_cooldown
-        8; // bomb_price
+        8 + // canvas_id
+        4 + // season
+        8 + // frozen_at_slot
+        32 + // snapshot_hash
+        8; // archived_at_slot
 
-    pub const PREFIX: &'static str = "place";
-    pub const TOKEN_MINT_PREFIX: &'static str = "tokes";
+    pub fn from_account_info(a: &AccountInfo) -> Result<SeasonArchive, ProgramError> {
+        Self::from_bytes(&a.data.borrow())
+    }
 
-    pub fn from_account_info(a: &AccountInfo) -> Result<PlaceState, ProgramError> {
-        let state: PlaceState =
-            try_from_slice_checked(&a.data.borrow_mut(), PlaceAccountType::PlaceState, Self::LEN)?;
+    pub fn from_bytes(b: &[u8]) -> Result<SeasonArchive, ProgramError> {
+        let archive: SeasonArchive = try_from_slice_checked(b, PlaceAccountType::SeasonArchive, Self::LEN)?;
+        Ok(archive)
+    }
 
-        Ok(state)
+    pub fn pda(canvas_id: u64, season: u32) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PlaceState::PREFIX.as_bytes(),
+                &canvas_id.to_le_bytes(),
+                Self::PREFIX.as_bytes(),
+                &season.to_le_bytes(),
+            ],
+            &crate::id(),
+        )
     }
+}
 
-    pub fn from_bytes(b: &[u8]) -> Result<PlaceState, ProgramError> {
-        let state: PlaceState = try_from_slice_checked(b, PlaceAccountType::PlaceState, Self::LEN)?;
-        Ok(state)
+//////////////////////////////////////////////////////////////////////////////////
+////////////////////////////// REGION NFT ////////////////////////////////////////
+
+/// Records a rectangle of patches from a frozen canvas that was minted as an NFT
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct RegionNftMeta {
+    pub acct_type: PlaceAccountType,
+
+    // canvas the region was taken from
+    pub canvas_id: u64,
+
+    // the PlaceState::frozen_at_slot of the freeze the region was minted during
+    pub frozen_at_slot: Slot,
+
+    // inclusive bounds of the region in patch coordinates
+    pub x_min: u8,
+    pub y_min: u8,
+    pub x_max: u8,
+    pub y_max: u8,
+
+    // pixels of the region's patches chained with CanvasSnapshot::chain_patch_hash in row major order
+    pub pixel_hash: [u8; 32],
+
+    // the token mint of the region NFT
+    pub token_mint_pda: Pubkey,
+}
+
+impl RegionNftMeta {
+    pub const PREFIX: &'static str = "region";
+    pub const MINT_PREFIX: &'static str = "mint";
+
+    pub const LEN: usize = 0 +
+        1 + // acct_type
+        8 + // canvas_id
+        8 + // frozen_at_slot
+        4 + // x_min, y_min, x_max, y_max
+        32 + // pixel_hash
+        32; // token_mint_pda
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<RegionNftMeta, ProgramError> {
+        let meta: RegionNftMeta =
+            try_from_slice_checked(&a.data.borrow_mut(), PlaceAccountType::RegionNftMeta, Self::LEN)?;
+        Ok(meta)
+    }
+
+    pub fn from_bytes(b: &[u8]) -> Result<RegionNftMeta, ProgramError> {
+        let meta: RegionNftMeta = try_from_slice_checked(b, PlaceAccountType::RegionNftMeta, Self::LEN)?;
+        Ok(meta)
     }
 
-    pub fn pda() -> (Pubkey, u8) {
+    /// A region can only be minted once per freeze of the canvas
+    pub fn pda(canvas_id: u64, frozen_at_slot: Slot, x_min: u8, y_min: u8, x_max: u8, y_max: u8) -> (Pubkey, u8) {
         Pubkey::find_program_address(
-            &[Self::PREFIX.as_bytes()],
+            &[
+                Self::PREFIX.as_bytes(),
+                &canvas_id.to_le_bytes(),
+                &frozen_at_slot.to_le_bytes(),
+                &[x_min, y_min, x_max, y_max],
+            ],
             &crate::id(),
         )
     }
 
-    pub fn token_mint_pda() -> (Pubkey, u8) {
+    pub fn pda_for_instance(&self) -> (Pubkey, u8) {
+        Self::pda(self.canvas_id, self.frozen_at_slot, self.x_min, self.y_min, self.x_max, self.y_max)
+    }
+
+    pub fn token_mint_pda(canvas_id: u64, frozen_at_slot: Slot, x_min: u8, y_min: u8, x_max: u8, y_max: u8) -> (Pubkey, u8) {
         Pubkey::find_program_address(
             &[
                 Self::PREFIX.as_bytes(),
-                Self::TOKEN_MINT_PREFIX.as_bytes(),
+                &canvas_id.to_le_bytes(),
+                &frozen_at_slot.to_le_bytes(),
+                &[x_min, y_min, x_max, y_max],
+                Self::MINT_PREFIX.as_bytes(),
             ],
             &crate::id(),
         )
     }
 
-    pub fn token_mint_mpl_metadata_pda() -> (Pubkey, u8) {
-        let (token_mint_pda, _) = Self::token_mint_pda();
-        mpl_token_metadata::pda::find_metadata_account(&token_mint_pda)
+    pub fn token_metadata_pda(canvas_id: u64, frozen_at_slot: Slot, x_min: u8, y_min: u8, x_max: u8, y_max: u8) -> (Pubkey, u8) {
+        let (mint_pda, _) = Self::token_mint_pda(canvas_id, frozen_at_slot, x_min, y_min, x_max, y_max);
+        mpl_token_metadata::pda::find_metadata_account(&mint_pda)
+    }
+
+    /// Name of the NFT, must fit in the 32 bytes metaplex allows
+    pub fn token_name(&self) -> String {
+        format!("Tapestry ({},{})-({},{})", self.x_min, self.y_min, self.x_max, self.y_max)
+    }
+
+    /// URI of the NFT metadata json, records the bounds and pixel hash of the region
+    pub fn token_uri(&self) -> String {
+        format!(
+            "{}/region/{}/{}/{}/{}/{}/{}/{}.json",
+            crate::config::BASE_URL,
+            self.canvas_id,
+            self.frozen_at_slot,
+            self.x_min,
+            self.y_min,
+            self.x_max,
+            self.y_max,
+            Hash::new_from_array(self.pixel_hash),
+        )
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////
+///////////////////////////// ALLOWLIST RECEIPT //////////////////////////////////
+
+/// Counts the gameplay tokens a wallet has bought during the allowlist phase
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct AllowlistReceipt {
+    pub acct_type: PlaceAccountType,
+
+    // the canvas the purchases were made on
+    pub canvas_id: u64,
+
+    // the wallet that made the purchases
+    pub wallet: Pubkey,
+
+    // the allowlist the purchases count against, the count restarts when the root changes
+    pub allowlist_root: [u8; 32],
+
+    // gameplay tokens bought during the allowlist phase
+    pub purchases: u16,
+}
+
+impl AllowlistReceipt {
+    pub const PREFIX: &'static str = "allowlist";
+
+    pub const LEN: usize = 0 +
+        1 + // acct_type
+        8 + // canvas_id
+        32 + // wallet
+        32 + // allowlist_root
+        2; // purchases
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<AllowlistReceipt, ProgramError> {
+        Self::from_bytes(&a.data.borrow())
+    }
+
+    pub fn from_bytes(b: &[u8]) -> Result<AllowlistReceipt, ProgramError> {
+        let receipt: AllowlistReceipt = try_from_slice_checked(b, PlaceAccountType::AllowlistReceipt, Self::LEN)?;
+        Ok(receipt)
+    }
+
+    pub fn pda(canvas_id: u64, wallet: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                Self::PREFIX.as_bytes(),
+                &canvas_id.to_le_bytes(),
+                wallet.as_ref(),
+            ],
+            &crate::id(),
+        )
     }
 }
 
@@ -112,6 +1270,7 @@ impl PlaceState {
 pub enum GameplayTokenType {
     PaintBrush,
     Bomb,
+    Shield,
 }
 
 /// Holds the metadata and relevant state for a gameplay token
@@ -119,6 +1278,12 @@ pub enum GameplayTokenType {
 pub struct GameplayTokenMeta {
     pub acct_type: PlaceAccountType,
 
+    // layout version of this account, see CURRENT_ACCOUNT_VERSION
+    pub version: u8,
+
+    // the canvas this token was purchased for, it can only paint and claim on that canvas
+    pub canvas_id: u64,
+
     pub gameplay_type: GameplayTokenType,
 
     // I think this should be the slot rounded to some reasonable number
@@ -142,6 +1307,47 @@ pub struct GameplayTokenMeta {
     // within the SetPixel instruction, we increment this number instead.
     // user's can later claim their tokens in a separate transaction.
     pub place_tokens_owed: u32,
+
+    // lamports paid for this token, the retire refund is a share of it.
+    // 0 for tokens bought with place tokens
+    pub purchase_price: u64,
+
+    // number of times place tokens have been spent to lower the cooldown
+    pub upgrade_level: u8,
+
+    // set by a moderator, banned tokens can't paint or claim place tokens
+    pub is_banned: bool,
+
+    // season place_tokens_owed was earned in, reset when the token is used in a later season
+    pub season: u32,
+
+    // room for new fields without reallocating, always zero until a new version uses it
+    pub reserved: [u8; GAMEPLAY_TOKEN_META_RESERVED_LEN],
+}
+
+/// The gameplay token meta as it was first deployed, only read to upgrade it
+#[derive(BorshDeserialize, BorshSerialize, PartialEq, Debug, Clone)]
+struct GameplayTokenMetaV0 {
+    acct_type: PlaceAccountType,
+    gameplay_type: GameplayTokenType,
+    created_at_slot: Slot,
+    random_seed: u64,
+    token_mint_pda: Pubkey,
+    update_allowed_slot: Slot,
+    cooldown_duration: Slot,
+    place_tokens_owed: u32,
+}
+
+impl GameplayTokenMetaV0 {
+    const LEN: usize = 0 +
+        1 + // acct_type
+        1 + // gameplay_type
+        8 + // created_at_slot
+        8 + // random_seed
+        32 + // token_mint_pda
+        8 + // update_allowed_slot
+        8 + // cooldown_duration
+        4; // place_tokens_owed
 }
 
 impl GameplayTokenMeta {
@@ -150,26 +1356,63 @@ impl GameplayTokenMeta {
 
     pub const LEN: usize = 0 + 
         1 + // acct_type
+        1 + // version
+        8 + // canvas_id
         1 + // gameplay_type
         8 + // created_at_slot
         8 + // random_seed
         32 + // token_mint_pda
         8 +  // update_allowed_after
         8 +  // cooldown_duration
-        4; // place_tokens_owed
+        4 + // place_tokens_owed
+        8 + // purchase_price
+        1 + // upgrade_level
+        1 + // is_banned
+        4 + // season
+        GAMEPLAY_TOKEN_META_RESERVED_LEN; // reserved
 
-    pub fn from_account_info(a: &AccountInfo) -> Result<GameplayTokenMeta, ProgramError> {
-        let state: GameplayTokenMeta =
-            try_from_slice_checked(&a.data.borrow_mut(), PlaceAccountType::GameplayTokenMeta, Self::LEN)?;
+    pub const LEN_V0: usize = GameplayTokenMetaV0::LEN;
 
-        Ok(state)
+    pub fn from_account_info(a: &AccountInfo) -> Result<GameplayTokenMeta, ProgramError> {
+        Self::from_bytes(&a.data.borrow())
     }
 
+    /// Reads any layout version, older versions are upgraded to the current one in memory
     pub fn from_bytes(b: &[u8]) -> Result<GameplayTokenMeta, ProgramError> {
+        if b.len() == Self::LEN_V0 {
+            let v0: GameplayTokenMetaV0 = try_from_slice_checked(b, PlaceAccountType::GameplayTokenMeta, Self::LEN_V0)?;
+            return Ok(Self::from_v0(v0));
+        }
+
         let state: GameplayTokenMeta = try_from_slice_checked(b, PlaceAccountType::GameplayTokenMeta, Self::LEN)?;
+        if state.version > CURRENT_ACCOUNT_VERSION {
+            return Err(PlaceError::UnsupportedAccountVersion.into());
+        }
         Ok(state)
     }
 
+    /// Version 0 tokens were all bought with lamports paid into the meta account itself,
+    /// so there is no purchase price for a retire refund to be a share of
+    fn from_v0(v0: GameplayTokenMetaV0) -> GameplayTokenMeta {
+        GameplayTokenMeta {
+            acct_type: PlaceAccountType::GameplayTokenMeta,
+            version: CURRENT_ACCOUNT_VERSION,
+            canvas_id: LEGACY_CANVAS_ID,
+            gameplay_type: v0.gameplay_type,
+            created_at_slot: v0.created_at_slot,
+            random_seed: v0.random_seed,
+            token_mint_pda: v0.token_mint_pda,
+            update_allowed_slot: v0.update_allowed_slot,
+            cooldown_duration: v0.cooldown_duration,
+            place_tokens_owed: v0.place_tokens_owed,
+            purchase_price: 0,
+            upgrade_level: 0,
+            is_banned: false,
+            season: 0,
+            reserved: [0; GAMEPLAY_TOKEN_META_RESERVED_LEN],
+        }
+    }
+
     pub fn pda(random_seed: u64) -> (Pubkey, u8) {
         Pubkey::find_program_address(
             &[
@@ -183,7 +1426,16 @@ impl GameplayTokenMeta {
     pub fn pda_for_instance(&self) -> (Pubkey, u8) {
         return Self::pda(self.random_seed);
     }
-    
+
+    /// Moves the token into the given season, resetting the place tokens it was owed. Claims
+    /// stay open while a finished season is frozen, so they can be claimed before it ends
+    pub fn start_season(&mut self, season: u32) {
+        if self.season != season {
+            self.season = season;
+            self.place_tokens_owed = 0;
+        }
+    }
+
     pub fn token_mint_pda(random_seed: u64) -> (Pubkey, u8) {
         return Pubkey::find_program_address(
             &[
@@ -213,16 +1465,13 @@ impl GameplayTokenMeta {
 //////////////////////////////////////////////////////////////////////////////////
 ///////////////////////////////// PATCH ACCOUNT //////////////////////////////////
 
-// TODO(will): move these into impl to make this more classy
-pub const PLACE_HEIGHT_PX: u16 = 1000;
-pub const PLACE_WIDTH_PX: u16 = 1000;
-pub const PATCH_SIZE_PX: usize = 20;
 pub const PATCH_PDA_PREFIX: &str = "patch";
 
-pub fn find_address_for_patch(x: u8, y: u8, program_id: &Pubkey) -> (Pubkey, u8) {
+pub fn find_address_for_patch(canvas_id: u64, x: u8, y: u8, program_id: &Pubkey) -> (Pubkey, u8) {
     return Pubkey::find_program_address(
         &[
             PATCH_PDA_PREFIX.as_bytes(),
+            &canvas_id.to_le_bytes(),
             &x.to_le_bytes(),
             &y.to_le_bytes(),
         ],
@@ -230,50 +1479,230 @@ pub fn find_address_for_patch(x: u8, y: u8, program_id: &Pubkey) -> (Pubkey, u8)
     );
 }
 
-/// Length of this
-///
-pub const PATCH_DATA_LEN: usize = 0 
-    + 1 // acct_type
-    + 1 // x
-    + 1 // y
-    + 4 // length of pixels
-    + (PATCH_SIZE_PX * PATCH_SIZE_PX); // Pixels
+// half width of the square area painted by a bomb, the blast is (2r + 1) pixels wide
+pub const BOMB_BLAST_RADIUS_PX: usize = 3;
+
+// half width of the square area protected by a shield, the area is (2r + 1) pixels wide
+pub const SHIELD_RADIUS_PX: u8 = 2;
+
+/// Width and height in pixels of a patch (or patch attribution) holding `pixel_count` pixels
+pub fn patch_size_for_pixel_count(pixel_count: usize) -> usize {
+    let mut size = 0;
+    while (size + 1) * (size + 1) <= pixel_count {
+        size += 1;
+    }
+    size
+}
+
+/// How pixels are encoded in a patch
+#[derive(BorshDeserialize, BorshSerialize, PartialEq, Debug, Clone, Copy)]
+pub enum PixelFormat {
+    // 8 bit value, which gets mapped to a 32 bit color from a pallete on clients
+    Indexed8,
+
+    // 16 bit color with 5 bits red, 6 bits green and 5 bits blue, stored little endian
+    Rgb565,
+}
+
+impl PixelFormat {
+    pub fn bytes_per_pixel(&self) -> usize {
+        match self {
+            PixelFormat::Indexed8 => 1,
+            PixelFormat::Rgb565 => 2,
+        }
+    }
+
+    /// Pixel values are passed to instructions as u16, palette indexes must fit in a byte
+    pub fn is_valid_pixel(&self, pixel: u16) -> bool {
+        match self {
+            PixelFormat::Indexed8 => pixel <= u8::MAX as u16,
+            PixelFormat::Rgb565 => true,
+        }
+    }
+}
+
+/// An area of a patch that can't be painted until the shield expires
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy, Default)]
+pub struct PatchShield {
+    // inclusive bounds of the protected area, as offsets within the patch
+    pub x_min: u8,
+    pub y_min: u8,
+    pub x_max: u8,
+    pub y_max: u8,
+
+    // slot at which the area stops being protected, 0 if the patch was never shielded
+    pub expires_at_slot: Slot,
+}
+
+impl PatchShield {
+    pub const LEN: usize = 0 +
+        4 + // x_min, y_min, x_max, y_max
+        8; // expires_at_slot
+
+    pub fn is_active(&self, slot: Slot) -> bool {
+        slot < self.expires_at_slot
+    }
+
+    pub fn protects(&self, x_offset: u8, y_offset: u8, slot: Slot) -> bool {
+        self.is_active(slot)
+            && x_offset >= self.x_min
+            && x_offset <= self.x_max
+            && y_offset >= self.y_min
+            && y_offset <= self.y_max
+    }
+}
 
 /// In order to prevent a global write lock, i'll chunk the pixel into regions
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
 pub struct Patch {
     pub acct_type: PlaceAccountType,
+    // layout version of this account, see CURRENT_ACCOUNT_VERSION
+    pub version: u8,
+    // canvas the patch belongs to
+    pub canvas_id: u64,
+    // encoding of the pixels, copied from the place when the patch is created
+    pub format: PixelFormat,
     // x coordinate of the patch ULO
     pub x: u8,
     // y coordinate of the patch ULO
     pub y: u8,
 
-    // Pixels in row major order
+    // the most recently activated shield on this patch
+    pub shield: PatchShield,
+
+    // room for new fields without reallocating, always zero until a new version uses it
+    pub reserved: [u8; PATCH_RESERVED_LEN],
+
+    // Pixels in row major order, each pixel takes `format.bytes_per_pixel()` bytes
     pub pixels: Vec<u8>,
 }
 
+/// The patch as it was first deployed, only read to upgrade it
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+struct PatchV0 {
+    acct_type: PlaceAccountType,
+    x: u8,
+    y: u8,
+    pixels: Vec<u8>,
+}
+
 impl Patch {
     pub const PREFIX: &'static str = "patch";
 
-    pub const LEN: usize = 0 
+    // version 0 patches were 20px and palette indexed, with only the coordinates before the pixels
+    pub const LEN_V0: usize = 0
+        + 1 // acct_type
+        + 1 // x
+        + 1 // y
+        + 4 // length of pixels
+        + (20 * 20); // Pixels
+
+    /// Length of a patch account for a place with the given patch size and pixel format
+    pub fn len_for_size(patch_size_px: u8, format: PixelFormat) -> usize {
+        let patch_size_px = patch_size_px as usize;
+        0
         + 1 // acct_type
+        + 1 // version
+        + 8 // canvas_id
+        + 1 // format
         + 1 // x
         + 1 // y
+        + PatchShield::LEN // shield
+        + PATCH_RESERVED_LEN // reserved
         + 4 // length of pixels
-        + (PATCH_SIZE_PX * PATCH_SIZE_PX); // Pixels
+        + (patch_size_px * patch_size_px * format.bytes_per_pixel()) // Pixels
+    }
+
+    /// Version 0 patches have no version byte, x sits where it would be. They are told apart
+    /// by length instead, no later patch size and pixel format adds up to LEN_V0
+    pub fn is_v0_layout(b: &[u8]) -> bool {
+        b.len() == Self::LEN_V0
+    }
 
     pub fn from_account_info(a: &AccountInfo) -> Result<Patch, ProgramError> {
-        let patch: Patch =
-            try_from_slice_checked(&a.data.borrow_mut(), PlaceAccountType::Patch, Self::LEN)?;
-        return Ok(patch);
+        return Self::from_bytes(&a.data.borrow());
     }
 
+    /// The patch size is not known without the place state, so the account length is
+    /// checked against the number of pixels in the patch instead. Reads any layout version,
+    /// older versions are upgraded to the current one in memory
     pub fn from_bytes(b: &[u8]) -> Result<Patch, ProgramError> {
-        let patch: Patch = try_from_slice_checked(b, PlaceAccountType::Patch, Self::LEN)?;
+        if Self::is_v0_layout(b) {
+            let v0: PatchV0 = try_from_slice_checked(b, PlaceAccountType::Patch, Self::LEN_V0)?;
+            return Ok(Self::from_v0(v0));
+        }
+
+        let patch: Patch = try_from_slice_checked(b, PlaceAccountType::Patch, b.len())?;
+        let pixel_count = patch.pixel_count();
+        let patch_size_px = patch_size_for_pixel_count(pixel_count);
+        if patch.pixels.len() % patch.format.bytes_per_pixel() != 0
+            || patch_size_px * patch_size_px != pixel_count
+            || b.len() != Self::len_for_size(patch_size_px as u8, patch.format)
+        {
+            return Err(PlaceError::AccountDataTypeMismatch.into());
+        }
+        if patch.version > CURRENT_ACCOUNT_VERSION {
+            return Err(PlaceError::UnsupportedAccountVersion.into());
+        }
         Ok(patch)
     }
 
-    pub fn pda(x: u8, y: u8) -> (Pubkey, u8) {
+    fn from_v0(v0: PatchV0) -> Patch {
+        Patch {
+            acct_type: PlaceAccountType::Patch,
+            version: CURRENT_ACCOUNT_VERSION,
+            canvas_id: LEGACY_CANVAS_ID,
+            format: PixelFormat::Indexed8,
+            x: v0.x,
+            y: v0.y,
+            shield: PatchShield::default(),
+            reserved: [0; PATCH_RESERVED_LEN],
+            pixels: v0.pixels,
+        }
+    }
+
+    pub fn pixel_count(&self) -> usize {
+        self.pixels.len() / self.format.bytes_per_pixel()
+    }
+
+    pub fn patch_size(&self) -> usize {
+        patch_size_for_pixel_count(self.pixel_count())
+    }
+
+    /// Value of the pixel at the given row major index
+    pub fn pixel_at(&self, idx: usize) -> u16 {
+        match self.format {
+            PixelFormat::Indexed8 => self.pixels[idx] as u16,
+            PixelFormat::Rgb565 => u16::from_le_bytes([self.pixels[idx * 2], self.pixels[idx * 2 + 1]]),
+        }
+    }
+
+    /// Callers are expected to have checked the value with `PixelFormat::is_valid_pixel`
+    pub fn set_pixel_at(&mut self, idx: usize, pixel: u16) {
+        match self.format {
+            PixelFormat::Indexed8 => self.pixels[idx] = pixel as u8,
+            PixelFormat::Rgb565 => self.pixels[idx * 2..idx * 2 + 2].copy_from_slice(&pixel.to_le_bytes()),
+        }
+    }
+
+    pub fn pda(canvas_id: u64, x: u8, y: u8) -> (Pubkey, u8) {
+        return Pubkey::find_program_address(
+            &[
+                Self::PREFIX.as_bytes(),
+                &canvas_id.to_le_bytes(),
+                &x.to_le_bytes(),
+                &y.to_le_bytes(),
+            ],
+            &crate::id(),
+        );
+    }
+
+    pub fn pda_for_instance(&self) -> (Pubkey, u8) {
+        return Self::pda(self.canvas_id, self.x, self.y);
+    }
+
+    /// Where version 0 patches live, before canvas ids were part of the seeds
+    pub fn legacy_pda(x: u8, y: u8) -> (Pubkey, u8) {
         return Pubkey::find_program_address(
             &[
                 Self::PREFIX.as_bytes(),
@@ -283,8 +1712,130 @@ impl Patch {
             &crate::id(),
         );
     }
+}
+
+//////////////////////////////////////////////////////////////////////////////////
+///////////////////////////// PATCH ATTRIBUTION //////////////////////////////////
+
+/// The last write to a single pixel
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy, Default)]
+pub struct PixelAttribution {
+    // random seed of the gameplay token that last painted this pixel
+    pub gameplay_token_seed: u64,
+
+    // slot the pixel was last painted at, 0 if the pixel has never been painted
+    pub slot: Slot,
+}
+
+impl PixelAttribution {
+    pub const LEN: usize = 0 +
+        8 + // gameplay_token_seed
+        8; // slot
+
+    pub fn is_painted(&self) -> bool {
+        self.slot != 0
+    }
+}
+
+/// Companion account to a patch, records who last painted each pixel of the patch and when
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct PatchAttribution {
+    pub acct_type: PlaceAccountType,
+    // canvas the patch belongs to
+    pub canvas_id: u64,
+    // x coordinate of the patch this attribution belongs to
+    pub x: u8,
+    // y coordinate of the patch this attribution belongs to
+    pub y: u8,
+
+    // Attribution for each pixel, in the same row major order as Patch::pixels
+    pub pixels: Vec<PixelAttribution>,
+}
+
+impl PatchAttribution {
+    pub const PREFIX: &'static str = "attr";
+
+    /// Length of a patch attribution account for a place with the given patch size
+    pub fn len_for_size(patch_size_px: u8) -> usize {
+        let patch_size_px = patch_size_px as usize;
+        0
+        + 1 // acct_type
+        + 8 // canvas_id
+        + 1 // x
+        + 1 // y
+        + 4 // length of pixels
+        + (patch_size_px * patch_size_px * PixelAttribution::LEN) // Pixels
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<PatchAttribution, ProgramError> {
+        Self::from_bytes(&a.data.borrow())
+    }
+
+    pub fn from_bytes(b: &[u8]) -> Result<PatchAttribution, ProgramError> {
+        let attribution: PatchAttribution =
+            try_from_slice_checked(b, PlaceAccountType::PatchAttribution, b.len())?;
+        let patch_size_px = patch_size_for_pixel_count(attribution.pixels.len());
+        if patch_size_px * patch_size_px != attribution.pixels.len()
+            || b.len() != Self::len_for_size(patch_size_px as u8)
+        {
+            return Err(PlaceError::AccountDataTypeMismatch.into());
+        }
+        Ok(attribution)
+    }
+
+    pub fn patch_size(&self) -> usize {
+        patch_size_for_pixel_count(self.pixels.len())
+    }
+
+    pub fn pda(canvas_id: u64, x: u8, y: u8) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                Patch::PREFIX.as_bytes(),
+                &canvas_id.to_le_bytes(),
+                &x.to_le_bytes(),
+                &y.to_le_bytes(),
+                Self::PREFIX.as_bytes(),
+            ],
+            &crate::id(),
+        )
+    }
 
     pub fn pda_for_instance(&self) -> (Pubkey, u8) {
-        return Self::pda(self.x, self.y);
+        Self::pda(self.canvas_id, self.x, self.y)
+    }
+
+    /// The last write to the pixel at the given offset, None if it has never been painted
+    pub fn attribution_at(&self, x_offset: u8, y_offset: u8) -> Option<&PixelAttribution> {
+        if x_offset as usize >= self.patch_size() {
+            return None;
+        }
+        let idx = (y_offset as usize * self.patch_size()) + x_offset as usize;
+        self.pixels.get(idx).filter(|a| a.is_painted())
+    }
+
+    /// Offsets of all pixels in this patch last painted by the given gameplay token
+    pub fn pixels_painted_by(&self, gameplay_token_seed: u64) -> Vec<(u8, u8)> {
+        let patch_size = self.patch_size();
+        self.pixels
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| a.is_painted() && a.gameplay_token_seed == gameplay_token_seed)
+            .map(|(idx, _)| ((idx % patch_size) as u8, (idx / patch_size) as u8))
+            .collect()
+    }
+
+    /// Forget every write, as if the patch had never been painted
+    pub fn clear(&mut self) {
+        for pixel in self.pixels.iter_mut() {
+            *pixel = PixelAttribution::default();
+        }
+    }
+
+    pub fn record(&mut self, x_offset: u8, y_offset: u8, gameplay_token_seed: u64, slot: Slot) {
+        let idx = (y_offset as usize * self.patch_size()) + x_offset as usize;
+        self.pixels[idx] = PixelAttribution {
+            gameplay_token_seed,
+            slot,
+        };
     }
 }
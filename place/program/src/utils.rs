@@ -1,5 +1,6 @@
 use solana_program::{
     account_info::AccountInfo,
+    bpf_loader_upgradeable,
     entrypoint::ProgramResult,
     msg,
     program::{invoke, invoke_signed},
@@ -69,6 +70,37 @@ pub fn assert_signer(account_info: &AccountInfo) -> ProgramResult {
     }
 }
 
+/// Checks the signer is the upgrade authority of the program, as recorded in its program data account
+pub fn assert_upgrade_authority(
+    program_id: &Pubkey,
+    program_data_acct: &AccountInfo,
+    upgrade_authority_acct: &AccountInfo,
+) -> ProgramResult {
+    assert_signer(upgrade_authority_acct)?;
+
+    let (program_data, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    if program_data != *program_data_acct.key
+        || *program_data_acct.owner != bpf_loader_upgradeable::id()
+    {
+        return Err(PlaceError::IncorrectProgramDataAccount.into());
+    }
+
+    // bincode encoded UpgradeableLoaderState::ProgramData, a u32 variant index, the slot it
+    // was last deployed at, then the upgrade authority as an Option<Pubkey>
+    let data = program_data_acct.data.borrow();
+    if data.len() < 45 || data[0..4] != 3u32.to_le_bytes() {
+        return Err(PlaceError::IncorrectProgramDataAccount.into());
+    }
+
+    // a program without an upgrade authority is immutable, nobody can initialize places
+    if data[12] != 1 || &data[13..45] != upgrade_authority_acct.key.as_ref() {
+        return Err(PlaceError::NotUpgradeAuthority.into());
+    }
+
+    Ok(())
+}
+
 pub fn assert_system_prog(account_info: &AccountInfo) -> ProgramResult {
     if solana_program::system_program::check_id(account_info.key) {
         Ok(())
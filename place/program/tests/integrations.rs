@@ -1,32 +1,44 @@
 use solana_program::instruction::InstructionError;
 use solana_program::program_pack::Pack;
-use solana_program::{borsh::try_from_slice_unchecked, system_instruction};
+use solana_program::{
+    borsh::try_from_slice_unchecked, bpf_loader_upgradeable, hash::Hash, pubkey::Pubkey,
+    system_instruction,
+};
 
 use assert_matches::assert_matches;
-use solana_place::state::{find_address_for_patch, GameplayTokenMeta};
-use solana_program_test::{processor, tokio, ProgramTest};
+use borsh::BorshSerialize;
+use solana_place::state::{
+    find_address_for_patch, EmissionSchedule, GameplayTokenMeta, GameplayTokenRewards,
+};
+use solana_program_test::{processor, tokio, BanksClient, ProgramTest};
 use solana_sdk::transaction::TransactionError;
 use solana_sdk::{
-    commitment_config::CommitmentLevel, signature::Keypair, signature::Signer,
-    transaction::Transaction, transport::TransportError,
+    account::Account, commitment_config::CommitmentLevel, signature::keypair_from_seed,
+    signature::Keypair, signature::Signer, transaction::Transaction, transport::TransportError,
 };
 
 use spl_token::state::Account as TokenAccount;
 
+use solana_place::allowlist::{allowlist_leaf, verify_allowlist_proof, AllowlistTree};
 use solana_place::instruction;
-use solana_place::state::{GameplayTokenType, Patch, PlaceAccountType, PlaceState, PATCH_SIZE_PX};
+use solana_place::instruction::{AllowlistProof, UpdatePlaceStateDataArgs};
+use solana_place::state::{
+    AllowlistReceipt, GameplayTokenType, Patch, PatchAttribution, PauseFlags, PixelFormat,
+    PlaceAccountType, PlaceRole, PlaceState, PlaceTokenPricing, PriceCurve, PriceCurveKind,
+    PurchasePhases, RegionNftMeta, SeasonArchive, UpgradeSchedule,
+};
+
+// every test except test_small_place runs on a place with the default dimensions
+const PATCH_SIZE_PX: usize = solana_place::state::DEFAULT_PATCH_SIZE_PX as usize;
+
+// every test except test_multiple_canvases runs on a single canvas
+const CANVAS_ID: u64 = 0;
 
 #[tokio::test]
 async fn test_all_the_things() {
     // let program_id = Pubkey::new_unique();
     let program_id = solana_place::id();
-    let mut pt = ProgramTest::new(
-        "solana_place",
-        program_id,
-        processor!(solana_place::entrypoint::process_instruction),
-    );
-
-    pt.add_program("mpl_token_metadata", mpl_token_metadata::id(), None);
+    let pt = place_program_test();
 
     let mut pt_ctx = pt.start_with_context().await;
 
@@ -52,19 +64,39 @@ async fn test_all_the_things() {
 
     // Initialize the place state account
 
-    let update_place_ix = instruction::get_ix_update_place_state(
+    let upgrade_authority = place_upgrade_authority();
+    let update_place_ix = instruction::get_ix_initialize_place(
+        CANVAS_ID,
         payer.pubkey(),
-        Some(payer.pubkey()),
-        Some(false),
+        upgrade_authority.pubkey(),
+        payer.pubkey(),
+        None,
+        None,
+        None,
         None,
         None,
         None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(PauseFlags::NONE),
     );
 
     let update_place_tx = Transaction::new_signed_with_payer(
         &[update_place_ix],
         Some(&payer.pubkey()),
-        &[&payer],
+        &[&payer, &upgrade_authority],
         recent_blockhash,
     );
 
@@ -73,7 +105,7 @@ async fn test_all_the_things() {
 
     {
         // Check update results
-        let (place_state_pda, _) = PlaceState::pda();
+        let (place_state_pda, _) = PlaceState::pda(CANVAS_ID);
         let place_state_acct = banks_client
             .get_account(place_state_pda)
             .await
@@ -83,7 +115,7 @@ async fn test_all_the_things() {
         let state = PlaceState::from_bytes(&place_state_acct.data).unwrap();
         assert_eq!(state.acct_type, PlaceAccountType::PlaceState);
         assert_eq!(state.owner, payer.pubkey());
-        assert_eq!(state.is_frozen, false);
+        assert!(!state.is_frozen());
         assert_eq!(
             state.paintbrush_price,
             solana_place::state::DEFAULT_PAINTBRUSH_PRICE
@@ -93,6 +125,23 @@ async fn test_all_the_things() {
             solana_place::state::DEFAULT_PAINTBRUSH_COOLDOWN
         );
         assert_eq!(state.bomb_price, solana_place::state::DEFAULT_BOMB_PRICE);
+        assert_eq!(
+            state.shield_price,
+            solana_place::state::DEFAULT_SHIELD_PRICE
+        );
+        assert_eq!(
+            state.shield_duration,
+            solana_place::state::DEFAULT_SHIELD_DURATION
+        );
+        assert_eq!(state.width_px, solana_place::state::DEFAULT_PLACE_WIDTH_PX);
+        assert_eq!(
+            state.height_px,
+            solana_place::state::DEFAULT_PLACE_HEIGHT_PX
+        );
+        assert_eq!(
+            state.patch_size_px,
+            solana_place::state::DEFAULT_PATCH_SIZE_PX
+        );
     }
 
     // update the place state account
@@ -103,11 +152,13 @@ async fn test_all_the_things() {
 
     let update_place_ix2 = instruction::get_ix_update_place_state(
         payer.pubkey(),
-        None,
-        None,
-        Some(new_paintbrush_price),
-        Some(new_paintbrush_cooldown),
-        Some(new_bomb_price),
+        UpdatePlaceStateDataArgs {
+            canvas_id: CANVAS_ID,
+            paintbrush_price: Some(new_paintbrush_price),
+            paintbrush_cooldown: Some(new_paintbrush_cooldown),
+            bomb_price: Some(new_bomb_price),
+            ..Default::default()
+        },
     );
 
     let update_place_tx2 = Transaction::new_signed_with_payer(
@@ -122,7 +173,7 @@ async fn test_all_the_things() {
 
     {
         // Check update results
-        let (place_state_pda, _) = PlaceState::pda();
+        let (place_state_pda, _) = PlaceState::pda(CANVAS_ID);
         let place_state_acct = banks_client
             .get_account(place_state_pda)
             .await
@@ -132,7 +183,7 @@ async fn test_all_the_things() {
         let state = PlaceState::from_bytes(&place_state_acct.data).unwrap();
         assert_eq!(state.acct_type, PlaceAccountType::PlaceState);
         assert_eq!(state.owner, payer.pubkey());
-        assert_eq!(state.is_frozen, false);
+        assert!(!state.is_frozen());
         assert_eq!(state.paintbrush_price, new_paintbrush_price);
         assert_eq!(state.paintbrush_cooldown, new_paintbrush_cooldown);
         assert_eq!(state.bomb_price, new_bomb_price);
@@ -140,7 +191,7 @@ async fn test_all_the_things() {
 
     // initialize the token mint
 
-    let init_mint_ix = instruction::get_ix_init_mint(payer.pubkey());
+    let init_mint_ix = instruction::get_ix_init_mint(CANVAS_ID, payer.pubkey());
     let init_mint_tx = Transaction::new_signed_with_payer(
         &[init_mint_ix],
         Some(&payer.pubkey()),
@@ -158,7 +209,7 @@ async fn test_all_the_things() {
         .unwrap()
         .unwrap();
 
-    let init_mint_ix2 = instruction::get_ix_init_mint(payer.pubkey());
+    let init_mint_ix2 = instruction::get_ix_init_mint(CANVAS_ID, payer.pubkey());
     let init_mint_tx2 = Transaction::new_signed_with_payer(
         &[init_mint_ix2],
         Some(&payer.pubkey()),
@@ -181,6 +232,7 @@ async fn test_all_the_things() {
     let random_seed: u64 = 10101;
 
     let purchase_gameplay_token_ix = instruction::get_ix_purchase_gameplay_token(
+        CANVAS_ID,
         game_player.pubkey(),
         random_seed,
         GameplayTokenType::PaintBrush,
@@ -249,7 +301,7 @@ async fn test_all_the_things() {
     );
 
     let claimable_tokens = gameplay_token_acct.place_tokens_owed;
-    let (place_token_mint_pda, _) = PlaceState::token_mint_pda();
+    let (place_token_mint_pda, _) = PlaceState::token_mint_pda(CANVAS_ID);
 
     // Create an ATA for game_player to claim tokens into
 
@@ -279,6 +331,7 @@ async fn test_all_the_things() {
     // Claim the tokens from the purchase into the game_player's ata
 
     let claim_tokens_ix = solana_place::instruction::get_ix_claim_tokens(
+        CANVAS_ID,
         game_player.pubkey(),
         place_tokens_ata,
         gameplay_token_acct.token_mint_pda,
@@ -324,12 +377,17 @@ async fn test_all_the_things() {
     let x_offset = 0u8;
     let y_offset = 0u8;
 
-    let pixel: u8 = 0b10101010;
+    let pixel: u16 = 0b10101010;
 
-    let (patch_pda, _) = find_address_for_patch(x, y, &program_id);
+    let (patch_pda, _) = find_address_for_patch(CANVAS_ID, x, y, &program_id);
 
-    let init_patch_ix =
-        solana_place::instruction::get_ix_init_patch(solana_place::id(), payer.pubkey(), x, y);
+    let init_patch_ix = solana_place::instruction::get_ix_init_patch(
+        solana_place::id(),
+        CANVAS_ID,
+        payer.pubkey(),
+        x,
+        y,
+    );
 
     let init_patch_tx = Transaction::new_signed_with_payer(
         &[init_patch_ix],
@@ -352,6 +410,7 @@ async fn test_all_the_things() {
 
     let set_pixel_ix = solana_place::instruction::get_ix_set_pixel(
         solana_place::id(),
+        CANVAS_ID,
         game_player.pubkey(),
         gameplay_token_pda,
         game_player_ata,
@@ -397,9 +456,9 @@ async fn test_all_the_things() {
             for x in 0..PATCH_SIZE_PX {
                 let idx = y * PATCH_SIZE_PX + x;
                 if x_offset == x as u8 && y_offset == y as u8 {
-                    assert_eq!(pixel, patch.pixels[idx])
+                    assert_eq!(pixel, patch.pixel_at(idx))
                 } else {
-                    assert_eq!(0 as u8, patch.pixels[idx]);
+                    assert_eq!(0, patch.pixel_at(idx));
                 }
             }
         }
@@ -419,6 +478,7 @@ async fn test_all_the_things() {
     // Attempt to set another pixel, but should fail because of cooldown
     let set_pixel_ix2 = solana_place::instruction::get_ix_set_pixel(
         solana_place::id(),
+        CANVAS_ID,
         game_player.pubkey(),
         gameplay_token_pda,
         game_player_ata,
@@ -458,3 +518,5487 @@ async fn test_all_the_things() {
         gampelay_token_acct_after.place_tokens_owed,
     );
 }
+
+/// Key recorded as the place program's upgrade authority in every test, the only key that
+/// can initialize places
+fn place_upgrade_authority() -> Keypair {
+    keypair_from_seed(&[7; 32]).unwrap()
+}
+
+/// A program data account for the place program, laid out the way the upgradeable loader
+/// serializes UpgradeableLoaderState::ProgramData
+fn place_program_data_account(upgrade_authority: Option<Pubkey>) -> Account {
+    let mut data = 3u32.to_le_bytes().to_vec();
+    data.extend_from_slice(&0u64.to_le_bytes());
+    match upgrade_authority {
+        Some(upgrade_authority) => {
+            data.push(1);
+            data.extend_from_slice(upgrade_authority.as_ref());
+        }
+        None => data.push(0),
+    }
+    data.resize(45, 0);
+
+    Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: bpf_loader_upgradeable::id(),
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Sets up the place and token metadata programs, with place_upgrade_authority() as the
+/// upgrade authority of the place program
+fn place_program_test() -> ProgramTest {
+    let program_id = solana_place::id();
+    let mut pt = ProgramTest::new(
+        "solana_place",
+        program_id,
+        processor!(solana_place::entrypoint::process_instruction),
+    );
+
+    pt.add_program("mpl_token_metadata", mpl_token_metadata::id(), None);
+
+    let (program_data, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    pt.add_account(
+        program_data,
+        place_program_data_account(Some(place_upgrade_authority().pubkey())),
+    );
+
+    pt
+}
+
+/// Starts a test validator with the place program loaded, initializes the place state
+/// with default values owned by the payer, and funds a separate game player keypair
+async fn start_place_test() -> (BanksClient, Keypair, Keypair, Hash) {
+    start_place_test_with_layout(None, None, None, None).await
+}
+
+async fn start_place_test_with_layout(
+    width_px: Option<u16>,
+    height_px: Option<u16>,
+    patch_size_px: Option<u8>,
+    pixel_format: Option<PixelFormat>,
+) -> (BanksClient, Keypair, Keypair, Hash) {
+    start_place_test_with_program(
+        place_program_test(),
+        width_px,
+        height_px,
+        patch_size_px,
+        pixel_format,
+    )
+    .await
+}
+
+/// Same as start_place_test_with_layout, for a program test that already has accounts added
+async fn start_place_test_with_program(
+    pt: ProgramTest,
+    width_px: Option<u16>,
+    height_px: Option<u16>,
+    patch_size_px: Option<u8>,
+    pixel_format: Option<PixelFormat>,
+) -> (BanksClient, Keypair, Keypair, Hash) {
+    let pt_ctx = pt.start_with_context().await;
+
+    let mut banks_client = pt_ctx.banks_client;
+    let payer = pt_ctx.payer;
+    let recent_blockhash = pt_ctx.last_blockhash;
+
+    let game_player = Keypair::new();
+    let fund_game_player_ix =
+        system_instruction::transfer(&payer.pubkey(), &game_player.pubkey(), 10_000_000_000);
+
+    let upgrade_authority = place_upgrade_authority();
+    let init_place_ix = instruction::get_ix_initialize_place(
+        CANVAS_ID,
+        payer.pubkey(),
+        upgrade_authority.pubkey(),
+        payer.pubkey(),
+        None,
+        None,
+        None,
+        width_px,
+        height_px,
+        patch_size_px,
+        pixel_format,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(PauseFlags::NONE),
+    );
+
+    let setup_tx = Transaction::new_signed_with_payer(
+        &[fund_game_player_ix, init_place_ix],
+        Some(&payer.pubkey()),
+        &[&payer, &upgrade_authority],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(setup_tx).await, Ok(()));
+
+    (banks_client, payer, game_player, recent_blockhash)
+}
+
+async fn get_place_state(banks_client: &mut BanksClient) -> PlaceState {
+    let (place_state_pda, _) = PlaceState::pda(CANVAS_ID);
+    let place_state_acct = banks_client
+        .get_account(place_state_pda)
+        .await
+        .unwrap()
+        .unwrap();
+
+    PlaceState::from_bytes(&place_state_acct.data).unwrap()
+}
+
+/// Purchases a gameplay token for the player at the default price, returning the
+/// gameplay token meta pda and the player's ATA holding the NFT
+async fn purchase_gameplay_token(
+    banks_client: &mut BanksClient,
+    game_player: &Keypair,
+    random_seed: u64,
+    token_type: GameplayTokenType,
+    recent_blockhash: Hash,
+) -> (Pubkey, Pubkey) {
+    let price = match token_type {
+        GameplayTokenType::PaintBrush => solana_place::state::DEFAULT_PAINTBRUSH_PRICE,
+        GameplayTokenType::Bomb => solana_place::state::DEFAULT_BOMB_PRICE,
+        GameplayTokenType::Shield => solana_place::state::DEFAULT_SHIELD_PRICE,
+    };
+
+    let purchase_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_purchase_gameplay_token(
+            CANVAS_ID,
+            game_player.pubkey(),
+            random_seed,
+            token_type,
+            price,
+        )],
+        Some(&game_player.pubkey()),
+        &[game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(purchase_tx).await, Ok(()));
+
+    let (gameplay_token_pda, _) = GameplayTokenMeta::pda(random_seed);
+    let (gameplay_token_mint_pda, _) = GameplayTokenMeta::token_mint_pda(random_seed);
+    let gameplay_token_ata = spl_associated_token_account::get_associated_token_address(
+        &game_player.pubkey(),
+        &gameplay_token_mint_pda,
+    );
+
+    (gameplay_token_pda, gameplay_token_ata)
+}
+
+#[tokio::test]
+async fn test_detonate_bomb() {
+    let program_id = solana_place::id();
+    let (mut banks_client, payer, game_player, recent_blockhash) = start_place_test().await;
+
+    // Initialize the patches around the blast
+
+    let mut setup_ixs = vec![];
+    for (patch_x, patch_y) in [(0u8, 0u8), (1, 0), (0, 1), (1, 1)] {
+        setup_ixs.push(instruction::get_ix_init_patch(
+            program_id,
+            CANVAS_ID,
+            payer.pubkey(),
+            patch_x,
+            patch_y,
+        ));
+    }
+
+    let setup_tx = Transaction::new_signed_with_payer(
+        &setup_ixs,
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(setup_tx).await, Ok(()));
+
+    let (paintbrush_pda, paintbrush_ata) = purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        20202,
+        GameplayTokenType::PaintBrush,
+        recent_blockhash,
+    )
+    .await;
+
+    let (bomb_pda, bomb_ata) = purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        30303,
+        GameplayTokenType::Bomb,
+        recent_blockhash,
+    )
+    .await;
+
+    // the blast is centered in the bottom right corner of patch (0, 0), so it spills
+    // into all of the neighboring patches
+
+    let x = 0u8;
+    let y = 0u8;
+    let x_offset = (PATCH_SIZE_PX - 1) as u8;
+    let y_offset = (PATCH_SIZE_PX - 1) as u8;
+    let pixel = 7u16;
+
+    let place_state = get_place_state(&mut banks_client).await;
+    let blast_patches = place_state.bomb_blast_patches(x, y, x_offset, y_offset);
+    assert_eq!(blast_patches, vec![(0, 0), (1, 0), (0, 1), (1, 1)]);
+
+    // A paintbrush can not be detonated
+
+    let paintbrush_detonate_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_detonate_bomb(
+            &place_state,
+            game_player.pubkey(),
+            paintbrush_pda,
+            paintbrush_ata,
+            x,
+            y,
+            x_offset,
+            y_offset,
+            pixel,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(paintbrush_detonate_tx)
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(31))
+        ))
+    );
+
+    // Missing one of the patches touched by the blast
+
+    let mut missing_patch_ix = instruction::get_ix_detonate_bomb(
+        &place_state,
+        game_player.pubkey(),
+        bomb_pda,
+        bomb_ata,
+        x,
+        y,
+        x_offset,
+        y_offset,
+        pixel,
+    );
+    missing_patch_ix.accounts.pop();
+
+    let missing_patch_tx = Transaction::new_signed_with_payer(
+        &[missing_patch_ix],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(missing_patch_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(33))
+        ))
+    );
+
+    // Detonate the bomb
+
+    let bomb_before: GameplayTokenMeta = banks_client
+        .get_account_data_with_borsh(bomb_pda)
+        .await
+        .unwrap();
+
+    let detonate_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_detonate_bomb(
+            &place_state,
+            game_player.pubkey(),
+            bomb_pda,
+            bomb_ata,
+            x,
+            y,
+            x_offset,
+            y_offset,
+            pixel,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(detonate_tx).await, Ok(()));
+
+    let radius = solana_place::state::BOMB_BLAST_RADIUS_PX;
+    let center_x = x as usize * PATCH_SIZE_PX + x_offset as usize;
+    let center_y = y as usize * PATCH_SIZE_PX + y_offset as usize;
+
+    for (patch_x, patch_y) in blast_patches {
+        let (patch_pda, _) = find_address_for_patch(CANVAS_ID, patch_x, patch_y, &program_id);
+        let patch_acct = banks_client.get_account(patch_pda).await.unwrap().unwrap();
+        let patch: Patch = try_from_slice_unchecked(&patch_acct.data).unwrap();
+
+        for patch_y_offset in 0..PATCH_SIZE_PX {
+            for patch_x_offset in 0..PATCH_SIZE_PX {
+                let place_x = patch_x as usize * PATCH_SIZE_PX + patch_x_offset;
+                let place_y = patch_y as usize * PATCH_SIZE_PX + patch_y_offset;
+                let in_blast = place_x + radius >= center_x
+                    && place_x <= center_x + radius
+                    && place_y + radius >= center_y
+                    && place_y <= center_y + radius;
+
+                let idx = patch_y_offset * PATCH_SIZE_PX + patch_x_offset;
+                if in_blast {
+                    assert_eq!(pixel, patch.pixel_at(idx));
+                } else {
+                    assert_eq!(0, patch.pixel_at(idx));
+                }
+            }
+        }
+    }
+
+    let bomb_after: GameplayTokenMeta = banks_client
+        .get_account_data_with_borsh(bomb_pda)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        bomb_before.place_tokens_owed + 1,
+        bomb_after.place_tokens_owed
+    );
+    assert!(bomb_after.update_allowed_slot > bomb_before.update_allowed_slot);
+
+    // The bomb should now be cooling down
+
+    let (recent_blockhash2, _) = banks_client
+        .get_latest_blockhash_with_commitment(CommitmentLevel::Confirmed)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let detonate_tx2 = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_detonate_bomb(
+            &place_state,
+            game_player.pubkey(),
+            bomb_pda,
+            bomb_ata,
+            x,
+            y,
+            x_offset,
+            y_offset,
+            pixel + 1,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash2,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(detonate_tx2).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(12))
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_set_pixels() {
+    let program_id = solana_place::id();
+    let (mut banks_client, payer, game_player, recent_blockhash) = start_place_test().await;
+
+    let x = 2u8;
+    let y = 3u8;
+
+    let init_patch_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_init_patch(
+            program_id,
+            CANVAS_ID,
+            payer.pubkey(),
+            x,
+            y,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(init_patch_tx).await,
+        Ok(())
+    );
+
+    let (gameplay_token_pda, gameplay_token_ata) = purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        40404,
+        GameplayTokenType::PaintBrush,
+        recent_blockhash,
+    )
+    .await;
+
+    let (patch_pda, _) = find_address_for_patch(CANVAS_ID, x, y, &program_id);
+
+    // A single bad offset should reject the whole write
+
+    let bad_pixels = vec![
+        instruction::PixelWrite {
+            x_offset: 0,
+            y_offset: 0,
+            pixel: 1,
+        },
+        instruction::PixelWrite {
+            x_offset: PATCH_SIZE_PX as u8,
+            y_offset: 0,
+            pixel: 1,
+        },
+    ];
+
+    let bad_set_pixels_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_set_pixels(
+            program_id,
+            CANVAS_ID,
+            game_player.pubkey(),
+            gameplay_token_pda,
+            gameplay_token_ata,
+            x,
+            y,
+            bad_pixels,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(bad_set_pixels_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(32))
+        ))
+    );
+
+    let patch: Patch = banks_client
+        .get_account_data_with_borsh(patch_pda)
+        .await
+        .unwrap();
+    assert!(patch.pixels.iter().all(|p| *p == 0));
+
+    // Write a small diagonal line
+
+    let pixels: Vec<instruction::PixelWrite> = (0..5u8)
+        .map(|i| instruction::PixelWrite {
+            x_offset: i,
+            y_offset: i,
+            pixel: (i + 1) as u16,
+        })
+        .collect();
+
+    let gameplay_token_before: GameplayTokenMeta = banks_client
+        .get_account_data_with_borsh(gameplay_token_pda)
+        .await
+        .unwrap();
+
+    let set_pixels_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_set_pixels(
+            program_id,
+            CANVAS_ID,
+            game_player.pubkey(),
+            gameplay_token_pda,
+            gameplay_token_ata,
+            x,
+            y,
+            pixels.clone(),
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(set_pixels_tx).await,
+        Ok(())
+    );
+
+    let patch: Patch = banks_client
+        .get_account_data_with_borsh(patch_pda)
+        .await
+        .unwrap();
+
+    for write in pixels.iter() {
+        let idx = write.y_offset as usize * PATCH_SIZE_PX + write.x_offset as usize;
+        assert_eq!(write.pixel, patch.pixel_at(idx));
+    }
+    assert_eq!(
+        patch.pixels.iter().filter(|p| **p != 0).count(),
+        pixels.len()
+    );
+
+    // every written pixel should be attributed to the paintbrush
+
+    let (patch_attribution_pda, _) = PatchAttribution::pda(CANVAS_ID, x, y);
+    let patch_attribution_acct = banks_client
+        .get_account(patch_attribution_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let patch_attribution = PatchAttribution::from_bytes(&patch_attribution_acct.data).unwrap();
+
+    let mut painted_by_token = patch_attribution.pixels_painted_by(40404);
+    painted_by_token.sort();
+    let mut expected_painted: Vec<(u8, u8)> =
+        pixels.iter().map(|w| (w.x_offset, w.y_offset)).collect();
+    expected_painted.sort();
+    assert_eq!(painted_by_token, expected_painted);
+
+    let attribution = patch_attribution.attribution_at(0, 0).unwrap();
+    assert_eq!(attribution.gameplay_token_seed, 40404);
+    assert!(attribution.slot > 0);
+    assert_eq!(patch_attribution.attribution_at(0, 1), None);
+
+    let gameplay_token_after: GameplayTokenMeta = banks_client
+        .get_account_data_with_borsh(gameplay_token_pda)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        gameplay_token_before.place_tokens_owed + pixels.len() as u32,
+        gameplay_token_after.place_tokens_owed
+    );
+    assert!(
+        gameplay_token_after.update_allowed_slot
+            >= gameplay_token_before.update_allowed_slot
+                + gameplay_token_before.cooldown_duration * pixels.len() as u64
+    );
+}
+
+#[tokio::test]
+async fn test_freeze_and_seal_canvas() {
+    let program_id = solana_place::id();
+    let (mut banks_client, payer, game_player, recent_blockhash) = start_place_test().await;
+
+    let init_patch_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_init_patch(
+            program_id,
+            CANVAS_ID,
+            payer.pubkey(),
+            0,
+            0,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(init_patch_tx).await,
+        Ok(())
+    );
+
+    let (gameplay_token_pda, gameplay_token_ata) = purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        50505,
+        GameplayTokenType::PaintBrush,
+        recent_blockhash,
+    )
+    .await;
+
+    let set_pixel_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_set_pixel(
+            program_id,
+            CANVAS_ID,
+            game_player.pubkey(),
+            gameplay_token_pda,
+            gameplay_token_ata,
+            0,
+            0,
+            3,
+            4,
+            9,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(set_pixel_tx).await, Ok(()));
+
+    // Sealing requires the place to be frozen
+
+    let unfrozen_state = get_place_state(&mut banks_client).await;
+    let early_seal_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_seal_canvas(
+            &unfrozen_state,
+            payer.pubkey(),
+            0,
+            1,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(early_seal_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(36))
+        ))
+    );
+
+    // Freeze the place
+
+    let freeze_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_update_place_state(
+            payer.pubkey(),
+            UpdatePlaceStateDataArgs {
+                canvas_id: CANVAS_ID,
+                paused: Some(PauseFlags::FROZEN),
+                ..Default::default()
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(freeze_tx).await, Ok(()));
+
+    let (place_state_pda, _) = PlaceState::pda(CANVAS_ID);
+    let place_state_acct = banks_client
+        .get_account(place_state_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let state = PlaceState::from_bytes(&place_state_acct.data).unwrap();
+    assert!(state.is_frozen());
+
+    // Every mutating instruction should now be rejected
+
+    let (recent_blockhash2, _) = banks_client
+        .get_latest_blockhash_with_commitment(CommitmentLevel::Confirmed)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let frozen_ixs = vec![
+        instruction::get_ix_set_pixel(
+            program_id,
+            CANVAS_ID,
+            game_player.pubkey(),
+            gameplay_token_pda,
+            gameplay_token_ata,
+            0,
+            0,
+            5,
+            5,
+            1,
+        ),
+        instruction::get_ix_init_patch(program_id, CANVAS_ID, game_player.pubkey(), 1, 0),
+        instruction::get_ix_purchase_gameplay_token(
+            CANVAS_ID,
+            game_player.pubkey(),
+            60606,
+            GameplayTokenType::PaintBrush,
+            solana_place::state::DEFAULT_PAINTBRUSH_PRICE,
+        ),
+    ];
+
+    for frozen_ix in frozen_ixs {
+        let frozen_tx = Transaction::new_signed_with_payer(
+            &[frozen_ix],
+            Some(&game_player.pubkey()),
+            &[&game_player],
+            recent_blockhash2,
+        );
+
+        assert_matches!(
+            banks_client.process_transaction(frozen_tx).await,
+            Err(TransportError::TransactionError(
+                TransactionError::InstructionError(0, InstructionError::Custom(35))
+            ))
+        );
+    }
+
+    // Moderators can still clear a frozen canvas, until it starts being sealed
+
+    let clear_tx = |pixel: u16| {
+        Transaction::new_signed_with_payer(
+            &[instruction::get_ix_clear_region(
+                &state,
+                payer.pubkey(),
+                0,
+                0,
+                1,
+                1,
+                pixel,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash2,
+        )
+    };
+
+    assert_matches!(banks_client.process_transaction(clear_tx(2)).await, Ok(()));
+
+    // Seal the canvas in batches
+
+    let patch_count = state.patch_count();
+    let batch_size = 24u32;
+    let mut first_patch = 0u32;
+    while first_patch < patch_count {
+        let seal_tx = Transaction::new_signed_with_payer(
+            &[instruction::get_ix_seal_canvas(
+                &state,
+                payer.pubkey(),
+                first_patch,
+                batch_size,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash2,
+        );
+
+        assert_matches!(banks_client.process_transaction(seal_tx).await, Ok(()));
+
+        if first_patch == 0 {
+            assert_matches!(
+                banks_client.process_transaction(clear_tx(3)).await,
+                Err(TransportError::TransactionError(
+                    TransactionError::InstructionError(0, InstructionError::Custom(95))
+                ))
+            );
+        }
+
+        first_patch += batch_size;
+    }
+
+    assert_matches!(
+        banks_client.process_transaction(clear_tx(4)).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(95))
+        ))
+    );
+
+    // Only patch (0, 0) was initialized, every other patch is chained as blank pixels
+
+    let (patch_pda, _) = find_address_for_patch(CANVAS_ID, 0, 0, &program_id);
+    let patch: Patch = banks_client
+        .get_account_data_with_borsh(patch_pda)
+        .await
+        .unwrap();
+
+    let blank_pixels = vec![0u8; PATCH_SIZE_PX * PATCH_SIZE_PX];
+    let mut expected_hash = [0u8; 32];
+    for index in 0..patch_count {
+        let (patch_x, patch_y) = state.patch_coords(index);
+        let pixels = if index == 0 {
+            &patch.pixels
+        } else {
+            &blank_pixels
+        };
+        expected_hash = solana_place::state::CanvasSnapshot::chain_patch_hash(
+            &expected_hash,
+            patch_x,
+            patch_y,
+            pixels,
+        );
+    }
+
+    let (snapshot_pda, _) = solana_place::state::CanvasSnapshot::pda(CANVAS_ID);
+    let snapshot_acct = banks_client
+        .get_account(snapshot_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let snapshot = solana_place::state::CanvasSnapshot::from_bytes(&snapshot_acct.data).unwrap();
+
+    assert_eq!(snapshot.is_sealed, true);
+    assert_eq!(snapshot.next_patch, patch_count);
+    assert_eq!(snapshot.frozen_at_slot, state.frozen_at_slot);
+    assert_eq!(snapshot.hash, expected_hash);
+
+    // Sealing again fails
+
+    let reseal_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_seal_canvas(
+            &state,
+            payer.pubkey(),
+            0,
+            1,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash2,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(reseal_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(37))
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_small_place() {
+    let program_id = solana_place::id();
+    let (mut banks_client, payer, game_player, recent_blockhash) =
+        start_place_test_with_layout(Some(40), Some(30), Some(10), None).await;
+
+    let state = get_place_state(&mut banks_client).await;
+    assert_eq!(state.width_px, 40);
+    assert_eq!(state.height_px, 30);
+    assert_eq!(state.patch_size_px, 10);
+    assert_eq!(state.patch_count(), 12);
+
+    // The last patch is allowed, one past it is not
+
+    let init_patch_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_init_patch(
+            program_id,
+            CANVAS_ID,
+            payer.pubkey(),
+            3,
+            2,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(init_patch_tx).await,
+        Ok(())
+    );
+
+    let (patch_pda, _) = find_address_for_patch(CANVAS_ID, 3, 2, &program_id);
+    let patch_acct = banks_client.get_account(patch_pda).await.unwrap().unwrap();
+    assert_eq!(
+        patch_acct.data.len(),
+        Patch::len_for_size(10, PixelFormat::Indexed8)
+    );
+    let patch = Patch::from_bytes(&patch_acct.data).unwrap();
+    assert_eq!(patch.pixel_count(), 100);
+
+    let bad_patch_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_init_patch(
+            program_id,
+            CANVAS_ID,
+            payer.pubkey(),
+            4,
+            0,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(bad_patch_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(2))
+        ))
+    );
+
+    // Offsets are bounded by the smaller patch size
+
+    let (gameplay_token_pda, gameplay_token_ata) = purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        70707,
+        GameplayTokenType::PaintBrush,
+        recent_blockhash,
+    )
+    .await;
+
+    let bad_offset_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_set_pixel(
+            program_id,
+            CANVAS_ID,
+            game_player.pubkey(),
+            gameplay_token_pda,
+            gameplay_token_ata,
+            3,
+            2,
+            10,
+            0,
+            1,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(bad_offset_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(32))
+        ))
+    );
+
+    // Palette indexes must fit in a byte
+
+    let bad_value_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_set_pixel(
+            program_id,
+            CANVAS_ID,
+            game_player.pubkey(),
+            gameplay_token_pda,
+            gameplay_token_ata,
+            3,
+            2,
+            9,
+            9,
+            256,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(bad_value_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(45))
+        ))
+    );
+
+    let set_pixel_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_set_pixel(
+            program_id,
+            CANVAS_ID,
+            game_player.pubkey(),
+            gameplay_token_pda,
+            gameplay_token_ata,
+            3,
+            2,
+            9,
+            9,
+            4,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(set_pixel_tx).await, Ok(()));
+
+    let patch: Patch = banks_client
+        .get_account_data_with_borsh(patch_pda)
+        .await
+        .unwrap();
+    assert_eq!(patch.pixels[99], 4);
+}
+
+#[tokio::test]
+async fn test_multiple_canvases() {
+    let program_id = solana_place::id();
+    let (mut banks_client, payer, game_player, recent_blockhash) = start_place_test().await;
+
+    // Create a second, smaller canvas alongside the default one
+
+    let other_canvas_id: u64 = 7;
+    let create_canvas_tx = Transaction::new_signed_with_payer(
+        &[
+            instruction::get_ix_initialize_place(
+                other_canvas_id,
+                payer.pubkey(),
+                place_upgrade_authority().pubkey(),
+                payer.pubkey(),
+                None,
+                None,
+                None,
+                Some(40),
+                Some(40),
+                Some(10),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(PauseFlags::NONE),
+            ),
+            instruction::get_ix_init_patch(program_id, CANVAS_ID, payer.pubkey(), 0, 0),
+            instruction::get_ix_init_patch(program_id, other_canvas_id, payer.pubkey(), 0, 0),
+        ],
+        Some(&payer.pubkey()),
+        &[&payer, &place_upgrade_authority()],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(create_canvas_tx).await,
+        Ok(())
+    );
+
+    let (other_place_state_pda, _) = PlaceState::pda(other_canvas_id);
+    assert_ne!(other_place_state_pda, PlaceState::pda(CANVAS_ID).0);
+    let other_state: PlaceState = banks_client
+        .get_account_data_with_borsh(other_place_state_pda)
+        .await
+        .unwrap();
+    assert_eq!(other_state.canvas_id, other_canvas_id);
+    assert_eq!(other_state.width_px, 40);
+
+    let state = get_place_state(&mut banks_client).await;
+    assert_eq!(state.canvas_id, CANVAS_ID);
+    assert_eq!(state.width_px, solana_place::state::DEFAULT_PLACE_WIDTH_PX);
+
+    let (patch_pda, _) = find_address_for_patch(CANVAS_ID, 0, 0, &program_id);
+    let (other_patch_pda, _) = find_address_for_patch(other_canvas_id, 0, 0, &program_id);
+    assert_ne!(patch_pda, other_patch_pda);
+
+    let other_patch: Patch = banks_client
+        .get_account_data_with_borsh(other_patch_pda)
+        .await
+        .unwrap();
+    assert_eq!(other_patch.canvas_id, other_canvas_id);
+    assert_eq!(other_patch.pixel_count(), 100);
+
+    // A gameplay token bought for one canvas can't paint another
+
+    let (gameplay_token_pda, gameplay_token_ata) = purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        80808,
+        GameplayTokenType::PaintBrush,
+        recent_blockhash,
+    )
+    .await;
+
+    let gameplay_token: GameplayTokenMeta = banks_client
+        .get_account_data_with_borsh(gameplay_token_pda)
+        .await
+        .unwrap();
+    assert_eq!(gameplay_token.canvas_id, CANVAS_ID);
+
+    let wrong_canvas_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_set_pixel(
+            program_id,
+            other_canvas_id,
+            game_player.pubkey(),
+            gameplay_token_pda,
+            gameplay_token_ata,
+            0,
+            0,
+            1,
+            1,
+            3,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(wrong_canvas_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(43))
+        ))
+    );
+
+    let set_pixel_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_set_pixel(
+            program_id,
+            CANVAS_ID,
+            game_player.pubkey(),
+            gameplay_token_pda,
+            gameplay_token_ata,
+            0,
+            0,
+            1,
+            1,
+            3,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(set_pixel_tx).await, Ok(()));
+
+    let patch: Patch = banks_client
+        .get_account_data_with_borsh(patch_pda)
+        .await
+        .unwrap();
+    assert_eq!(patch.pixels[PATCH_SIZE_PX + 1], 3);
+
+    let other_patch: Patch = banks_client
+        .get_account_data_with_borsh(other_patch_pda)
+        .await
+        .unwrap();
+    assert!(other_patch.pixels.iter().all(|pixel| *pixel == 0));
+
+    // Freezing one canvas leaves the other playable
+
+    let freeze_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_update_place_state(
+            payer.pubkey(),
+            UpdatePlaceStateDataArgs {
+                canvas_id: other_canvas_id,
+                paused: Some(PauseFlags::FROZEN),
+                ..Default::default()
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(freeze_tx).await, Ok(()));
+
+    let state = get_place_state(&mut banks_client).await;
+    assert!(!state.is_frozen());
+}
+
+#[tokio::test]
+async fn test_rgb565_place() {
+    let program_id = solana_place::id();
+    let (mut banks_client, payer, game_player, recent_blockhash) =
+        start_place_test_with_layout(Some(40), Some(40), Some(10), Some(PixelFormat::Rgb565)).await;
+
+    let state = get_place_state(&mut banks_client).await;
+    assert_eq!(state.pixel_format, PixelFormat::Rgb565);
+
+    let init_patch_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_init_patch(
+            program_id,
+            CANVAS_ID,
+            payer.pubkey(),
+            1,
+            1,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(init_patch_tx).await,
+        Ok(())
+    );
+
+    let (patch_pda, _) = find_address_for_patch(CANVAS_ID, 1, 1, &program_id);
+    let patch_acct = banks_client.get_account(patch_pda).await.unwrap().unwrap();
+    assert_eq!(
+        patch_acct.data.len(),
+        Patch::len_for_size(10, PixelFormat::Rgb565)
+    );
+    let patch = Patch::from_bytes(&patch_acct.data).unwrap();
+    assert_eq!(patch.format, PixelFormat::Rgb565);
+    assert_eq!(patch.pixel_count(), 100);
+    assert_eq!(patch.pixels.len(), 200);
+
+    // Paint a full 16 bit colour
+
+    let (gameplay_token_pda, gameplay_token_ata) = purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        90909,
+        GameplayTokenType::PaintBrush,
+        recent_blockhash,
+    )
+    .await;
+
+    let orange: u16 = 0b11111_101000_00000;
+    let set_pixel_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_set_pixel(
+            program_id,
+            CANVAS_ID,
+            game_player.pubkey(),
+            gameplay_token_pda,
+            gameplay_token_ata,
+            1,
+            1,
+            4,
+            2,
+            orange,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(set_pixel_tx).await, Ok(()));
+
+    let patch: Patch = banks_client
+        .get_account_data_with_borsh(patch_pda)
+        .await
+        .unwrap();
+    let idx = 2 * 10 + 4;
+    assert_eq!(patch.pixel_at(idx), orange);
+    assert_eq!(&patch.pixels[idx * 2..idx * 2 + 2], &orange.to_le_bytes());
+    assert_eq!(
+        (0..patch.pixel_count())
+            .filter(|i| patch.pixel_at(*i) != 0)
+            .count(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_mint_region_nft() {
+    let program_id = solana_place::id();
+    let (mut banks_client, payer, game_player, recent_blockhash) = start_place_test().await;
+
+    let init_patch_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_init_patch(
+            program_id,
+            CANVAS_ID,
+            payer.pubkey(),
+            1,
+            0,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(init_patch_tx).await,
+        Ok(())
+    );
+
+    let (gameplay_token_pda, gameplay_token_ata) = purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        11111,
+        GameplayTokenType::PaintBrush,
+        recent_blockhash,
+    )
+    .await;
+
+    let set_pixel_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_set_pixel(
+            program_id,
+            CANVAS_ID,
+            game_player.pubkey(),
+            gameplay_token_pda,
+            gameplay_token_ata,
+            1,
+            0,
+            2,
+            2,
+            5,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(set_pixel_tx).await, Ok(()));
+
+    // Regions can only be minted from a frozen place
+
+    let unfrozen_state = get_place_state(&mut banks_client).await;
+    let early_mint_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_mint_region_nft(
+            &unfrozen_state,
+            payer.pubkey(),
+            0,
+            0,
+            1,
+            0,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(early_mint_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(36))
+        ))
+    );
+
+    let freeze_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_update_place_state(
+            payer.pubkey(),
+            UpdatePlaceStateDataArgs {
+                canvas_id: CANVAS_ID,
+                paused: Some(PauseFlags::FROZEN),
+                ..Default::default()
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(freeze_tx).await, Ok(()));
+
+    let state = get_place_state(&mut banks_client).await;
+
+    // Only the owner can mint regions
+
+    let not_owner_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_mint_region_nft(
+            &state,
+            game_player.pubkey(),
+            0,
+            0,
+            1,
+            0,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(not_owner_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(7))
+        ))
+    );
+
+    let bad_bounds_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_mint_region_nft(
+            &state,
+            payer.pubkey(),
+            1,
+            0,
+            0,
+            0,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(bad_bounds_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(46))
+        ))
+    );
+
+    // Mint the two patches (0, 0) and (1, 0), only the second one was initialized
+
+    let mint_region_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_mint_region_nft(
+            &state,
+            payer.pubkey(),
+            0,
+            0,
+            1,
+            0,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(mint_region_tx).await,
+        Ok(())
+    );
+
+    let (patch_pda, _) = find_address_for_patch(CANVAS_ID, 1, 0, &program_id);
+    let patch: Patch = banks_client
+        .get_account_data_with_borsh(patch_pda)
+        .await
+        .unwrap();
+    let blank_pixels = vec![0u8; PATCH_SIZE_PX * PATCH_SIZE_PX];
+    let expected_hash =
+        solana_place::state::CanvasSnapshot::chain_patch_hash(&[0; 32], 0, 0, &blank_pixels);
+    let expected_hash =
+        solana_place::state::CanvasSnapshot::chain_patch_hash(&expected_hash, 1, 0, &patch.pixels);
+
+    let (region_meta_pda, _) = RegionNftMeta::pda(CANVAS_ID, state.frozen_at_slot, 0, 0, 1, 0);
+    let region_meta_acct = banks_client
+        .get_account(region_meta_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let region_meta = RegionNftMeta::from_bytes(&region_meta_acct.data).unwrap();
+    assert_eq!(region_meta.canvas_id, CANVAS_ID);
+    assert_eq!(region_meta.frozen_at_slot, state.frozen_at_slot);
+    assert_eq!(
+        (
+            region_meta.x_min,
+            region_meta.y_min,
+            region_meta.x_max,
+            region_meta.y_max
+        ),
+        (0, 0, 1, 0)
+    );
+    assert_eq!(region_meta.pixel_hash, expected_hash);
+
+    let region_ata = spl_associated_token_account::get_associated_token_address(
+        &payer.pubkey(),
+        &region_meta.token_mint_pda,
+    );
+    let region_ata_acct = banks_client.get_account(region_ata).await.unwrap().unwrap();
+    let region_ata_parsed = TokenAccount::unpack(&region_ata_acct.data).unwrap();
+    assert_eq!(region_ata_parsed.amount, 1);
+
+    let region_mint_acct = banks_client
+        .get_account(region_meta.token_mint_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let region_mint = spl_token::state::Mint::unpack(&region_mint_acct.data).unwrap();
+    assert_eq!(region_mint.supply, 1);
+    assert!(region_mint.mint_authority.is_none());
+
+    let (region_mpl_meta_pda, _) =
+        RegionNftMeta::token_metadata_pda(CANVAS_ID, state.frozen_at_slot, 0, 0, 1, 0);
+    let region_mpl_meta_acct = banks_client
+        .get_account(region_mpl_meta_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let region_mpl_meta: mpl_token_metadata::state::Metadata =
+        try_from_slice_unchecked(&region_mpl_meta_acct.data).unwrap();
+    assert_eq!(
+        region_mpl_meta.data.uri.trim_matches(char::from(0)),
+        region_meta.token_uri()
+    );
+    assert!(region_meta
+        .token_uri()
+        .starts_with(solana_place::config::BASE_URL));
+
+    // Each region can only be minted once per freeze
+
+    let (recent_blockhash2, _) = banks_client
+        .get_latest_blockhash_with_commitment(CommitmentLevel::Confirmed)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let remint_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_mint_region_nft(
+            &state,
+            payer.pubkey(),
+            0,
+            0,
+            1,
+            0,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash2,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(remint_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(47))
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_shield() {
+    let program_id = solana_place::id();
+    let (mut banks_client, payer, game_player, recent_blockhash) = start_place_test().await;
+
+    let init_patch_tx = Transaction::new_signed_with_payer(
+        &[
+            instruction::get_ix_init_patch(program_id, CANVAS_ID, payer.pubkey(), 0, 0),
+            instruction::get_ix_init_patch(program_id, CANVAS_ID, payer.pubkey(), 1, 0),
+        ],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(init_patch_tx).await,
+        Ok(())
+    );
+
+    let mut tokens = vec![];
+    for (random_seed, token_type) in [
+        (12121, GameplayTokenType::Shield),
+        (12122, GameplayTokenType::PaintBrush),
+        (12123, GameplayTokenType::PaintBrush),
+        (12124, GameplayTokenType::Bomb),
+        (12125, GameplayTokenType::Shield),
+    ] {
+        tokens.push(
+            purchase_gameplay_token(
+                &mut banks_client,
+                &game_player,
+                random_seed,
+                token_type,
+                recent_blockhash,
+            )
+            .await,
+        );
+    }
+    let (shield_pda, shield_ata) = tokens[0];
+    let (brush_pda, brush_ata) = tokens[1];
+    let (brush2_pda, brush2_ata) = tokens[2];
+    let (bomb_pda, bomb_ata) = tokens[3];
+    let (shield2_pda, shield2_ata) = tokens[4];
+
+    // Only shield tokens can be activated
+
+    let not_shield_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_activate_shield(
+            program_id,
+            CANVAS_ID,
+            game_player.pubkey(),
+            brush_pda,
+            brush_ata,
+            0,
+            0,
+            5,
+            5,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(not_shield_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(31))
+        ))
+    );
+
+    let shield_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_activate_shield(
+            program_id,
+            CANVAS_ID,
+            game_player.pubkey(),
+            shield_pda,
+            shield_ata,
+            0,
+            0,
+            5,
+            5,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(shield_tx).await, Ok(()));
+
+    let (patch_pda, _) = find_address_for_patch(CANVAS_ID, 0, 0, &program_id);
+    let patch: Patch = banks_client
+        .get_account_data_with_borsh(patch_pda)
+        .await
+        .unwrap();
+    let current_slot = banks_client.get_root_slot().await.unwrap();
+    assert_eq!(
+        (
+            patch.shield.x_min,
+            patch.shield.y_min,
+            patch.shield.x_max,
+            patch.shield.y_max
+        ),
+        (3, 3, 7, 7)
+    );
+    assert!(patch.shield.is_active(current_slot));
+
+    // Every painting instruction is rejected inside the shielded area
+
+    let shielded_set_pixel_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_set_pixel(
+            program_id,
+            CANVAS_ID,
+            game_player.pubkey(),
+            brush_pda,
+            brush_ata,
+            0,
+            0,
+            5,
+            5,
+            1,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(shielded_set_pixel_tx)
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(50))
+        ))
+    );
+
+    let shielded_set_pixels_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_set_pixels(
+            program_id,
+            CANVAS_ID,
+            game_player.pubkey(),
+            brush2_pda,
+            brush2_ata,
+            0,
+            0,
+            vec![
+                instruction::PixelWrite {
+                    x_offset: 9,
+                    y_offset: 9,
+                    pixel: 1,
+                },
+                instruction::PixelWrite {
+                    x_offset: 7,
+                    y_offset: 7,
+                    pixel: 1,
+                },
+            ],
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(shielded_set_pixels_tx)
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(50))
+        ))
+    );
+
+    let state = get_place_state(&mut banks_client).await;
+    let shielded_bomb_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_detonate_bomb(
+            &state,
+            game_player.pubkey(),
+            bomb_pda,
+            bomb_ata,
+            0,
+            0,
+            10,
+            10,
+            1,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(shielded_bomb_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(50))
+        ))
+    );
+
+    let patch: Patch = banks_client
+        .get_account_data_with_borsh(patch_pda)
+        .await
+        .unwrap();
+    assert!(patch.pixels.iter().all(|p| *p == 0));
+
+    // Pixels outside the shielded area can still be painted
+
+    let set_pixel_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_set_pixel(
+            program_id,
+            CANVAS_ID,
+            game_player.pubkey(),
+            brush_pda,
+            brush_ata,
+            0,
+            0,
+            8,
+            8,
+            1,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(set_pixel_tx).await, Ok(()));
+
+    // A patch can only have one active shield
+
+    let second_shield_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_activate_shield(
+            program_id,
+            CANVAS_ID,
+            game_player.pubkey(),
+            shield2_pda,
+            shield2_ata,
+            0,
+            0,
+            15,
+            15,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(second_shield_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(51))
+        ))
+    );
+
+    // Shields are clipped to the edges of their patch
+
+    let edge_shield_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_activate_shield(
+            program_id,
+            CANVAS_ID,
+            game_player.pubkey(),
+            shield2_pda,
+            shield2_ata,
+            1,
+            0,
+            0,
+            (PATCH_SIZE_PX - 1) as u8,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(edge_shield_tx).await,
+        Ok(())
+    );
+
+    let (edge_patch_pda, _) = find_address_for_patch(CANVAS_ID, 1, 0, &program_id);
+    let edge_patch: Patch = banks_client
+        .get_account_data_with_borsh(edge_patch_pda)
+        .await
+        .unwrap();
+    let max_offset = (PATCH_SIZE_PX - 1) as u8;
+    assert_eq!(
+        (
+            edge_patch.shield.x_min,
+            edge_patch.shield.y_min,
+            edge_patch.shield.x_max,
+            edge_patch.shield.y_max
+        ),
+        (0, max_offset - 2, 2, max_offset)
+    );
+}
+
+#[tokio::test]
+async fn test_treasury() {
+    let (mut banks_client, payer, game_player, recent_blockhash) = start_place_test().await;
+
+    let (treasury_pda, _) = PlaceState::treasury_pda(CANVAS_ID);
+    let rent = banks_client.get_rent().await.unwrap();
+
+    let random_seed = 31313;
+    let (gameplay_token_pda, _) = purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        random_seed,
+        GameplayTokenType::PaintBrush,
+        recent_blockhash,
+    )
+    .await;
+
+    // The purchase price lands in the treasury, the gameplay token only holds its rent
+
+    let treasury_balance = banks_client.get_balance(treasury_pda).await.unwrap();
+    assert_eq!(
+        treasury_balance,
+        solana_place::state::DEFAULT_PAINTBRUSH_PRICE
+    );
+
+    let gameplay_token_balance = banks_client.get_balance(gameplay_token_pda).await.unwrap();
+    assert_eq!(
+        gameplay_token_balance,
+        rent.minimum_balance(GameplayTokenMeta::LEN)
+    );
+
+    // Only the owner or treasurer can withdraw
+
+    let destination = Keypair::new();
+    let not_owner_withdraw_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_withdraw_treasury(
+            CANVAS_ID,
+            game_player.pubkey(),
+            destination.pubkey(),
+            treasury_balance,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(not_owner_withdraw_tx)
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(64))
+        ))
+    );
+
+    let overdraw_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_withdraw_treasury(
+            CANVAS_ID,
+            payer.pubkey(),
+            destination.pubkey(),
+            treasury_balance + 1,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(overdraw_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(53))
+        ))
+    );
+
+    let withdraw_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_withdraw_treasury(
+            CANVAS_ID,
+            payer.pubkey(),
+            destination.pubkey(),
+            treasury_balance,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(withdraw_tx).await, Ok(()));
+
+    assert_eq!(banks_client.get_balance(treasury_pda).await.unwrap(), 0);
+    assert_eq!(
+        banks_client
+            .get_balance(destination.pubkey())
+            .await
+            .unwrap(),
+        treasury_balance
+    );
+
+    // Lamports stranded in a gameplay token account can be swept into the treasury
+
+    let stranded_lamports = 250_000_000;
+    let strand_tx = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &payer.pubkey(),
+            &gameplay_token_pda,
+            stranded_lamports,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(strand_tx).await, Ok(()));
+
+    let sweep_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_sweep_gameplay_tokens(
+            CANVAS_ID,
+            &[random_seed],
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(sweep_tx).await, Ok(()));
+
+    assert_eq!(
+        banks_client.get_balance(gameplay_token_pda).await.unwrap(),
+        rent.minimum_balance(GameplayTokenMeta::LEN)
+    );
+    assert_eq!(
+        banks_client.get_balance(treasury_pda).await.unwrap(),
+        stranded_lamports
+    );
+
+    // Gameplay tokens from another canvas are refused
+
+    let wrong_canvas_sweep_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_sweep_gameplay_tokens(
+            CANVAS_ID + 1,
+            &[random_seed],
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(wrong_canvas_sweep_tx)
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(43))
+        ))
+    );
+}
+
+/// A gameplay token meta as the first deployment of the program wrote them, before versions,
+/// canvases, purchase prices or upgrades existed
+fn baseline_gameplay_token_meta(random_seed: u64, place_tokens_owed: u32) -> Vec<u8> {
+    let mut data = vec![PlaceAccountType::GameplayTokenMeta as u8];
+    data.push(GameplayTokenType::PaintBrush as u8);
+    data.extend_from_slice(&3u64.to_le_bytes()); // created_at_slot
+    data.extend_from_slice(&random_seed.to_le_bytes());
+    data.extend_from_slice(GameplayTokenMeta::token_mint_pda(random_seed).0.as_ref());
+    data.extend_from_slice(&5u64.to_le_bytes()); // update_allowed_slot
+    data.extend_from_slice(&750u64.to_le_bytes()); // cooldown_duration
+    data.extend_from_slice(&place_tokens_owed.to_le_bytes());
+    assert_eq!(data.len(), 70);
+    data
+}
+
+#[tokio::test]
+async fn test_sweep_baseline_gameplay_tokens() {
+    // before the treasury existed, purchase prices were paid into the meta account itself
+    let random_seed = 51515;
+    let stranded_lamports = solana_place::state::DEFAULT_PAINTBRUSH_PRICE;
+    let mut meta_acct = program_owned_account(baseline_gameplay_token_meta(random_seed, 4));
+    let rent_exempt_lamports = meta_acct.lamports;
+    meta_acct.lamports += stranded_lamports;
+
+    let (gameplay_token_pda, _) = GameplayTokenMeta::pda(random_seed);
+    let mut pt = place_program_test();
+    pt.add_account(gameplay_token_pda, meta_acct);
+
+    let (mut banks_client, _payer, game_player, recent_blockhash) =
+        start_place_test_with_program(pt, None, None, None, None).await;
+
+    let mut mismatched_sweep_ix =
+        instruction::get_ix_sweep_gameplay_tokens(CANVAS_ID, &[random_seed]);
+    mismatched_sweep_ix.accounts.pop();
+    let mismatched_sweep_tx = Transaction::new_signed_with_payer(
+        &[mismatched_sweep_ix],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(mismatched_sweep_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(6))
+        ))
+    );
+
+    let sweep_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_sweep_gameplay_tokens(
+            CANVAS_ID,
+            &[random_seed],
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(sweep_tx).await, Ok(()));
+
+    let (treasury_pda, _) = PlaceState::treasury_pda(CANVAS_ID);
+    assert_eq!(
+        banks_client.get_balance(treasury_pda).await.unwrap(),
+        stranded_lamports
+    );
+
+    // the account itself is untouched, it still needs migrating to be used
+    let meta_acct = banks_client
+        .get_account(gameplay_token_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(meta_acct.lamports, rent_exempt_lamports);
+    assert_eq!(meta_acct.data, baseline_gameplay_token_meta(random_seed, 4));
+}
+
+#[tokio::test]
+async fn test_price_curve() {
+    let (mut banks_client, payer, game_player, recent_blockhash) = start_place_test().await;
+
+    let base_price = solana_place::state::DEFAULT_PAINTBRUSH_PRICE;
+    let step = 1_000_000;
+    let decay_slots = 1_000_000;
+
+    let linear_curve_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_update_place_state(
+            payer.pubkey(),
+            UpdatePlaceStateDataArgs {
+                canvas_id: CANVAS_ID,
+                price_curve: Some(PriceCurve {
+                    kind: PriceCurveKind::Linear,
+                    step,
+                    decay_slots,
+                    max_price: 0,
+                }),
+                ..Default::default()
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(linear_curve_tx).await,
+        Ok(())
+    );
+
+    // Nothing has been sold yet, so the first token goes for the base price
+
+    purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        41414,
+        GameplayTokenType::PaintBrush,
+        recent_blockhash,
+    )
+    .await;
+
+    let state = get_place_state(&mut banks_client).await;
+    assert_eq!(state.paintbrush_sales.recent_sales, 1);
+    assert_eq!(state.bomb_sales.recent_sales, 0);
+
+    let current_slot = banks_client.get_root_slot().await.unwrap();
+    let next_price = state.current_price(GameplayTokenType::PaintBrush, current_slot);
+    assert_eq!(next_price, base_price + step);
+
+    // The base price is now stale
+
+    let stale_price_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_purchase_gameplay_token(
+            CANVAS_ID,
+            game_player.pubkey(),
+            41415,
+            GameplayTokenType::PaintBrush,
+            base_price,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(stale_price_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(11))
+        ))
+    );
+
+    let curve_price_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_purchase_gameplay_token(
+            CANVAS_ID,
+            game_player.pubkey(),
+            41415,
+            GameplayTokenType::PaintBrush,
+            next_price,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(curve_price_tx).await,
+        Ok(())
+    );
+
+    // Each token type follows its own sales
+
+    purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        41416,
+        GameplayTokenType::Bomb,
+        recent_blockhash,
+    )
+    .await;
+
+    let state = get_place_state(&mut banks_client).await;
+    assert_eq!(state.paintbrush_sales.recent_sales, 2);
+    assert_eq!(state.bomb_sales.recent_sales, 1);
+
+    // Sales are forgotten as slots pass without another sale
+
+    let last_sale_slot = state.paintbrush_sales.last_sale_slot;
+    assert_eq!(
+        state.current_price(GameplayTokenType::PaintBrush, last_sale_slot),
+        base_price + 2 * step
+    );
+    assert_eq!(
+        state.current_price(GameplayTokenType::PaintBrush, last_sale_slot + decay_slots),
+        base_price + step
+    );
+    assert_eq!(
+        state.current_price(
+            GameplayTokenType::PaintBrush,
+            last_sale_slot + 5 * decay_slots
+        ),
+        base_price
+    );
+
+    // Exponential curves compound, and the price never goes above the max price
+
+    let exponential_curve = PriceCurve {
+        kind: PriceCurveKind::Exponential,
+        step: 5_000,
+        decay_slots,
+        max_price: 0,
+    };
+    assert_eq!(exponential_curve.price(base_price, 0), base_price);
+    assert_eq!(exponential_curve.price(base_price, 2), base_price * 9 / 4);
+    assert!(
+        exponential_curve.price(base_price, u64::MAX) >= exponential_curve.price(base_price, 1000)
+    );
+
+    let capped_curve = PriceCurve {
+        max_price: base_price * 2,
+        ..exponential_curve
+    };
+
+    let exponential_curve_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_update_place_state(
+            payer.pubkey(),
+            UpdatePlaceStateDataArgs {
+                canvas_id: CANVAS_ID,
+                price_curve: Some(capped_curve),
+                ..Default::default()
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(exponential_curve_tx).await,
+        Ok(())
+    );
+
+    let capped_price_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_purchase_gameplay_token(
+            CANVAS_ID,
+            game_player.pubkey(),
+            41417,
+            GameplayTokenType::PaintBrush,
+            base_price * 2,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(capped_price_tx).await,
+        Ok(())
+    );
+}
+
+#[tokio::test]
+async fn test_retire_gameplay_token() {
+    let (mut banks_client, payer, game_player, recent_blockhash) = start_place_test().await;
+
+    let refund_bps = 5_000;
+
+    let invalid_refund_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_update_place_state(
+            payer.pubkey(),
+            UpdatePlaceStateDataArgs {
+                canvas_id: CANVAS_ID,
+                retire_refund_bps: Some(10_001),
+                ..Default::default()
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(invalid_refund_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(54))
+        ))
+    );
+
+    let (place_token_mint_pda, _) = PlaceState::token_mint_pda(CANVAS_ID);
+    let place_tokens_ata = spl_associated_token_account::get_associated_token_address(
+        &game_player.pubkey(),
+        &place_token_mint_pda,
+    );
+
+    let setup_tx = Transaction::new_signed_with_payer(
+        &[
+            instruction::get_ix_update_place_state(
+                payer.pubkey(),
+                UpdatePlaceStateDataArgs {
+                    canvas_id: CANVAS_ID,
+                    retire_refund_bps: Some(refund_bps),
+                    ..Default::default()
+                },
+            ),
+            instruction::get_ix_init_mint(CANVAS_ID, payer.pubkey()),
+            spl_associated_token_account::create_associated_token_account(
+                &payer.pubkey(),
+                &game_player.pubkey(),
+                &place_token_mint_pda,
+            ),
+        ],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(setup_tx).await, Ok(()));
+
+    let random_seed = 51515;
+    let (gameplay_token_pda, gameplay_token_ata) = purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        random_seed,
+        GameplayTokenType::PaintBrush,
+        recent_blockhash,
+    )
+    .await;
+
+    let gameplay_token_meta: GameplayTokenMeta = banks_client
+        .get_account_data_with_borsh(gameplay_token_pda)
+        .await
+        .unwrap();
+    assert_eq!(
+        gameplay_token_meta.purchase_price,
+        solana_place::state::DEFAULT_PAINTBRUSH_PRICE
+    );
+    let tokens_owed = gameplay_token_meta.place_tokens_owed as u64;
+    assert!(tokens_owed > 0);
+
+    let (treasury_pda, _) = PlaceState::treasury_pda(CANVAS_ID);
+    let treasury_balance_before = banks_client.get_balance(treasury_pda).await.unwrap();
+    let player_balance_before = banks_client
+        .get_balance(game_player.pubkey())
+        .await
+        .unwrap();
+    let gameplay_token_rent = banks_client.get_balance(gameplay_token_pda).await.unwrap();
+
+    let retire_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_retire_gameplay_token(
+            CANVAS_ID,
+            game_player.pubkey(),
+            place_tokens_ata,
+            random_seed,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(retire_tx).await, Ok(()));
+
+    // Owed place tokens were paid out before the meta account was closed
+
+    let place_tokens_acct = banks_client
+        .get_account(place_tokens_ata)
+        .await
+        .unwrap()
+        .unwrap();
+    let place_tokens = TokenAccount::unpack(&place_tokens_acct.data).unwrap();
+    assert_eq!(place_tokens.amount, tokens_owed);
+
+    // The NFT is burned and every account that could be closed is gone
+
+    assert_matches!(banks_client.get_account(gameplay_token_pda).await, Ok(None));
+    assert_matches!(banks_client.get_account(gameplay_token_ata).await, Ok(None));
+
+    let (gameplay_token_mint_pda, _) = GameplayTokenMeta::token_mint_pda(random_seed);
+    let gameplay_token_mint_acct = banks_client
+        .get_account(gameplay_token_mint_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let gameplay_token_mint =
+        spl_token::state::Mint::unpack(&gameplay_token_mint_acct.data).unwrap();
+    assert_eq!(gameplay_token_mint.supply, 0);
+
+    // The holder gets the rent back plus the refund share of the purchase price
+
+    let refund = solana_place::state::DEFAULT_PAINTBRUSH_PRICE * refund_bps as u64 / 10_000;
+    let treasury_balance_after = banks_client.get_balance(treasury_pda).await.unwrap();
+    assert_eq!(treasury_balance_before - treasury_balance_after, refund);
+
+    let player_balance_after = banks_client
+        .get_balance(game_player.pubkey())
+        .await
+        .unwrap();
+    assert!(player_balance_after > player_balance_before + refund + gameplay_token_rent);
+
+    // A retired token can't be retired twice, paid for by the payer so the tx isn't a duplicate
+
+    let retire_again_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_retire_gameplay_token(
+            CANVAS_ID,
+            game_player.pubkey(),
+            place_tokens_ata,
+            random_seed,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer, &game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(retire_again_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(24))
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_upgrade_gameplay_token() {
+    let (mut banks_client, payer, game_player, recent_blockhash) = start_place_test().await;
+
+    let schedule = UpgradeSchedule {
+        base_cost: 1,
+        cooldown_step: 300,
+        min_cooldown: 200,
+        max_level: 2,
+    };
+
+    let (place_token_mint_pda, _) = PlaceState::token_mint_pda(CANVAS_ID);
+    let place_tokens_ata = spl_associated_token_account::get_associated_token_address(
+        &game_player.pubkey(),
+        &place_token_mint_pda,
+    );
+
+    let setup_tx = Transaction::new_signed_with_payer(
+        &[
+            instruction::get_ix_update_place_state(
+                payer.pubkey(),
+                UpdatePlaceStateDataArgs {
+                    canvas_id: CANVAS_ID,
+                    upgrade_schedule: Some(schedule),
+                    ..Default::default()
+                },
+            ),
+            instruction::get_ix_init_mint(CANVAS_ID, payer.pubkey()),
+            spl_associated_token_account::create_associated_token_account(
+                &payer.pubkey(),
+                &game_player.pubkey(),
+                &place_token_mint_pda,
+            ),
+        ],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(setup_tx).await, Ok(()));
+
+    let random_seed = 61616;
+    let (gameplay_token_pda, _) = purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        random_seed,
+        GameplayTokenType::PaintBrush,
+        recent_blockhash,
+    )
+    .await;
+
+    let (gameplay_token_mint_pda, _) = GameplayTokenMeta::token_mint_pda(random_seed);
+    let claim_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_claim_tokens(
+            CANVAS_ID,
+            game_player.pubkey(),
+            place_tokens_ata,
+            gameplay_token_mint_pda,
+            random_seed,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(claim_tx).await, Ok(()));
+
+    let gameplay_token_meta: GameplayTokenMeta = banks_client
+        .get_account_data_with_borsh(gameplay_token_pda)
+        .await
+        .unwrap();
+    assert_eq!(gameplay_token_meta.upgrade_level, 0);
+    assert_eq!(
+        gameplay_token_meta.cooldown_duration,
+        solana_place::state::DEFAULT_PAINTBRUSH_COOLDOWN
+    );
+
+    // The holder has to accept the cost of the next level
+
+    let too_cheap_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_upgrade_gameplay_token(
+            CANVAS_ID,
+            game_player.pubkey(),
+            random_seed,
+            0,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(too_cheap_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(57))
+        ))
+    );
+
+    // Each level burns more place tokens and lowers the cooldown down to the floor
+
+    for (max_cost, expected_cooldown, expected_place_tokens) in [
+        (1, solana_place::state::DEFAULT_PAINTBRUSH_COOLDOWN - 300, 4),
+        (2, 200, 2),
+    ] {
+        let upgrade_tx = Transaction::new_signed_with_payer(
+            &[instruction::get_ix_upgrade_gameplay_token(
+                CANVAS_ID,
+                game_player.pubkey(),
+                random_seed,
+                max_cost,
+            )],
+            Some(&game_player.pubkey()),
+            &[&game_player],
+            recent_blockhash,
+        );
+
+        assert_matches!(banks_client.process_transaction(upgrade_tx).await, Ok(()));
+
+        let gameplay_token_meta: GameplayTokenMeta = banks_client
+            .get_account_data_with_borsh(gameplay_token_pda)
+            .await
+            .unwrap();
+        assert_eq!(gameplay_token_meta.upgrade_level, max_cost as u8);
+        assert_eq!(gameplay_token_meta.cooldown_duration, expected_cooldown);
+
+        let place_tokens_acct = banks_client
+            .get_account(place_tokens_ata)
+            .await
+            .unwrap()
+            .unwrap();
+        let place_tokens = TokenAccount::unpack(&place_tokens_acct.data).unwrap();
+        assert_eq!(place_tokens.amount, expected_place_tokens);
+    }
+
+    let max_level_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_upgrade_gameplay_token(
+            CANVAS_ID,
+            game_player.pubkey(),
+            random_seed,
+            3,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(max_level_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(55))
+        ))
+    );
+
+    // Raising the max level doesn't allow going below the cooldown floor
+
+    for (schedule, max_cost, expected_error) in [
+        (
+            UpgradeSchedule {
+                max_level: 5,
+                ..schedule
+            },
+            4,
+            56,
+        ),
+        // the token program refuses to burn more place tokens than the holder has
+        (
+            UpgradeSchedule {
+                max_level: 5,
+                min_cooldown: 0,
+                ..schedule
+            },
+            5,
+            1,
+        ),
+    ] {
+        let update_schedule_tx = Transaction::new_signed_with_payer(
+            &[instruction::get_ix_update_place_state(
+                payer.pubkey(),
+                UpdatePlaceStateDataArgs {
+                    canvas_id: CANVAS_ID,
+                    upgrade_schedule: Some(schedule),
+                    ..Default::default()
+                },
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+
+        assert_matches!(
+            banks_client.process_transaction(update_schedule_tx).await,
+            Ok(())
+        );
+
+        let upgrade_tx = Transaction::new_signed_with_payer(
+            &[instruction::get_ix_upgrade_gameplay_token(
+                CANVAS_ID,
+                game_player.pubkey(),
+                random_seed,
+                max_cost,
+            )],
+            Some(&game_player.pubkey()),
+            &[&game_player],
+            recent_blockhash,
+        );
+
+        assert_matches!(
+            banks_client.process_transaction(upgrade_tx).await,
+            Err(TransportError::TransactionError(
+                TransactionError::InstructionError(0, InstructionError::Custom(code))
+            )) if code == expected_error
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_purchase_with_place_tokens() {
+    let (mut banks_client, payer, game_player, recent_blockhash) = start_place_test().await;
+
+    let (place_token_mint_pda, _) = PlaceState::token_mint_pda(CANVAS_ID);
+    let place_tokens_ata = spl_associated_token_account::get_associated_token_address(
+        &game_player.pubkey(),
+        &place_token_mint_pda,
+    );
+
+    let setup_tx = Transaction::new_signed_with_payer(
+        &[
+            instruction::get_ix_init_mint(CANVAS_ID, payer.pubkey()),
+            spl_associated_token_account::create_associated_token_account(
+                &payer.pubkey(),
+                &game_player.pubkey(),
+                &place_token_mint_pda,
+            ),
+        ],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(setup_tx).await, Ok(()));
+
+    // Earn some place tokens with a gameplay token bought with lamports
+
+    let random_seed = 71717;
+    purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        random_seed,
+        GameplayTokenType::PaintBrush,
+        recent_blockhash,
+    )
+    .await;
+
+    let (gameplay_token_mint_pda, _) = GameplayTokenMeta::token_mint_pda(random_seed);
+    let claim_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_claim_tokens(
+            CANVAS_ID,
+            game_player.pubkey(),
+            place_tokens_ata,
+            gameplay_token_mint_pda,
+            random_seed,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(claim_tx).await, Ok(()));
+
+    let get_place_token_amount = |acct: Option<solana_sdk::account::Account>| {
+        TokenAccount::unpack(&acct.unwrap().data).unwrap().amount
+    };
+    let earned = get_place_token_amount(banks_client.get_account(place_tokens_ata).await.unwrap());
+    assert_eq!(earned, 5);
+
+    // Place token payments are disabled until the owner prices them
+
+    let disabled_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_purchase_gameplay_token_with_payment(
+            CANVAS_ID,
+            game_player.pubkey(),
+            71718,
+            GameplayTokenType::PaintBrush,
+            0,
+            instruction::PaymentMethod::PlaceTokens,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(disabled_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(58))
+        ))
+    );
+
+    let pricing = PlaceTokenPricing {
+        paintbrush_price: 2,
+        bomb_price: 3,
+        shield_price: 0,
+        burn_payments: true,
+    };
+
+    let update_pricing_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_update_place_state(
+            payer.pubkey(),
+            UpdatePlaceStateDataArgs {
+                canvas_id: CANVAS_ID,
+                place_token_pricing: Some(pricing),
+                ..Default::default()
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(update_pricing_tx).await,
+        Ok(())
+    );
+
+    // The desired price is checked against the place token price
+
+    let wrong_price_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_purchase_gameplay_token_with_payment(
+            CANVAS_ID,
+            game_player.pubkey(),
+            71718,
+            GameplayTokenType::PaintBrush,
+            solana_place::state::DEFAULT_PAINTBRUSH_PRICE,
+            instruction::PaymentMethod::PlaceTokens,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(wrong_price_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(11))
+        ))
+    );
+
+    // The place token accounts are only passed for place token payments, and are required then
+
+    let mut missing_accounts_ix = instruction::get_ix_purchase_gameplay_token_with_payment(
+        CANVAS_ID,
+        game_player.pubkey(),
+        71718,
+        GameplayTokenType::PaintBrush,
+        pricing.paintbrush_price,
+        instruction::PaymentMethod::PlaceTokens,
+    );
+    let lamports_ix = instruction::get_ix_purchase_gameplay_token(
+        CANVAS_ID,
+        game_player.pubkey(),
+        71718,
+        GameplayTokenType::PaintBrush,
+        solana_place::state::DEFAULT_PAINTBRUSH_PRICE,
+    );
+    assert_eq!(
+        missing_accounts_ix.accounts.len(),
+        lamports_ix.accounts.len() + 3
+    );
+    missing_accounts_ix
+        .accounts
+        .truncate(lamports_ix.accounts.len());
+
+    let missing_accounts_tx = Transaction::new_signed_with_payer(
+        &[missing_accounts_ix],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(missing_accounts_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(6))
+        ))
+    );
+
+    // Burned payments come out of the supply
+
+    let burn_purchase_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_purchase_gameplay_token_with_payment(
+            CANVAS_ID,
+            game_player.pubkey(),
+            71718,
+            GameplayTokenType::PaintBrush,
+            pricing.paintbrush_price,
+            instruction::PaymentMethod::PlaceTokens,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(burn_purchase_tx).await,
+        Ok(())
+    );
+
+    let gameplay_token_meta: GameplayTokenMeta = banks_client
+        .get_account_data_with_borsh(GameplayTokenMeta::pda(71718).0)
+        .await
+        .unwrap();
+    assert_eq!(
+        gameplay_token_meta.gameplay_type,
+        GameplayTokenType::PaintBrush
+    );
+    assert_eq!(gameplay_token_meta.purchase_price, 0);
+
+    let place_token_mint_acct = banks_client
+        .get_account(place_token_mint_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let place_token_mint = spl_token::state::Mint::unpack(&place_token_mint_acct.data).unwrap();
+    assert_eq!(place_token_mint.supply, earned - pricing.paintbrush_price);
+
+    // Payments that aren't burned go to the treasury's place token account
+
+    let treasury_pricing_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_update_place_state(
+            payer.pubkey(),
+            UpdatePlaceStateDataArgs {
+                canvas_id: CANVAS_ID,
+                place_token_pricing: Some(PlaceTokenPricing {
+                    burn_payments: false,
+                    ..pricing
+                }),
+                ..Default::default()
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(treasury_pricing_tx).await,
+        Ok(())
+    );
+
+    let treasury_purchase_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_purchase_gameplay_token_with_payment(
+            CANVAS_ID,
+            game_player.pubkey(),
+            71719,
+            GameplayTokenType::Bomb,
+            pricing.bomb_price,
+            instruction::PaymentMethod::PlaceTokens,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(treasury_purchase_tx).await,
+        Ok(())
+    );
+
+    let treasury_place_tokens = get_place_token_amount(
+        banks_client
+            .get_account(PlaceState::treasury_place_token_ata(CANVAS_ID))
+            .await
+            .unwrap(),
+    );
+    assert_eq!(treasury_place_tokens, pricing.bomb_price);
+    assert_eq!(
+        get_place_token_amount(banks_client.get_account(place_tokens_ata).await.unwrap()),
+        0
+    );
+
+    // The token program refuses payments the buyer can't cover
+
+    let broke_purchase_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_purchase_gameplay_token_with_payment(
+            CANVAS_ID,
+            game_player.pubkey(),
+            71720,
+            GameplayTokenType::PaintBrush,
+            pricing.paintbrush_price,
+            instruction::PaymentMethod::PlaceTokens,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(broke_purchase_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(1))
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_token_rewards() {
+    let program_id = solana_place::id();
+    let (mut banks_client, payer, game_player, recent_blockhash) = start_place_test().await;
+
+    let bomb_cooldown = 10;
+    let paintbrush_rewards = GameplayTokenRewards {
+        purchase_bonus: 7,
+        use_reward: 3,
+    };
+    let bomb_rewards = GameplayTokenRewards {
+        purchase_bonus: 0,
+        use_reward: 2,
+    };
+
+    let setup_tx = Transaction::new_signed_with_payer(
+        &[
+            instruction::get_ix_update_place_state(
+                payer.pubkey(),
+                UpdatePlaceStateDataArgs {
+                    canvas_id: CANVAS_ID,
+                    bomb_cooldown: Some(bomb_cooldown),
+                    paintbrush_rewards: Some(paintbrush_rewards),
+                    bomb_rewards: Some(bomb_rewards),
+                    ..Default::default()
+                },
+            ),
+            instruction::get_ix_init_patch(program_id, CANVAS_ID, payer.pubkey(), 0, 0),
+        ],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(setup_tx).await, Ok(()));
+
+    let (brush_pda, brush_ata) = purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        81818,
+        GameplayTokenType::PaintBrush,
+        recent_blockhash,
+    )
+    .await;
+
+    let (bomb_pda, bomb_ata) = purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        81819,
+        GameplayTokenType::Bomb,
+        recent_blockhash,
+    )
+    .await;
+
+    // Each token type gets its own cooldown and purchase bonus
+
+    let brush: GameplayTokenMeta = banks_client
+        .get_account_data_with_borsh(brush_pda)
+        .await
+        .unwrap();
+    assert_eq!(
+        brush.cooldown_duration,
+        solana_place::state::DEFAULT_PAINTBRUSH_COOLDOWN
+    );
+    assert_eq!(brush.place_tokens_owed, paintbrush_rewards.purchase_bonus);
+
+    let bomb: GameplayTokenMeta = banks_client
+        .get_account_data_with_borsh(bomb_pda)
+        .await
+        .unwrap();
+    assert_eq!(bomb.cooldown_duration, bomb_cooldown);
+    assert_eq!(bomb.place_tokens_owed, bomb_rewards.purchase_bonus);
+
+    // And earns its own reward per use
+
+    let state = get_place_state(&mut banks_client).await;
+    let use_tx = Transaction::new_signed_with_payer(
+        &[
+            instruction::get_ix_set_pixel(
+                program_id,
+                CANVAS_ID,
+                game_player.pubkey(),
+                brush_pda,
+                brush_ata,
+                0,
+                0,
+                1,
+                1,
+                1,
+            ),
+            instruction::get_ix_detonate_bomb(
+                &state,
+                game_player.pubkey(),
+                bomb_pda,
+                bomb_ata,
+                0,
+                0,
+                10,
+                10,
+                2,
+            ),
+        ],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(use_tx).await, Ok(()));
+
+    let brush: GameplayTokenMeta = banks_client
+        .get_account_data_with_borsh(brush_pda)
+        .await
+        .unwrap();
+    assert_eq!(
+        brush.place_tokens_owed,
+        paintbrush_rewards.purchase_bonus + paintbrush_rewards.use_reward
+    );
+
+    let bomb: GameplayTokenMeta = banks_client
+        .get_account_data_with_borsh(bomb_pda)
+        .await
+        .unwrap();
+    assert_eq!(bomb.place_tokens_owed, bomb_rewards.use_reward);
+}
+
+#[tokio::test]
+async fn test_claim_tokens_batch() {
+    let (mut banks_client, payer, game_player, recent_blockhash) = start_place_test().await;
+
+    let (place_token_mint_pda, _) = PlaceState::token_mint_pda(CANVAS_ID);
+    let place_tokens_ata = spl_associated_token_account::get_associated_token_address(
+        &game_player.pubkey(),
+        &place_token_mint_pda,
+    );
+
+    let setup_tx = Transaction::new_signed_with_payer(
+        &[
+            instruction::get_ix_init_mint(CANVAS_ID, payer.pubkey()),
+            spl_associated_token_account::create_associated_token_account(
+                &payer.pubkey(),
+                &game_player.pubkey(),
+                &place_token_mint_pda,
+            ),
+        ],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(setup_tx).await, Ok(()));
+
+    let random_seeds = [91919, 91920, 91921];
+    for random_seed in random_seeds {
+        purchase_gameplay_token(
+            &mut banks_client,
+            &game_player,
+            random_seed,
+            GameplayTokenType::PaintBrush,
+            recent_blockhash,
+        )
+        .await;
+    }
+
+    let purchase_bonus = solana_place::state::DEFAULT_PAINTBRUSH_REWARDS.purchase_bonus as u64;
+
+    // Claim one of them on its own so the batch has a meta with nothing owed
+
+    let (gameplay_token_mint_pda, _) = GameplayTokenMeta::token_mint_pda(random_seeds[0]);
+    let claim_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_claim_tokens(
+            CANVAS_ID,
+            game_player.pubkey(),
+            place_tokens_ata,
+            gameplay_token_mint_pda,
+            random_seeds[0],
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(claim_tx).await, Ok(()));
+
+    // Pairs have to be complete
+
+    let mut odd_accounts_ix = instruction::get_ix_claim_tokens_batch(
+        CANVAS_ID,
+        game_player.pubkey(),
+        place_tokens_ata,
+        &random_seeds,
+    );
+    odd_accounts_ix.accounts.pop();
+
+    let odd_accounts_tx = Transaction::new_signed_with_payer(
+        &[odd_accounts_ix],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(odd_accounts_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(6))
+        ))
+    );
+
+    // The claimed meta is skipped, and a meta passed twice only pays once
+
+    let batch_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_claim_tokens_batch(
+            CANVAS_ID,
+            game_player.pubkey(),
+            place_tokens_ata,
+            &[
+                random_seeds[0],
+                random_seeds[1],
+                random_seeds[1],
+                random_seeds[2],
+            ],
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(batch_tx).await, Ok(()));
+
+    let place_tokens_acct = banks_client
+        .get_account(place_tokens_ata)
+        .await
+        .unwrap()
+        .unwrap();
+    let place_tokens = TokenAccount::unpack(&place_tokens_acct.data).unwrap();
+    assert_eq!(place_tokens.amount, 3 * purchase_bonus);
+
+    for random_seed in random_seeds {
+        let gameplay_token_meta: GameplayTokenMeta = banks_client
+            .get_account_data_with_borsh(GameplayTokenMeta::pda(random_seed).0)
+            .await
+            .unwrap();
+        assert_eq!(gameplay_token_meta.place_tokens_owed, 0);
+    }
+
+    // A batch with nothing owed at all is refused
+
+    let empty_batch_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_claim_tokens_batch(
+            CANVAS_ID,
+            game_player.pubkey(),
+            place_tokens_ata,
+            &random_seeds,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(empty_batch_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(30))
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_emission_schedule() {
+    let (mut banks_client, payer, game_player, recent_blockhash) = start_place_test().await;
+
+    let (place_token_mint_pda, _) = PlaceState::token_mint_pda(CANVAS_ID);
+    let place_tokens_ata = spl_associated_token_account::get_associated_token_address(
+        &game_player.pubkey(),
+        &place_token_mint_pda,
+    );
+
+    // one window covers the whole test, so only claims move it along
+    let emission_schedule = EmissionSchedule {
+        max_supply: 12,
+        window_slots: 1_000_000,
+        tokens_per_window: 8,
+    };
+
+    let setup_tx = Transaction::new_signed_with_payer(
+        &[
+            instruction::get_ix_update_place_state(
+                payer.pubkey(),
+                UpdatePlaceStateDataArgs {
+                    canvas_id: CANVAS_ID,
+                    emission_schedule: Some(emission_schedule),
+                    ..Default::default()
+                },
+            ),
+            instruction::get_ix_init_mint(CANVAS_ID, payer.pubkey()),
+            spl_associated_token_account::create_associated_token_account(
+                &payer.pubkey(),
+                &game_player.pubkey(),
+                &place_token_mint_pda,
+            ),
+        ],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(setup_tx).await, Ok(()));
+
+    let random_seeds = [71717, 71718, 71719];
+    for random_seed in random_seeds {
+        purchase_gameplay_token(
+            &mut banks_client,
+            &game_player,
+            random_seed,
+            GameplayTokenType::PaintBrush,
+            recent_blockhash,
+        )
+        .await;
+    }
+
+    let purchase_bonus = solana_place::state::DEFAULT_PAINTBRUSH_REWARDS.purchase_bonus;
+    assert_eq!(purchase_bonus, 5);
+
+    let claim_ix = |random_seed: u64| {
+        let (gameplay_token_mint_pda, _) = GameplayTokenMeta::token_mint_pda(random_seed);
+        instruction::get_ix_claim_tokens(
+            CANVAS_ID,
+            game_player.pubkey(),
+            place_tokens_ata,
+            gameplay_token_mint_pda,
+            random_seed,
+        )
+    };
+
+    // A claim within the window budget pays out in full
+
+    let claim_tx = Transaction::new_signed_with_payer(
+        &[claim_ix(random_seeds[0])],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(claim_tx).await, Ok(()));
+
+    // The batch only gets the 3 tokens left in the window, the rest stays owed
+
+    let batch_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_claim_tokens_batch(
+            CANVAS_ID,
+            game_player.pubkey(),
+            place_tokens_ata,
+            &random_seeds[1..],
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(batch_tx).await, Ok(()));
+
+    let mut owed = vec![];
+    for random_seed in random_seeds {
+        let gameplay_token_meta: GameplayTokenMeta = banks_client
+            .get_account_data_with_borsh(GameplayTokenMeta::pda(random_seed).0)
+            .await
+            .unwrap();
+        owed.push(gameplay_token_meta.place_tokens_owed);
+    }
+    assert_eq!(owed, vec![0, 2, 5]);
+
+    let place_state = get_place_state(&mut banks_client).await;
+    assert_eq!(place_state.emitted_in_window, 8);
+
+    // With the window used up, claims are refused
+
+    let exhausted_tx = Transaction::new_signed_with_payer(
+        &[claim_ix(random_seeds[2])],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(exhausted_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(60))
+        ))
+    );
+
+    // Raising the window budget leaves the supply cap as the limit
+
+    let raise_window_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_update_place_state(
+            payer.pubkey(),
+            UpdatePlaceStateDataArgs {
+                canvas_id: CANVAS_ID,
+                emission_schedule: Some(EmissionSchedule {
+                    tokens_per_window: 100,
+                    ..emission_schedule
+                }),
+                ..Default::default()
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(raise_window_tx).await,
+        Ok(())
+    );
+
+    let batch_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_claim_tokens_batch(
+            CANVAS_ID,
+            game_player.pubkey(),
+            place_tokens_ata,
+            &random_seeds,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(batch_tx).await, Ok(()));
+
+    let place_token_mint_acct = banks_client
+        .get_account(place_token_mint_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let place_token_mint = spl_token::state::Mint::unpack(&place_token_mint_acct.data).unwrap();
+    assert_eq!(place_token_mint.supply, 12);
+
+    let gameplay_token_meta: GameplayTokenMeta = banks_client
+        .get_account_data_with_borsh(GameplayTokenMeta::pda(random_seeds[2]).0)
+        .await
+        .unwrap();
+    assert_eq!(gameplay_token_meta.place_tokens_owed, 3);
+
+    // Nothing more can be minted at the cap, and retiring would forfeit what is owed
+
+    let capped_tx = Transaction::new_signed_with_payer(
+        &[claim_ix(random_seeds[2])],
+        Some(&payer.pubkey()),
+        &[&payer, &game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(capped_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(60))
+        ))
+    );
+
+    let retire_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_retire_gameplay_token(
+            CANVAS_ID,
+            game_player.pubkey(),
+            place_tokens_ata,
+            random_seeds[2],
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(retire_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(60))
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_roles() {
+    let (mut banks_client, payer, game_player, recent_blockhash) = start_place_test().await;
+
+    let pricing_admin = Keypair::new();
+    let freeze_admin = Keypair::new();
+
+    let update_ix = |signer: Pubkey, paused: Option<PauseFlags>, paintbrush_price: Option<u64>| {
+        instruction::get_ix_update_place_state(
+            signer,
+            UpdatePlaceStateDataArgs {
+                canvas_id: CANVAS_ID,
+                paused,
+                paintbrush_price,
+                ..Default::default()
+            },
+        )
+    };
+
+    // Roles start out unassigned
+
+    let no_role_tx = Transaction::new_signed_with_payer(
+        &[update_ix(pricing_admin.pubkey(), None, Some(1))],
+        Some(&payer.pubkey()),
+        &[&payer, &pricing_admin],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(no_role_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(61))
+        ))
+    );
+
+    // Only the owner hands out roles, and super-admin isn't one of them
+
+    let not_owner_grant_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_grant_role(
+            CANVAS_ID,
+            game_player.pubkey(),
+            PlaceRole::PricingAdmin,
+            game_player.pubkey(),
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(not_owner_grant_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(7))
+        ))
+    );
+
+    let grant_super_admin_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_grant_role(
+            CANVAS_ID,
+            payer.pubkey(),
+            PlaceRole::SuperAdmin,
+            game_player.pubkey(),
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(grant_super_admin_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(65))
+        ))
+    );
+
+    let grant_tx = Transaction::new_signed_with_payer(
+        &[
+            instruction::get_ix_grant_role(
+                CANVAS_ID,
+                payer.pubkey(),
+                PlaceRole::PricingAdmin,
+                pricing_admin.pubkey(),
+            ),
+            instruction::get_ix_grant_role(
+                CANVAS_ID,
+                payer.pubkey(),
+                PlaceRole::FreezeAdmin,
+                freeze_admin.pubkey(),
+            ),
+        ],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(grant_tx).await, Ok(()));
+
+    let place_state = get_place_state(&mut banks_client).await;
+    assert_eq!(place_state.roles.pricing_admin, pricing_admin.pubkey());
+    assert_eq!(place_state.roles.freeze_admin, freeze_admin.pubkey());
+    assert_eq!(place_state.roles.moderator, Pubkey::default());
+    assert_eq!(place_state.roles.treasurer, Pubkey::default());
+
+    // Each admin can only touch the fields their role covers
+
+    let pricing_tx = Transaction::new_signed_with_payer(
+        &[update_ix(pricing_admin.pubkey(), None, Some(2))],
+        Some(&payer.pubkey()),
+        &[&payer, &pricing_admin],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(pricing_tx).await, Ok(()));
+    assert_eq!(get_place_state(&mut banks_client).await.paintbrush_price, 2);
+
+    let pricing_freeze_tx = Transaction::new_signed_with_payer(
+        &[update_ix(
+            pricing_admin.pubkey(),
+            Some(PauseFlags::FROZEN),
+            None,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer, &pricing_admin],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(pricing_freeze_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(62))
+        ))
+    );
+
+    let pricing_owner_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_propose_owner(
+            CANVAS_ID,
+            pricing_admin.pubkey(),
+            pricing_admin.pubkey(),
+        )],
+        Some(&payer.pubkey()),
+        &[&payer, &pricing_admin],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(pricing_owner_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(7))
+        ))
+    );
+
+    // Updates that set no role gated field are left to the owner
+
+    let empty_update_tx = Transaction::new_signed_with_payer(
+        &[update_ix(game_player.pubkey(), None, None)],
+        Some(&payer.pubkey()),
+        &[&payer, &game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(empty_update_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(7))
+        ))
+    );
+
+    let role_empty_update_tx = Transaction::new_signed_with_payer(
+        &[update_ix(freeze_admin.pubkey(), None, None)],
+        Some(&payer.pubkey()),
+        &[&payer, &freeze_admin],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(role_empty_update_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(7))
+        ))
+    );
+
+    let owner_empty_update_tx = Transaction::new_signed_with_payer(
+        &[update_ix(payer.pubkey(), None, None)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(owner_empty_update_tx)
+            .await,
+        Ok(())
+    );
+
+    let freeze_pricing_tx = Transaction::new_signed_with_payer(
+        &[update_ix(
+            freeze_admin.pubkey(),
+            Some(PauseFlags::FROZEN),
+            Some(3),
+        )],
+        Some(&payer.pubkey()),
+        &[&payer, &freeze_admin],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(freeze_pricing_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(61))
+        ))
+    );
+
+    let freeze_tx = Transaction::new_signed_with_payer(
+        &[
+            update_ix(freeze_admin.pubkey(), Some(PauseFlags::FROZEN), None),
+            update_ix(freeze_admin.pubkey(), Some(PauseFlags::NONE), None),
+        ],
+        Some(&payer.pubkey()),
+        &[&payer, &freeze_admin],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(freeze_tx).await, Ok(()));
+
+    // The treasurer role guards withdrawals
+
+    let not_treasurer_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_withdraw_treasury(
+            CANVAS_ID,
+            pricing_admin.pubkey(),
+            pricing_admin.pubkey(),
+            0,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer, &pricing_admin],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(not_treasurer_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(64))
+        ))
+    );
+
+    // Revoking takes the role away again
+
+    let revoke_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_revoke_role(
+            CANVAS_ID,
+            payer.pubkey(),
+            PlaceRole::PricingAdmin,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(revoke_tx).await, Ok(()));
+
+    let revoked_tx = Transaction::new_signed_with_payer(
+        &[update_ix(pricing_admin.pubkey(), None, Some(4))],
+        Some(&payer.pubkey()),
+        &[&payer, &pricing_admin],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(revoked_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(61))
+        ))
+    );
+
+    let place_state = get_place_state(&mut banks_client).await;
+    assert_eq!(place_state.roles.pricing_admin, Pubkey::default());
+    assert_eq!(place_state.paintbrush_price, 2);
+}
+
+#[tokio::test]
+async fn test_ownership_transfer() {
+    let (mut banks_client, payer, game_player, recent_blockhash) = start_place_test().await;
+
+    let new_owner = Keypair::new();
+
+    // Nothing to accept or cancel before a proposal
+
+    let early_accept_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_accept_ownership(
+            CANVAS_ID,
+            new_owner.pubkey(),
+        )],
+        Some(&payer.pubkey()),
+        &[&payer, &new_owner],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(early_accept_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(67))
+        ))
+    );
+
+    // Only the owner can propose
+
+    let not_owner_propose_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_propose_owner(
+            CANVAS_ID,
+            game_player.pubkey(),
+            game_player.pubkey(),
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(not_owner_propose_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(7))
+        ))
+    );
+
+    // A proposal doesn't move ownership, and can be cancelled
+
+    let propose_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_propose_owner(
+            CANVAS_ID,
+            payer.pubkey(),
+            game_player.pubkey(),
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(propose_tx).await, Ok(()));
+
+    let place_state = get_place_state(&mut banks_client).await;
+    assert_eq!(place_state.owner, payer.pubkey());
+    assert_eq!(place_state.pending_owner, game_player.pubkey());
+
+    let cancel_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_cancel_ownership_transfer(
+            CANVAS_ID,
+            payer.pubkey(),
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(cancel_tx).await, Ok(()));
+
+    let place_state = get_place_state(&mut banks_client).await;
+    assert_eq!(place_state.owner, payer.pubkey());
+    assert_eq!(place_state.pending_owner, Pubkey::default());
+
+    let cancelled_accept_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_accept_ownership(
+            CANVAS_ID,
+            game_player.pubkey(),
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(cancelled_accept_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(67))
+        ))
+    );
+
+    // Only the proposed owner can accept
+
+    let propose_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_propose_owner(
+            CANVAS_ID,
+            payer.pubkey(),
+            new_owner.pubkey(),
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(propose_tx).await, Ok(()));
+
+    let wrong_accept_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_accept_ownership(
+            CANVAS_ID,
+            game_player.pubkey(),
+        )],
+        Some(&payer.pubkey()),
+        &[&payer, &game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(wrong_accept_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(66))
+        ))
+    );
+
+    let accept_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_accept_ownership(
+            CANVAS_ID,
+            new_owner.pubkey(),
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player, &new_owner],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(accept_tx).await, Ok(()));
+
+    let place_state = get_place_state(&mut banks_client).await;
+    assert_eq!(place_state.owner, new_owner.pubkey());
+    assert_eq!(place_state.pending_owner, Pubkey::default());
+
+    // The old owner lost every role along with ownership
+
+    let old_owner_propose_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_propose_owner(
+            CANVAS_ID,
+            payer.pubkey(),
+            payer.pubkey(),
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(old_owner_propose_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(7))
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_initialize_place() {
+    let pt = place_program_test();
+    let pt_ctx = pt.start_with_context().await;
+
+    let mut banks_client = pt_ctx.banks_client;
+    let payer = pt_ctx.payer;
+    let recent_blockhash = pt_ctx.last_blockhash;
+
+    let upgrade_authority = place_upgrade_authority();
+    let new_owner = Keypair::new();
+
+    let init_ix = |upgrade_authority: Pubkey, paintbrush_cooldown: Option<u64>| {
+        instruction::get_ix_initialize_place(
+            CANVAS_ID,
+            payer.pubkey(),
+            upgrade_authority,
+            new_owner.pubkey(),
+            None,
+            paintbrush_cooldown,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+    };
+
+    // Updating no longer creates the place state
+
+    let update_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_update_place_state(
+            payer.pubkey(),
+            UpdatePlaceStateDataArgs {
+                canvas_id: CANVAS_ID,
+                ..Default::default()
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(update_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(71))
+        ))
+    );
+
+    // Only the upgrade authority can initialize, checked against the real program data account
+
+    let not_authority = Keypair::new();
+    let not_authority_tx = Transaction::new_signed_with_payer(
+        &[init_ix(not_authority.pubkey(), None)],
+        Some(&payer.pubkey()),
+        &[&payer, &not_authority],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(not_authority_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(69))
+        ))
+    );
+
+    let mut wrong_program_data_ix = init_ix(upgrade_authority.pubkey(), None);
+    wrong_program_data_ix.accounts[3].pubkey = payer.pubkey();
+    let wrong_program_data_tx = Transaction::new_signed_with_payer(
+        &[wrong_program_data_ix],
+        Some(&payer.pubkey()),
+        &[&payer, &upgrade_authority],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(wrong_program_data_tx)
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(68))
+        ))
+    );
+
+    // The upgrade authority picks the owner, unset parameters fall back to their defaults
+
+    let init_tx = Transaction::new_signed_with_payer(
+        &[init_ix(upgrade_authority.pubkey(), Some(5))],
+        Some(&payer.pubkey()),
+        &[&payer, &upgrade_authority],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(init_tx).await, Ok(()));
+
+    let place_state = get_place_state(&mut banks_client).await;
+    assert_eq!(place_state.owner, new_owner.pubkey());
+    assert_eq!(place_state.paintbrush_cooldown, 5);
+    assert_eq!(
+        place_state.bomb_price,
+        solana_place::state::DEFAULT_BOMB_PRICE
+    );
+    assert_eq!(
+        place_state.paintbrush_price,
+        solana_place::state::DEFAULT_PAINTBRUSH_PRICE
+    );
+
+    // A place can only be initialized once
+
+    let reinit_tx = Transaction::new_signed_with_payer(
+        &[init_ix(upgrade_authority.pubkey(), Some(6))],
+        Some(&payer.pubkey()),
+        &[&payer, &upgrade_authority],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(reinit_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(70))
+        ))
+    );
+}
+
+fn program_owned_account(data: Vec<u8>) -> Account {
+    Account {
+        lamports: solana_sdk::rent::Rent::default().minimum_balance(data.len()),
+        data,
+        owner: solana_place::id(),
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+#[tokio::test]
+async fn test_migrate_accounts() {
+    let migrator = Keypair::new();
+
+    // version 0 fixtures of each account type, written out byte by byte in the layouts
+    // the program was first deployed with
+    let mut place_state_v0 = vec![PlaceAccountType::PlaceState as u8];
+    place_state_v0.extend_from_slice(migrator.pubkey().as_ref()); // owner
+    place_state_v0.push(1); // is_frozen
+    place_state_v0.extend_from_slice(&1_000u64.to_le_bytes()); // paintbrush_price
+    place_state_v0.extend_from_slice(&10u64.to_le_bytes()); // paintbrush_cooldown
+    place_state_v0.extend_from_slice(&3_000u64.to_le_bytes()); // bomb_price
+    assert_eq!(place_state_v0.len(), 58);
+    assert_eq!(place_state_v0.len(), PlaceState::LEN_V0);
+
+    let place_state = PlaceState::from_bytes(&place_state_v0).unwrap();
+    assert_eq!(
+        place_state.version,
+        solana_place::state::CURRENT_ACCOUNT_VERSION
+    );
+    assert_eq!(place_state.canvas_id, solana_place::state::LEGACY_CANVAS_ID);
+    assert_eq!(place_state.owner, migrator.pubkey());
+    assert_eq!(place_state.paused, PauseFlags::FROZEN);
+    assert_eq!(place_state.paintbrush_price, 1_000);
+    assert_eq!(place_state.paintbrush_cooldown, 10);
+    assert_eq!(place_state.bomb_price, 3_000);
+    assert_eq!(
+        place_state.width_px,
+        solana_place::state::DEFAULT_PLACE_WIDTH_PX
+    );
+    assert_eq!(
+        place_state.patch_size_px,
+        solana_place::state::DEFAULT_PATCH_SIZE_PX
+    );
+    assert_eq!(place_state.pixel_format, PixelFormat::Indexed8);
+    assert_eq!(
+        place_state.shield_price,
+        solana_place::state::DEFAULT_SHIELD_PRICE
+    );
+    assert_eq!(place_state.season, 0);
+
+    let random_seed = 42;
+    let meta_v0 = baseline_gameplay_token_meta(random_seed, 7);
+    assert_eq!(meta_v0.len(), GameplayTokenMeta::LEN_V0);
+    let meta = GameplayTokenMeta {
+        acct_type: PlaceAccountType::GameplayTokenMeta,
+        version: solana_place::state::CURRENT_ACCOUNT_VERSION,
+        canvas_id: solana_place::state::LEGACY_CANVAS_ID,
+        gameplay_type: GameplayTokenType::PaintBrush,
+        created_at_slot: 3,
+        random_seed,
+        token_mint_pda: GameplayTokenMeta::token_mint_pda(random_seed).0,
+        update_allowed_slot: 5,
+        cooldown_duration: 750,
+        place_tokens_owed: 7,
+        purchase_price: 0,
+        upgrade_level: 0,
+        is_banned: false,
+        season: 0,
+        reserved: [0; solana_place::state::GAMEPLAY_TOKEN_META_RESERVED_LEN],
+    };
+    assert_eq!(GameplayTokenMeta::from_bytes(&meta_v0).unwrap(), meta);
+
+    let mut pixels = vec![0; PATCH_SIZE_PX * PATCH_SIZE_PX];
+    pixels[0] = 9;
+    pixels[PATCH_SIZE_PX * PATCH_SIZE_PX - 1] = 11;
+    let mut patch_v0 = vec![PlaceAccountType::Patch as u8, 1, 0]; // acct_type, x, y
+    patch_v0.extend_from_slice(&(pixels.len() as u32).to_le_bytes());
+    patch_v0.extend_from_slice(&pixels);
+    assert_eq!(patch_v0.len(), 407);
+    assert!(Patch::is_v0_layout(&patch_v0));
+
+    let patch = Patch {
+        acct_type: PlaceAccountType::Patch,
+        version: solana_place::state::CURRENT_ACCOUNT_VERSION,
+        canvas_id: solana_place::state::LEGACY_CANVAS_ID,
+        format: PixelFormat::Indexed8,
+        x: 1,
+        y: 0,
+        shield: solana_place::state::PatchShield::default(),
+        reserved: [0; solana_place::state::PATCH_RESERVED_LEN],
+        pixels,
+    };
+    assert!(!Patch::is_v0_layout(&patch.try_to_vec().unwrap()));
+    assert_eq!(Patch::from_bytes(&patch_v0).unwrap(), patch);
+
+    // the version and canvas id of a current patch can look like a version 0 pixel count
+    let mut other_canvas_patch = patch.clone();
+    other_canvas_patch.canvas_id = 116_224;
+    let other_canvas_bytes = other_canvas_patch.try_to_vec().unwrap();
+    assert!(!Patch::is_v0_layout(&other_canvas_bytes));
+    assert_eq!(
+        Patch::from_bytes(&other_canvas_bytes).unwrap(),
+        other_canvas_patch
+    );
+
+    // version 0 place states and patches live at addresses without a canvas id, gameplay
+    // token metas never had one in their seeds
+    let (legacy_place_state_pda, _) = PlaceState::legacy_pda();
+    let (legacy_patch_pda, _) = Patch::legacy_pda(1, 0);
+    let (place_state_pda, _) = PlaceState::pda(CANVAS_ID);
+    let (meta_pda, _) = GameplayTokenMeta::pda(random_seed);
+    let (patch_pda, _) = find_address_for_patch(CANVAS_ID, 1, 0, &solana_place::id());
+
+    let mut pt = place_program_test();
+    pt.add_account(
+        legacy_place_state_pda,
+        program_owned_account(place_state_v0),
+    );
+    pt.add_account(meta_pda, program_owned_account(meta_v0));
+    pt.add_account(legacy_patch_pda, program_owned_account(patch_v0));
+    pt.add_account(
+        migrator.pubkey(),
+        Account {
+            lamports: 1_000_000_000,
+            data: vec![],
+            owner: solana_program::system_program::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    let pt_ctx = pt.start_with_context().await;
+    let mut banks_client = pt_ctx.banks_client;
+    let payer = pt_ctx.payer;
+    let recent_blockhash = pt_ctx.last_blockhash;
+
+    let migrate = |payer: &Keypair, account: Pubkey| {
+        Transaction::new_signed_with_payer(
+            &[instruction::get_ix_migrate_account(payer.pubkey(), account)],
+            Some(&payer.pubkey()),
+            &[payer],
+            recent_blockhash,
+        )
+    };
+
+    // the place state of the legacy canvas doesn't exist until it is moved out of its legacy address
+    let update_ix = instruction::get_ix_update_place_state(
+        migrator.pubkey(),
+        UpdatePlaceStateDataArgs {
+            canvas_id: CANVAS_ID,
+            paintbrush_price: Some(2_000),
+            ..Default::default()
+        },
+    );
+    let update_tx = Transaction::new_signed_with_payer(
+        &[update_ix.clone()],
+        Some(&migrator.pubkey()),
+        &[&migrator],
+        recent_blockhash,
+    );
+    assert_matches!(banks_client.process_transaction(update_tx).await, Err(_));
+
+    let migrate_legacy = |payer: &Keypair, legacy: Pubkey, destination: Pubkey| {
+        Transaction::new_signed_with_payer(
+            &[instruction::get_ix_migrate_legacy_account(
+                payer.pubkey(),
+                legacy,
+                destination,
+            )],
+            Some(&payer.pubkey()),
+            &[payer],
+            recent_blockhash,
+        )
+    };
+
+    // legacy accounts can't be migrated in place or moved anywhere but their canvas pda
+    assert_matches!(
+        banks_client
+            .process_transaction(migrate(&payer, legacy_place_state_pda))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(94))
+        ))
+    );
+    assert_matches!(
+        banks_client
+            .process_transaction(migrate_legacy(&payer, legacy_patch_pda, place_state_pda))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(94))
+        ))
+    );
+
+    let rent = banks_client.get_rent().await.unwrap();
+    for (legacy, address, len) in [
+        (
+            Some(legacy_place_state_pda),
+            place_state_pda,
+            PlaceState::LEN,
+        ),
+        (None, meta_pda, GameplayTokenMeta::LEN),
+        (
+            Some(legacy_patch_pda),
+            patch_pda,
+            Patch::len_for_size(PATCH_SIZE_PX as u8, PixelFormat::Indexed8),
+        ),
+    ] {
+        let migrate_tx = match legacy {
+            Some(legacy) => migrate_legacy(&payer, legacy, address),
+            None => migrate(&payer, address),
+        };
+        assert_matches!(banks_client.process_transaction(migrate_tx).await, Ok(()));
+
+        // moved accounts are closed at their legacy address
+        if let Some(legacy) = legacy {
+            assert_eq!(banks_client.get_account(legacy).await.unwrap(), None);
+        }
+
+        let account = banks_client.get_account(address).await.unwrap().unwrap();
+        assert_eq!(account.data.len(), len);
+        assert_eq!(
+            account.data[1],
+            solana_place::state::CURRENT_ACCOUNT_VERSION
+        );
+        assert!(account.lamports >= rent.minimum_balance(len));
+
+        // migrating an account that is already current is refused
+        assert_matches!(
+            banks_client
+                .process_transaction(migrate(&migrator, address))
+                .await,
+            Err(TransportError::TransactionError(
+                TransactionError::InstructionError(0, InstructionError::Custom(72))
+            ))
+        );
+    }
+
+    let place_state_acct = banks_client
+        .get_account(place_state_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        PlaceState::from_bytes(&place_state_acct.data).unwrap(),
+        place_state
+    );
+
+    let meta_acct = banks_client.get_account(meta_pda).await.unwrap().unwrap();
+    assert_eq!(
+        GameplayTokenMeta::from_bytes(&meta_acct.data).unwrap(),
+        meta
+    );
+
+    let patch_acct = banks_client.get_account(patch_pda).await.unwrap().unwrap();
+    assert_eq!(Patch::from_bytes(&patch_acct.data).unwrap(), patch);
+
+    // accounts from a newer version of the program aren't guessed at
+    let mut newer_patch = patch_acct.data.clone();
+    newer_patch[1] = solana_place::state::CURRENT_ACCOUNT_VERSION + 1;
+    assert_eq!(
+        Patch::from_bytes(&newer_patch),
+        Err(solana_program::program_error::ProgramError::Custom(93))
+    );
+
+    // once migrated the account can be written again
+    let update_tx = Transaction::new_signed_with_payer(
+        &[update_ix],
+        Some(&payer.pubkey()),
+        &[&payer, &migrator],
+        recent_blockhash,
+    );
+    assert_matches!(banks_client.process_transaction(update_tx).await, Ok(()));
+
+    let place_state_acct = banks_client
+        .get_account(place_state_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let updated_state = PlaceState::from_bytes(&place_state_acct.data).unwrap();
+    assert_eq!(updated_state.paintbrush_price, 2_000);
+    assert_eq!(updated_state.bomb_price, 3_000);
+}
+
+#[tokio::test]
+async fn test_moderation() {
+    let program_id = solana_place::id();
+    let (mut banks_client, payer, game_player, recent_blockhash) = start_place_test().await;
+
+    let moderator = Keypair::new();
+
+    let (place_token_mint_pda, _) = PlaceState::token_mint_pda(CANVAS_ID);
+    let place_tokens_ata = spl_associated_token_account::get_associated_token_address(
+        &game_player.pubkey(),
+        &place_token_mint_pda,
+    );
+
+    let mut setup_ixs = vec![
+        instruction::get_ix_init_mint(CANVAS_ID, payer.pubkey()),
+        spl_associated_token_account::create_associated_token_account(
+            &payer.pubkey(),
+            &game_player.pubkey(),
+            &place_token_mint_pda,
+        ),
+    ];
+    for (patch_x, patch_y) in [(0u8, 0u8), (1, 0), (0, 1), (1, 1)] {
+        setup_ixs.push(instruction::get_ix_init_patch(
+            program_id,
+            CANVAS_ID,
+            payer.pubkey(),
+            patch_x,
+            patch_y,
+        ));
+    }
+
+    let setup_tx = Transaction::new_signed_with_payer(
+        &setup_ixs,
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(setup_tx).await, Ok(()));
+
+    let random_seed = 31337;
+    let (gameplay_token_pda, gameplay_token_ata) = purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        random_seed,
+        GameplayTokenType::PaintBrush,
+        recent_blockhash,
+    )
+    .await;
+
+    let set_pixel_tx = |x_offset: u8, y_offset: u8| {
+        Transaction::new_signed_with_payer(
+            &[instruction::get_ix_set_pixel(
+                program_id,
+                CANVAS_ID,
+                game_player.pubkey(),
+                gameplay_token_pda,
+                gameplay_token_ata,
+                0,
+                0,
+                x_offset,
+                y_offset,
+                5,
+            )],
+            Some(&game_player.pubkey()),
+            &[&game_player],
+            recent_blockhash,
+        )
+    };
+
+    assert_matches!(
+        banks_client.process_transaction(set_pixel_tx(19, 19)).await,
+        Ok(())
+    );
+
+    // Only the moderator can clear a region
+
+    let place_state = get_place_state(&mut banks_client).await;
+    let clear_tx = |moderator: &Keypair, x_max: u16, pop_patch: bool| {
+        let mut clear_ix = instruction::get_ix_clear_region(
+            &place_state,
+            moderator.pubkey(),
+            15,
+            15,
+            x_max,
+            24,
+            3,
+        );
+        if pop_patch {
+            clear_ix.accounts.pop();
+        }
+        Transaction::new_signed_with_payer(
+            &[clear_ix],
+            Some(&payer.pubkey()),
+            &[&payer, moderator],
+            recent_blockhash,
+        )
+    };
+
+    assert_matches!(
+        banks_client
+            .process_transaction(clear_tx(&game_player, 24, false))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(63))
+        ))
+    );
+
+    let grant_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_grant_role(
+            CANVAS_ID,
+            payer.pubkey(),
+            PlaceRole::Moderator,
+            moderator.pubkey(),
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(grant_tx).await, Ok(()));
+
+    // The rectangle has to be inside the place, and every patch it touches passed in
+
+    assert_matches!(
+        banks_client
+            .process_transaction(clear_tx(&moderator, 1000, false))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(75))
+        ))
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(clear_tx(&moderator, 24, true))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(76))
+        ))
+    );
+
+    // Patches (2, 0) and (2, 1) were never initialized
+
+    assert_matches!(
+        banks_client
+            .process_transaction(clear_tx(&moderator, 44, false))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(96))
+        ))
+    );
+
+    // Clear a rectangle straddling the corner shared by four patches
+
+    assert_matches!(
+        banks_client
+            .process_transaction(clear_tx(&moderator, 24, false))
+            .await,
+        Ok(())
+    );
+
+    for (patch_x, patch_y, inside, outside) in [
+        (0u8, 0u8, (19, 19), (14, 14)),
+        (1, 0, (4, 15), (5, 14)),
+        (0, 1, (15, 4), (14, 5)),
+        (1, 1, (0, 0), (5, 5)),
+    ] {
+        let (patch_pda, _) = Patch::pda(CANVAS_ID, patch_x, patch_y);
+        let patch_acct = banks_client.get_account(patch_pda).await.unwrap().unwrap();
+        let patch = Patch::from_bytes(&patch_acct.data).unwrap();
+        assert_eq!(patch.pixel_at(inside.1 * PATCH_SIZE_PX + inside.0), 3);
+        assert_eq!(patch.pixel_at(outside.1 * PATCH_SIZE_PX + outside.0), 0);
+    }
+
+    // Banned tokens can't paint or claim
+
+    let ban_tx = |moderator: &Keypair, is_banned: bool| {
+        Transaction::new_signed_with_payer(
+            &[instruction::get_ix_ban_gameplay_token(
+                CANVAS_ID,
+                moderator.pubkey(),
+                gameplay_token_pda,
+                is_banned,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, moderator],
+            recent_blockhash,
+        )
+    };
+
+    assert_matches!(
+        banks_client
+            .process_transaction(ban_tx(&game_player, true))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(63))
+        ))
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(ban_tx(&moderator, true))
+            .await,
+        Ok(())
+    );
+
+    let gameplay_token_acct = banks_client
+        .get_account(gameplay_token_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let gameplay_token = GameplayTokenMeta::from_bytes(&gameplay_token_acct.data).unwrap();
+    assert!(gameplay_token.is_banned);
+
+    assert_matches!(
+        banks_client.process_transaction(set_pixel_tx(0, 0)).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(74))
+        ))
+    );
+
+    let claim_ix = instruction::get_ix_claim_tokens(
+        CANVAS_ID,
+        game_player.pubkey(),
+        place_tokens_ata,
+        gameplay_token.token_mint_pda,
+        random_seed,
+    );
+    let claim_tx = Transaction::new_signed_with_payer(
+        &[claim_ix.clone()],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(claim_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(74))
+        ))
+    );
+
+    // Lifting the ban lets the token claim again
+
+    assert_matches!(
+        banks_client
+            .process_transaction(ban_tx(&moderator, false))
+            .await,
+        Ok(())
+    );
+
+    let claim_tx = Transaction::new_signed_with_payer(
+        &[claim_ix],
+        Some(&payer.pubkey()),
+        &[&payer, &game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(claim_tx).await, Ok(()));
+}
+
+#[tokio::test]
+async fn test_allowlist_purchase() {
+    let (mut banks_client, payer, game_player, recent_blockhash) = start_place_test().await;
+
+    // An odd number of entries, so one of them is carried up a layer without a sibling
+
+    let entries = [
+        (game_player.pubkey(), 2u16),
+        (Pubkey::new_unique(), 1),
+        (Pubkey::new_unique(), 1),
+    ];
+    let tree = AllowlistTree::new(&entries);
+    let root = tree.root();
+
+    for (index, (wallet, max_purchases)) in entries.iter().enumerate() {
+        let proof = tree.proof(index);
+        assert!(verify_allowlist_proof(
+            &root,
+            allowlist_leaf(wallet, *max_purchases),
+            &proof
+        ));
+        assert!(!verify_allowlist_proof(
+            &root,
+            allowlist_leaf(wallet, *max_purchases + 1),
+            &proof
+        ));
+    }
+
+    assert_eq!(
+        AllowlistTree::from_wallets(&[game_player.pubkey()], 2).root(),
+        allowlist_leaf(&game_player.pubkey(), 2)
+    );
+    assert_eq!(AllowlistTree::new(&[]).root(), [0; 32]);
+
+    let update_phases_tx = |allowlist_start_slot: u64, allowlist_end_slot: u64| {
+        Transaction::new_signed_with_payer(
+            &[instruction::get_ix_update_place_state(
+                payer.pubkey(),
+                UpdatePlaceStateDataArgs {
+                    canvas_id: CANVAS_ID,
+                    purchase_phases: Some(PurchasePhases {
+                        allowlist_root: root,
+                        allowlist_start_slot,
+                        allowlist_end_slot,
+                    }),
+                    ..Default::default()
+                },
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        )
+    };
+
+    let purchase_tx =
+        |buyer: &Keypair, random_seed: u64, allowlist_proof: Option<AllowlistProof>| {
+            Transaction::new_signed_with_payer(
+                &[instruction::get_ix_purchase_gameplay_token_with_proof(
+                    CANVAS_ID,
+                    buyer.pubkey(),
+                    random_seed,
+                    GameplayTokenType::PaintBrush,
+                    solana_place::state::DEFAULT_PAINTBRUSH_PRICE,
+                    instruction::PaymentMethod::Lamports,
+                    allowlist_proof,
+                )],
+                Some(&buyer.pubkey()),
+                &[buyer],
+                recent_blockhash,
+            )
+        };
+
+    let game_player_proof = |max_purchases: u16| {
+        Some(AllowlistProof {
+            max_purchases,
+            proof: tree.proof(0),
+        })
+    };
+
+    // Nobody can purchase before the allowlist phase starts
+
+    assert_matches!(
+        banks_client
+            .process_transaction(update_phases_tx(1_000_000, 2_000_000))
+            .await,
+        Ok(())
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(purchase_tx(&game_player, 1, game_player_proof(2)))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(77))
+        ))
+    );
+
+    // During the allowlist phase a valid proof is needed
+
+    assert_matches!(
+        banks_client
+            .process_transaction(update_phases_tx(0, 1_000_000))
+            .await,
+        Ok(())
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(purchase_tx(&game_player, 2, None))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(78))
+        ))
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(purchase_tx(&game_player, 3, game_player_proof(5)))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(79))
+        ))
+    );
+
+    // a proof is only good for the wallet it was made for
+    assert_matches!(
+        banks_client
+            .process_transaction(purchase_tx(&payer, 4, game_player_proof(2)))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(79))
+        ))
+    );
+
+    for random_seed in [5, 6] {
+        assert_matches!(
+            banks_client
+                .process_transaction(purchase_tx(&game_player, random_seed, game_player_proof(2)))
+                .await,
+            Ok(())
+        );
+    }
+
+    let (allowlist_receipt_pda, _) = AllowlistReceipt::pda(CANVAS_ID, &game_player.pubkey());
+    let allowlist_receipt_acct = banks_client
+        .get_account(allowlist_receipt_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let allowlist_receipt = AllowlistReceipt::from_bytes(&allowlist_receipt_acct.data).unwrap();
+    assert_eq!(allowlist_receipt.wallet, game_player.pubkey());
+    assert_eq!(allowlist_receipt.allowlist_root, root);
+    assert_eq!(allowlist_receipt.purchases, 2);
+
+    assert_matches!(
+        banks_client
+            .process_transaction(purchase_tx(&game_player, 7, game_player_proof(2)))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(80))
+        ))
+    );
+
+    // Once the allowlist phase ends anyone can purchase without a proof
+
+    assert_matches!(
+        banks_client
+            .process_transaction(update_phases_tx(0, 0))
+            .await,
+        Ok(())
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(purchase_tx(&game_player, 8, None))
+            .await,
+        Ok(())
+    );
+}
+
+#[tokio::test]
+async fn test_seasons() {
+    let program_id = solana_place::id();
+    let (mut banks_client, payer, game_player, recent_blockhash) =
+        start_place_test_with_layout(Some(40), Some(40), Some(20), None).await;
+
+    // Only the top row of patches is created, the bottom row is blank without existing
+
+    let (place_token_mint_pda, _) = PlaceState::token_mint_pda(CANVAS_ID);
+    let place_tokens_ata = spl_associated_token_account::get_associated_token_address(
+        &game_player.pubkey(),
+        &place_token_mint_pda,
+    );
+
+    let setup_tx = Transaction::new_signed_with_payer(
+        &[
+            instruction::get_ix_init_mint(CANVAS_ID, payer.pubkey()),
+            spl_associated_token_account::create_associated_token_account(
+                &payer.pubkey(),
+                &game_player.pubkey(),
+                &place_token_mint_pda,
+            ),
+            instruction::get_ix_init_patch(program_id, CANVAS_ID, payer.pubkey(), 0, 0),
+            instruction::get_ix_init_patch(program_id, CANVAS_ID, payer.pubkey(), 1, 0),
+        ],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(setup_tx).await, Ok(()));
+
+    let random_seed = 2468;
+    let (gameplay_token_pda, gameplay_token_ata) = purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        random_seed,
+        GameplayTokenType::PaintBrush,
+        recent_blockhash,
+    )
+    .await;
+
+    let set_pixel_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_set_pixel(
+            program_id,
+            CANVAS_ID,
+            game_player.pubkey(),
+            gameplay_token_pda,
+            gameplay_token_ata,
+            1,
+            0,
+            3,
+            3,
+            9,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(set_pixel_tx).await, Ok(()));
+
+    let gameplay_token_acct = banks_client
+        .get_account(gameplay_token_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let gameplay_token = GameplayTokenMeta::from_bytes(&gameplay_token_acct.data).unwrap();
+    assert_eq!(gameplay_token.season, 0);
+    assert!(gameplay_token.place_tokens_owed > 0);
+
+    // a second token that never claims what it earned this season
+    let unclaimed_random_seed = random_seed + 2;
+    let (unclaimed_gameplay_token_pda, unclaimed_gameplay_token_ata) = purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        unclaimed_random_seed,
+        GameplayTokenType::PaintBrush,
+        recent_blockhash,
+    )
+    .await;
+
+    let set_pixel_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_set_pixel(
+            program_id,
+            CANVAS_ID,
+            game_player.pubkey(),
+            unclaimed_gameplay_token_pda,
+            unclaimed_gameplay_token_ata,
+            0,
+            0,
+            4,
+            4,
+            9,
+        )],
+        Some(&game_player.pubkey()),
+        &[&game_player],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(set_pixel_tx).await, Ok(()));
+
+    let claim_tx = |fee_payer: &Keypair, random_seed: u64| {
+        Transaction::new_signed_with_payer(
+            &[instruction::get_ix_claim_tokens(
+                CANVAS_ID,
+                game_player.pubkey(),
+                place_tokens_ata,
+                GameplayTokenMeta::token_mint_pda(random_seed).0,
+                random_seed,
+            )],
+            Some(&fee_payer.pubkey()),
+            &[fee_payer, &game_player],
+            recent_blockhash,
+        )
+    };
+
+    let start_season_tx = |owner: &Keypair, finished_season: u32| {
+        Transaction::new_signed_with_payer(
+            &[instruction::get_ix_start_new_season(
+                CANVAS_ID,
+                owner.pubkey(),
+                finished_season,
+            )],
+            Some(&owner.pubkey()),
+            &[owner],
+            recent_blockhash,
+        )
+    };
+
+    // same instruction with someone else paying the fee, so retries aren't deduplicated
+    let start_season_paid_by_tx = |fee_payer: &Keypair, finished_season: u32| {
+        Transaction::new_signed_with_payer(
+            &[instruction::get_ix_start_new_season(
+                CANVAS_ID,
+                payer.pubkey(),
+                finished_season,
+            )],
+            Some(&fee_payer.pubkey()),
+            &[fee_payer, &payer],
+            recent_blockhash,
+        )
+    };
+
+    let freeze_tx = |fee_payer: &Keypair, is_frozen: bool| {
+        Transaction::new_signed_with_payer(
+            &[instruction::get_ix_update_place_state(
+                payer.pubkey(),
+                UpdatePlaceStateDataArgs {
+                    canvas_id: CANVAS_ID,
+                    paused: Some(if is_frozen {
+                        PauseFlags::FROZEN
+                    } else {
+                        PauseFlags::NONE
+                    }),
+                    ..Default::default()
+                },
+            )],
+            Some(&fee_payer.pubkey()),
+            &[fee_payer, &payer],
+            recent_blockhash,
+        )
+    };
+
+    // A new season needs the owner, a frozen canvas, and a seal of it
+
+    assert_matches!(
+        banks_client
+            .process_transaction(start_season_tx(&game_player, 0))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(7))
+        ))
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(start_season_tx(&payer, 1))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(36))
+        ))
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(freeze_tx(&payer, true))
+            .await,
+        Ok(())
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(start_season_paid_by_tx(&game_player, 0))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(82))
+        ))
+    );
+
+    let frozen_state = get_place_state(&mut banks_client).await;
+    let seal_tx = Transaction::new_signed_with_payer(
+        &[instruction::get_ix_seal_canvas(
+            &frozen_state,
+            payer.pubkey(),
+            0,
+            frozen_state.patch_count(),
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(seal_tx).await, Ok(()));
+
+    // claims stay open while frozen, so what was earned can be claimed before the season ends
+
+    assert_matches!(
+        banks_client
+            .process_transaction(claim_tx(&game_player, random_seed))
+            .await,
+        Ok(())
+    );
+
+    let place_tokens_acct = banks_client
+        .get_account(place_tokens_ata)
+        .await
+        .unwrap()
+        .unwrap();
+    let place_tokens = TokenAccount::unpack(&place_tokens_acct.data.as_slice()).unwrap();
+    assert_eq!(place_tokens.amount, gameplay_token.place_tokens_owed as u64);
+
+    let (snapshot_pda, _) = solana_place::state::CanvasSnapshot::pda(CANVAS_ID);
+    let snapshot_acct = banks_client
+        .get_account(snapshot_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let snapshot = solana_place::state::CanvasSnapshot::from_bytes(&snapshot_acct.data).unwrap();
+    assert!(snapshot.is_sealed);
+
+    // the archive has to be for the season that is ending
+
+    assert_matches!(
+        banks_client
+            .process_transaction(start_season_paid_by_tx(&game_player, 1))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(84))
+        ))
+    );
+
+    // Starting the season archives the seal and queues every patch to be reset
+
+    assert_matches!(
+        banks_client
+            .process_transaction(start_season_tx(&payer, 0))
+            .await,
+        Ok(())
+    );
+
+    let (season_archive_pda, _) = SeasonArchive::pda(CANVAS_ID, 0);
+    let season_archive_acct = banks_client
+        .get_account(season_archive_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let season_archive = SeasonArchive::from_bytes(&season_archive_acct.data).unwrap();
+    assert_eq!(season_archive.season, 0);
+    assert_eq!(season_archive.frozen_at_slot, frozen_state.frozen_at_slot);
+    assert_eq!(season_archive.snapshot_hash, snapshot.hash);
+
+    let state = get_place_state(&mut banks_client).await;
+    assert_eq!(state.season, 1);
+    assert_eq!(state.patches_to_reset, 4);
+
+    let snapshot_acct = banks_client
+        .get_account(snapshot_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let snapshot = solana_place::state::CanvasSnapshot::from_bytes(&snapshot_acct.data).unwrap();
+    assert!(!snapshot.is_sealed);
+
+    // the canvas stays frozen until every patch has been reset
+
+    assert_matches!(
+        banks_client
+            .process_transaction(freeze_tx(&payer, false))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(83))
+        ))
+    );
+
+    let mut odd_reset_ix = instruction::get_ix_reset_patches(&state, payer.pubkey(), 2);
+    odd_reset_ix.accounts.pop();
+    let odd_reset_tx = Transaction::new_signed_with_payer(
+        &[odd_reset_ix],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(odd_reset_tx).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(85))
+        ))
+    );
+
+    for _ in 0..2 {
+        let state = get_place_state(&mut banks_client).await;
+        let reset_tx = Transaction::new_signed_with_payer(
+            &[instruction::get_ix_reset_patches(&state, payer.pubkey(), 2)],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+
+        assert_matches!(banks_client.process_transaction(reset_tx).await, Ok(()));
+    }
+
+    let state = get_place_state(&mut banks_client).await;
+    assert_eq!(state.patches_to_reset, 0);
+
+    let (patch_pda, _) = Patch::pda(CANVAS_ID, 1, 0);
+    let patch_acct = banks_client.get_account(patch_pda).await.unwrap().unwrap();
+    let patch = Patch::from_bytes(&patch_acct.data).unwrap();
+    assert!(patch.pixels.iter().all(|pixel| *pixel == 0));
+
+    let (patch_attribution_pda, _) = PatchAttribution::pda(CANVAS_ID, 1, 0);
+    let patch_attribution_acct = banks_client
+        .get_account(patch_attribution_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let patch_attribution = PatchAttribution::from_bytes(&patch_attribution_acct.data).unwrap();
+    assert_eq!(patch_attribution.attribution_at(3, 3), None);
+    assert!(patch_attribution.pixels_painted_by(random_seed).is_empty());
+
+    assert_matches!(
+        banks_client
+            .process_transaction(freeze_tx(&game_player, false))
+            .await,
+        Ok(())
+    );
+
+    // Gameplay tokens carry over, but what they were owed last season is reset
+
+    assert_matches!(
+        banks_client
+            .process_transaction(claim_tx(&payer, unclaimed_random_seed))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(30))
+        ))
+    );
+
+    let unclaimed_gameplay_token_acct = banks_client
+        .get_account(unclaimed_gameplay_token_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let unclaimed_gameplay_token =
+        GameplayTokenMeta::from_bytes(&unclaimed_gameplay_token_acct.data).unwrap();
+    assert_eq!(unclaimed_gameplay_token.season, 0);
+    assert!(unclaimed_gameplay_token.place_tokens_owed > 0);
+
+    let place_tokens_acct = banks_client
+        .get_account(place_tokens_ata)
+        .await
+        .unwrap()
+        .unwrap();
+    let place_tokens = TokenAccount::unpack(&place_tokens_acct.data.as_slice()).unwrap();
+    assert_eq!(place_tokens.amount, gameplay_token.place_tokens_owed as u64);
+
+    let (new_gameplay_token_pda, _) = purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        random_seed + 1,
+        GameplayTokenType::PaintBrush,
+        recent_blockhash,
+    )
+    .await;
+
+    let new_gameplay_token_acct = banks_client
+        .get_account(new_gameplay_token_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let new_gameplay_token = GameplayTokenMeta::from_bytes(&new_gameplay_token_acct.data).unwrap();
+    assert_eq!(new_gameplay_token.season, 1);
+}
+
+#[tokio::test]
+async fn test_pause_flags() {
+    let program_id = solana_place::id();
+    let (mut banks_client, payer, game_player, recent_blockhash) = start_place_test().await;
+
+    let (place_token_mint_pda, _) = PlaceState::token_mint_pda(CANVAS_ID);
+    let place_tokens_ata = spl_associated_token_account::get_associated_token_address(
+        &game_player.pubkey(),
+        &place_token_mint_pda,
+    );
+
+    let setup_tx = Transaction::new_signed_with_payer(
+        &[
+            instruction::get_ix_init_mint(CANVAS_ID, payer.pubkey()),
+            spl_associated_token_account::create_associated_token_account(
+                &payer.pubkey(),
+                &game_player.pubkey(),
+                &place_token_mint_pda,
+            ),
+            instruction::get_ix_init_patch(program_id, CANVAS_ID, payer.pubkey(), 0, 0),
+        ],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(banks_client.process_transaction(setup_tx).await, Ok(()));
+
+    let random_seed = 1357;
+    let (gameplay_token_pda, gameplay_token_ata) = purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        random_seed,
+        GameplayTokenType::PaintBrush,
+        recent_blockhash,
+    )
+    .await;
+
+    let shield_seed = 2468;
+    let (shield_pda, shield_ata) = purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        shield_seed,
+        GameplayTokenType::Shield,
+        recent_blockhash,
+    )
+    .await;
+
+    let pause_tx = |signer: &Keypair, paused: PauseFlags| {
+        Transaction::new_signed_with_payer(
+            &[instruction::get_ix_update_place_state(
+                signer.pubkey(),
+                UpdatePlaceStateDataArgs {
+                    canvas_id: CANVAS_ID,
+                    paused: Some(paused),
+                    ..Default::default()
+                },
+            )],
+            Some(&signer.pubkey()),
+            &[signer],
+            recent_blockhash,
+        )
+    };
+
+    let set_pixel_tx = |pixel: u16| {
+        Transaction::new_signed_with_payer(
+            &[instruction::get_ix_set_pixel(
+                program_id,
+                CANVAS_ID,
+                game_player.pubkey(),
+                gameplay_token_pda,
+                gameplay_token_ata,
+                0,
+                0,
+                1,
+                1,
+                pixel,
+            )],
+            Some(&game_player.pubkey()),
+            &[&game_player],
+            recent_blockhash,
+        )
+    };
+
+    let claim_tx = |fee_payer: &Keypair| {
+        Transaction::new_signed_with_payer(
+            &[instruction::get_ix_claim_tokens(
+                CANVAS_ID,
+                game_player.pubkey(),
+                place_tokens_ata,
+                GameplayTokenMeta::token_mint_pda(random_seed).0,
+                random_seed,
+            )],
+            Some(&fee_payer.pubkey()),
+            &[fee_payer, &game_player],
+            recent_blockhash,
+        )
+    };
+
+    let purchase_tx = |seed: u64| {
+        Transaction::new_signed_with_payer(
+            &[instruction::get_ix_purchase_gameplay_token(
+                CANVAS_ID,
+                game_player.pubkey(),
+                seed,
+                GameplayTokenType::PaintBrush,
+                solana_place::state::DEFAULT_PAINTBRUSH_PRICE,
+            )],
+            Some(&game_player.pubkey()),
+            &[&game_player],
+            recent_blockhash,
+        )
+    };
+
+    let shield_tx = |fee_payer: &Keypair| {
+        Transaction::new_signed_with_payer(
+            &[instruction::get_ix_activate_shield(
+                program_id,
+                CANVAS_ID,
+                game_player.pubkey(),
+                shield_pda,
+                shield_ata,
+                0,
+                0,
+                10,
+                10,
+            )],
+            Some(&fee_payer.pubkey()),
+            &[fee_payer, &game_player],
+            recent_blockhash,
+        )
+    };
+
+    let retire_tx = |fee_payer: &Keypair| {
+        Transaction::new_signed_with_payer(
+            &[instruction::get_ix_retire_gameplay_token(
+                CANVAS_ID,
+                game_player.pubkey(),
+                place_tokens_ata,
+                random_seed + 1,
+            )],
+            Some(&fee_payer.pubkey()),
+            &[fee_payer, &game_player],
+            recent_blockhash,
+        )
+    };
+
+    let init_patch_tx = |patch_payer: &Keypair| {
+        Transaction::new_signed_with_payer(
+            &[instruction::get_ix_init_patch(
+                program_id,
+                CANVAS_ID,
+                patch_payer.pubkey(),
+                1,
+                0,
+            )],
+            Some(&patch_payer.pubkey()),
+            &[patch_payer],
+            recent_blockhash,
+        )
+    };
+
+    // Pausing needs the freeze admin role, and only known operations can be paused
+
+    assert_matches!(
+        banks_client
+            .process_transaction(pause_tx(&game_player, PauseFlags::ALL))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(61))
+        ))
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(pause_tx(&payer, PauseFlags(1 << 5)))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(90))
+        ))
+    );
+
+    // Pausing painting and claims leaves purchases and patch creation running
+
+    assert_matches!(
+        banks_client
+            .process_transaction(pause_tx(&payer, PauseFlags::PAINTING | PauseFlags::CLAIMS))
+            .await,
+        Ok(())
+    );
+
+    let state = get_place_state(&mut banks_client).await;
+    assert!(state.paused.contains(PauseFlags::PAINTING));
+    assert!(state.paused.contains(PauseFlags::CLAIMS));
+    assert!(!state.paused.contains(PauseFlags::PURCHASES));
+    assert!(!state.is_frozen());
+
+    assert_matches!(
+        banks_client.process_transaction(set_pixel_tx(4)).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(87))
+        ))
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(claim_tx(&game_player))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(88))
+        ))
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(purchase_tx(random_seed + 1))
+            .await,
+        Ok(())
+    );
+
+    // Shields are painting, and retiring a token pays out a claim
+
+    assert_matches!(
+        banks_client
+            .process_transaction(shield_tx(&game_player))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(87))
+        ))
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(retire_tx(&game_player))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(88))
+        ))
+    );
+
+    // A new set replaces the old one
+
+    assert_matches!(
+        banks_client
+            .process_transaction(pause_tx(
+                &payer,
+                PauseFlags::PURCHASES | PauseFlags::PATCH_CREATION
+            ))
+            .await,
+        Ok(())
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(purchase_tx(random_seed + 2))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(86))
+        ))
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(init_patch_tx(&payer))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(89))
+        ))
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(set_pixel_tx(5)).await,
+        Ok(())
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(claim_tx(&payer)).await,
+        Ok(())
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(shield_tx(&payer)).await,
+        Ok(())
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(retire_tx(&payer)).await,
+        Ok(())
+    );
+
+    // Everything runs again once nothing is paused
+
+    assert_matches!(
+        banks_client
+            .process_transaction(pause_tx(&payer, PauseFlags::NONE))
+            .await,
+        Ok(())
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(purchase_tx(random_seed + 3))
+            .await,
+        Ok(())
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(init_patch_tx(&game_player))
+            .await,
+        Ok(())
+    );
+}
+
+#[tokio::test]
+async fn test_init_patch_attribution() {
+    let program_id = solana_place::id();
+
+    // a patch created before attribution accounts existed
+    let patch = Patch {
+        acct_type: PlaceAccountType::Patch,
+        version: solana_place::state::CURRENT_ACCOUNT_VERSION,
+        canvas_id: CANVAS_ID,
+        format: PixelFormat::Indexed8,
+        x: 0,
+        y: 0,
+        shield: solana_place::state::PatchShield::default(),
+        reserved: [0; solana_place::state::PATCH_RESERVED_LEN],
+        pixels: vec![0; PATCH_SIZE_PX * PATCH_SIZE_PX],
+    };
+    let (patch_pda, _) = Patch::pda(CANVAS_ID, 0, 0);
+
+    let mut pt = place_program_test();
+    pt.add_account(
+        patch_pda,
+        program_owned_account(patch.try_to_vec().unwrap()),
+    );
+
+    let (mut banks_client, payer, game_player, recent_blockhash) =
+        start_place_test_with_program(pt, None, None, None, None).await;
+
+    let random_seed = 8080;
+    let (gameplay_token_pda, gameplay_token_ata) = purchase_gameplay_token(
+        &mut banks_client,
+        &game_player,
+        random_seed,
+        GameplayTokenType::PaintBrush,
+        recent_blockhash,
+    )
+    .await;
+
+    let set_pixel_tx = |pixel: u16| {
+        Transaction::new_signed_with_payer(
+            &[instruction::get_ix_set_pixel(
+                program_id,
+                CANVAS_ID,
+                game_player.pubkey(),
+                gameplay_token_pda,
+                gameplay_token_ata,
+                0,
+                0,
+                2,
+                3,
+                pixel,
+            )],
+            Some(&game_player.pubkey()),
+            &[&game_player],
+            recent_blockhash,
+        )
+    };
+
+    let init_attribution_tx = |attribution_payer: &Keypair, x_patch: u8| {
+        Transaction::new_signed_with_payer(
+            &[instruction::get_ix_init_patch_attribution(
+                CANVAS_ID,
+                attribution_payer.pubkey(),
+                x_patch,
+                0,
+            )],
+            Some(&attribution_payer.pubkey()),
+            &[attribution_payer],
+            recent_blockhash,
+        )
+    };
+
+    // The patch can't be painted until it has an attribution account
+
+    assert_matches!(
+        banks_client.process_transaction(set_pixel_tx(6)).await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(92))
+        ))
+    );
+
+    // Only existing patches get one
+
+    assert_matches!(
+        banks_client
+            .process_transaction(init_attribution_tx(&game_player, 1))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(1))
+        ))
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(init_attribution_tx(&game_player, 0))
+            .await,
+        Ok(())
+    );
+
+    assert_matches!(
+        banks_client
+            .process_transaction(init_attribution_tx(&payer, 0))
+            .await,
+        Err(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(91))
+        ))
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(set_pixel_tx(7)).await,
+        Ok(())
+    );
+
+    let (patch_attribution_pda, _) = PatchAttribution::pda(CANVAS_ID, 0, 0);
+    let patch_attribution_acct = banks_client
+        .get_account(patch_attribution_pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let patch_attribution = PatchAttribution::from_bytes(&patch_attribution_acct.data).unwrap();
+    assert_eq!(
+        patch_attribution.pixels_painted_by(random_seed),
+        vec![(2, 3)]
+    );
+}
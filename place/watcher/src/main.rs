@@ -2,7 +2,7 @@ use borsh::{maybestd::io::Error, BorshDeserialize, BorshSerialize};
 use png;
 use solana_client::pubsub_client;
 use solana_place;
-use solana_place::state::{Patch, PATCH_SIZE_PX, PLACE_HEIGHT_PX, PLACE_WIDTH_PX};
+use solana_place::state::{Patch, PixelFormat, PlaceState};
 use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::{client::SyncClient, commitment_config::CommitmentLevel};
@@ -15,10 +15,34 @@ use std::time::Duration;
 
 mod blend32;
 
+/// Decode a pixel of a patch into 8 bit RGB
+fn pixel_to_rgb(patch: &Patch, idx: usize) -> [u8; 3] {
+    let pixel = patch.pixel_at(idx);
+    match patch.format {
+        PixelFormat::Indexed8 => *blend32::Blend32::u8_to_rgb(pixel as u8),
+        PixelFormat::Rgb565 => {
+            let r = ((pixel >> 11) & 0x1f) as u8;
+            let g = ((pixel >> 5) & 0x3f) as u8;
+            let b = (pixel & 0x1f) as u8;
+            // replicate the high bits into the low bits so full intensity maps to 255
+            [
+                (r << 3) | (r >> 2),
+                (g << 2) | (g >> 4),
+                (b << 3) | (b >> 2),
+            ]
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // println!("cargo:rerun-if-env-changed=SOLANA_PLACE_PROGRAM_ID");
     let prog = solana_place::id();
+    // the canvas to render, a program deployment can host several
+    let canvas_id: u64 = std::env::var("TAPESTRY_CANVAS_ID")
+        .ok()
+        .and_then(|id| id.parse().ok())
+        .unwrap_or(0);
     let sync_options = SyncOptions {
         network: Network::Localhost,
         max_lag: None,
@@ -49,14 +73,32 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 Ok(slot) => {
                     println!("Got slot: {}", slot.slot);
                     if slot.slot % 100 == 0 {
-                        let place_height_px = PLACE_HEIGHT_PX as usize;
-                        let place_width_px = PLACE_WIDTH_PX as usize;
-                        let patch_size_px = PATCH_SIZE_PX as usize;
+                        // the canvas dimensions are set when the place is initialized
+                        let mut place_state: Option<PlaceState> = None;
+                        local.for_each_account(|_, account| {
+                            if let Ok(state) = PlaceState::from_bytes(account.data.as_slice()) {
+                                if state.canvas_id == canvas_id {
+                                    place_state = Some(state);
+                                }
+                            }
+                        });
+
+                        let place_state = match place_state {
+                            Some(state) => state,
+                            None => {
+                                println!("place state not found for canvas {}", canvas_id);
+                                continue;
+                            }
+                        };
+
+                        let place_height_px = place_state.height_px as usize;
+                        let place_width_px = place_state.width_px as usize;
+                        let patch_size_px = place_state.patch_size();
                         let pixel_data_size: usize =
                             (place_width_px * place_height_px * 3) as usize;
                         let mut pixel_data = vec![0u8; pixel_data_size];
                         let mut path = std::env::temp_dir();
-                        path.push(format!("{}.png", slot.slot));
+                        path.push(format!("{}-{}.png", canvas_id, slot.slot));
                         let file = File::create(path).unwrap();
                         let ref mut w = BufWriter::new(file);
                         let mut encoder =
@@ -67,8 +109,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             let data = account.data.as_slice();
                             let parse_result = Patch::from_bytes(data);
                             match parse_result {
-                                Ok(patch) => {
-                                    if patch.x >= 50 || patch.y >= 50 {
+                                Ok(patch) if patch.canvas_id == canvas_id => {
+                                    if !place_state.is_valid_patch(patch.x, patch.y)
+                                        || patch.format != place_state.pixel_format
+                                        || patch.pixel_count() != place_state.pixels_per_patch()
+                                    {
                                         println!("patch out of bounds");
                                         return;
                                     }
@@ -78,11 +123,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         "Patch X: {}, Patch Y: {}, Place X: {}, Place Y: {}",
                                         patch.x, patch.y, place_x_tl, place_y_tl
                                     );
-                                    let pixels = patch.pixels.as_slice().iter();
-                                    for (patch_idx, pixel) in pixels.enumerate() {
-                                        let pixel_rgb = blend32::Blend32::u8_to_rgb(*pixel);
-                                        let place_y = place_y_tl + (patch_idx / PATCH_SIZE_PX);
-                                        let place_x = place_x_tl + (patch_idx % PATCH_SIZE_PX);
+                                    for patch_idx in 0..patch.pixel_count() {
+                                        let pixel_rgb = pixel_to_rgb(&patch, patch_idx);
+                                        let place_y = place_y_tl + (patch_idx / patch_size_px);
+                                        let place_x = place_x_tl + (patch_idx % patch_size_px);
                                         let pixel_offset =
                                             ((place_y * place_width_px) + place_x) * 3;
                                         pixel_data[pixel_offset] = pixel_rgb[0];
@@ -90,7 +134,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         pixel_data[pixel_offset + 2] = pixel_rgb[2];
                                     }
                                 }
-                                Err(_) => {}
+                                _ => {}
                             }
                         });
 